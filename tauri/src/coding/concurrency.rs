@@ -0,0 +1,83 @@
+//! Concurrency helpers shared by coding modules
+
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Run `f` for every item with at most `limit` tasks in flight
+/// Results are returned in the same order as `items`, one per item
+/// A panic in `f` is resumed on the caller once it is joined, so no result goes missing silently
+pub async fn run_bounded<T, R, F, Fut>(items: Vec<T>, limit: usize, f: F) -> Vec<R>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    F: Fn(T) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = R> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(limit.max(1)));
+    let f = Arc::new(f);
+    let mut tasks = JoinSet::new();
+
+    for (index, item) in items.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        let f = f.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            (index, f(item).await)
+        });
+    }
+
+    let mut results: Vec<(usize, R)> = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            // The tasks are never aborted, a join error is always a panic
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_run_bounded_limits_and_keeps_order() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_seen = Arc::new(AtomicUsize::new(0));
+
+        let (in_flight_c, max_seen_c) = (in_flight.clone(), max_seen.clone());
+        let results = run_bounded((0..8).collect(), 3, move |i: usize| {
+            let in_flight = in_flight_c.clone();
+            let max_seen = max_seen_c.clone();
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_seen.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i * 2
+            }
+        })
+        .await;
+
+        assert_eq!(results, vec![0, 2, 4, 6, 8, 10, 12, 14]);
+        assert!(max_seen.load(Ordering::SeqCst) <= 3);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "item 2")]
+    async fn test_run_bounded_propagates_panics() {
+        run_bounded((0..4).collect(), 2, |i: usize| async move {
+            if i == 2 {
+                panic!("item {}", i);
+            }
+            i
+        })
+        .await;
+    }
+}
@@ -0,0 +1,154 @@
+//! Load pipeline timing shared by coding modules
+//!
+//! Lightweight counters recorded while configs, shell env and model caches are loaded,
+//! exposed for diagnosing slow startups.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Snapshot of the recorded load metrics
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadMetrics {
+    /// Duration of the last config list load (query + parse)
+    pub config_parse_ms: f64,
+    /// Number of configs in the last load
+    pub config_count: u64,
+    /// Duration of the last shell env resolution
+    pub shell_env_resolve_ms: f64,
+    pub model_cache_hits: u64,
+    pub model_cache_misses: u64,
+}
+
+/// Counters behind `LoadMetrics`, safe to update from any thread
+#[derive(Debug, Default)]
+pub struct LoadRecorder {
+    config_parse_us: AtomicU64,
+    config_count: AtomicU64,
+    shell_env_resolve_us: AtomicU64,
+    model_cache_hits: AtomicU64,
+    model_cache_misses: AtomicU64,
+}
+
+impl LoadRecorder {
+    pub const fn new() -> Self {
+        LoadRecorder {
+            config_parse_us: AtomicU64::new(0),
+            config_count: AtomicU64::new(0),
+            shell_env_resolve_us: AtomicU64::new(0),
+            model_cache_hits: AtomicU64::new(0),
+            model_cache_misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_config_load(&self, elapsed: Duration, count: usize) {
+        self.config_parse_us
+            .store(elapsed.as_micros() as u64, Ordering::Relaxed);
+        self.config_count.store(count as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_shell_env(&self, elapsed: Duration) {
+        self.shell_env_resolve_us
+            .store(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_model_cache(&self, hit: bool) {
+        let counter = if hit {
+            &self.model_cache_hits
+        } else {
+            &self.model_cache_misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> LoadMetrics {
+        let ms = |us: &AtomicU64| us.load(Ordering::Relaxed) as f64 / 1000.0;
+        LoadMetrics {
+            config_parse_ms: ms(&self.config_parse_us),
+            config_count: self.config_count.load(Ordering::Relaxed),
+            shell_env_resolve_ms: ms(&self.shell_env_resolve_us),
+            model_cache_hits: self.model_cache_hits.load(Ordering::Relaxed),
+            model_cache_misses: self.model_cache_misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+static LOAD_METRICS: LoadRecorder = LoadRecorder::new();
+
+/// Process-wide recorder
+pub fn recorder() -> &'static LoadRecorder {
+    &LOAD_METRICS
+}
+
+/// Current load metrics
+#[tauri::command]
+pub fn get_load_metrics() -> LoadMetrics {
+    recorder().snapshot()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_populated_after_load() {
+        use crate::coding::oh_my_opencode::adapter;
+        use crate::coding::oh_my_opencode::commands::list_configs;
+        use crate::coding::oh_my_opencode::types::OhMyOpenCodeConfigContent;
+        use surrealdb::engine::local::SurrealKv;
+
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-metrics-{}", uuid::Uuid::new_v4()));
+        let db = surrealdb::Surreal::new::<SurrealKv>(dir.join("database")).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        for name in ["work", "home", "oss"] {
+            db.query(format!("CREATE oh_my_opencode_config:`{}` CONTENT $data", name))
+                .bind((
+                    "data",
+                    adapter::to_db_value(&OhMyOpenCodeConfigContent {
+                        name: name.to_string(),
+                        is_applied: false,
+                        agents: None,
+                        other_fields: None,
+                        created_at: String::new(),
+                        updated_at: String::new(),
+                    }),
+                ))
+                .await
+                .unwrap();
+        }
+
+        let recorder = LoadRecorder::new();
+        assert_eq!(recorder.snapshot(), LoadMetrics::default());
+
+        let configs = list_configs(&db, None, &recorder).await.unwrap();
+        let metrics = recorder.snapshot();
+        assert_eq!(configs.len(), 3);
+        assert_eq!(metrics.config_count, 3);
+        assert!(metrics.config_parse_ms > 0.0);
+
+        drop(db);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_snapshot_reports_recorded_counters() {
+        let recorder = LoadRecorder::new();
+        recorder.record_config_load(Duration::from_micros(2500), 3);
+        recorder.record_shell_env(Duration::from_millis(12));
+        recorder.record_model_cache(true);
+        recorder.record_model_cache(true);
+        recorder.record_model_cache(false);
+
+        assert_eq!(
+            recorder.snapshot(),
+            LoadMetrics {
+                config_parse_ms: 2.5,
+                config_count: 3,
+                shell_env_resolve_ms: 12.0,
+                model_cache_hits: 2,
+                model_cache_misses: 1,
+            }
+        );
+    }
+}
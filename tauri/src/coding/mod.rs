@@ -4,5 +4,10 @@ pub mod open_code;
 pub mod oh_my_opencode;
 pub mod wsl;
 
+pub mod concurrency;
+pub mod debug_bundle;
+pub mod metrics;
+pub mod redact;
+
 mod db_id;
 pub use db_id::{db_clean_id, db_extract_id, db_extract_id_opt, db_build_id};
@@ -1,4 +1,6 @@
 use serde_json::{json, Value};
+use super::agent;
+use super::disabled;
 use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeConfigContent, OhMyOpenCodeGlobalConfig, OhMyOpenCodeGlobalConfigContent};
 use crate::coding::db_id::db_extract_id;
 
@@ -80,7 +82,8 @@ pub fn from_db_value(value: Value) -> OhMyOpenCodeConfig {
         is_applied,
         agents: value
             .get("agents")
-            .cloned(),
+            .filter(|v| !v.is_null())
+            .map(|v| agent::parse_agents(Some(v))),
         other_fields: value
             .get("other_fields")
             .or_else(|| value.get("otherFields"))
@@ -132,7 +135,9 @@ fn safe_to_string_array(value: &Value) -> Option<Vec<String>> {
 }
 
 /// Convert database Value to OhMyOpenCodeGlobalConfig with fault tolerance
-pub fn global_config_from_db_value(value: Value) -> OhMyOpenCodeGlobalConfig {
+/// Disabled lists stored under both casings are merged first, so neither spelling is lost
+pub fn global_config_from_db_value(mut value: Value) -> OhMyOpenCodeGlobalConfig {
+    disabled::merge_disabled_casings(&mut value);
     OhMyOpenCodeGlobalConfig {
         id: db_extract_id(&value),
         schema: value
@@ -0,0 +1,550 @@
+//! Oh My OpenCode Agent Module
+//!
+//! Typed view over the entries of a profile's `agents` map.
+//! Unknown keys are kept in `extra` so nothing is lost on round-trip.
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+use super::fingerprint;
+use super::types::OhMyOpenCodeConfig;
+
+/// Bucket used by `group_agents` for agents without a group
+pub const DEFAULT_AGENT_GROUP: &str = "default";
+
+/// Agent fields only used by AI Toolbox, removed before writing oh-my-opencode.json
+const UI_ONLY_FIELDS: &[&str] = &["group", "locked", "extends", "prompt_ref", "when", "depends_on"];
+
+/// Agent fields that do not change agent behavior, ignored by `agent_content_id`
+/// `description` is not one of them, oh-my-opencode shows it to the model choosing an agent
+const DISPLAY_ONLY_FIELDS: &[&str] = &["group", "locked", "color"];
+
+/// Reasoning effort accepted by reasoning models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+/// A single agent entry of the `agents` map
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    /// Name of a global prompt library entry, used when `prompt` is not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<IndexMap<String, bool>>,
+    /// Environment variables of the agent, values may use `{env:NAME}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<IndexMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(default, alias = "topP", skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Only written for models that support reasoning
+    #[serde(default, alias = "reasoningEffort", skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// Max thinking tokens, only written for models that support reasoning
+    #[serde(default, alias = "thinkingBudget", skip_serializing_if = "Option::is_none")]
+    pub thinking_budget: Option<u32>,
+    /// Name of another agent of the same profile this agent inherits from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    /// Agents this agent delegates to, used for the dependency graph only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
+    /// UI grouping only, never written to oh-my-opencode.json
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Locked agents are skipped by bulk edits, never written to oh-my-opencode.json
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub locked: bool,
+    /// All other agent fields, preserved as-is
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    /// A hand-written agent value that is not an object (a string, `null`), written back
+    /// verbatim so saving the profile does not drop it; the typed fields stay empty
+    #[serde(skip)]
+    pub raw: Option<Value>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl AgentProfile {
+    /// Parse a single agent value with fault tolerance
+    /// Fields that fail typed parsing (e.g. `temperature` is a string) are kept as-is in `extra`,
+    /// the other fields are still typed; a non-object value is kept in `raw`
+    pub fn from_value(value: &Value) -> Option<AgentProfile> {
+        let Some(obj) = value.as_object() else {
+            return Some(AgentProfile {
+                raw: Some(value.clone()),
+                ..Default::default()
+            });
+        };
+        match serde_json::from_value::<AgentProfile>(value.clone()) {
+            Ok(agent) => Some(agent),
+            Err(e) => {
+                eprintln!("Failed to parse agent, keeping invalid fields raw: {}", e);
+                let (valid, invalid): (Map<String, Value>, Map<String, Value>) =
+                    obj.clone().into_iter().partition(|(key, field)| {
+                        let single = Map::from_iter([(key.clone(), field.clone())]);
+                        serde_json::from_value::<AgentProfile>(Value::Object(single)).is_ok()
+                    });
+                let mut agent = serde_json::from_value::<AgentProfile>(Value::Object(valid)).unwrap_or_default();
+                agent.extra.extend(invalid);
+                Some(agent)
+            }
+        }
+    }
+
+    /// Convert back to a JSON value, a `raw` value as it was read
+    pub fn to_value(&self) -> Value {
+        if let Some(raw) = &self.raw {
+            return raw.clone();
+        }
+        serde_json::to_value(self).unwrap_or_else(|_| Value::Object(Map::new()))
+    }
+}
+
+impl OhMyOpenCodeConfig {
+    /// Typed agents of the profile, empty when it has none
+    pub fn typed_agents(&self) -> IndexMap<String, AgentProfile> {
+        self.agents.clone().unwrap_or_default()
+    }
+
+    /// The `agents` JSON of the profile, for content and file output
+    pub fn agents_value(&self) -> Option<Value> {
+        self.agents.as_ref().map(agents_to_value)
+    }
+}
+
+#[cfg(test)]
+impl OhMyOpenCodeConfig {
+    /// Profile `p1` ("Profile"), not applied, holding the given `agents` JSON
+    pub(crate) fn test_with_agents(agents: Value) -> Self {
+        OhMyOpenCodeConfig {
+            id: "p1".to_string(),
+            name: "Profile".to_string(),
+            is_applied: false,
+            agents: Some(parse_agents(Some(&agents))),
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+/// Parse the `agents` JSON of a profile into typed agents, keeping the original order
+/// Non-object entries are kept verbatim in `AgentProfile::raw`, never dropped
+pub fn parse_agents(agents: Option<&Value>) -> IndexMap<String, AgentProfile> {
+    let mut result = IndexMap::new();
+    if let Some(obj) = agents.and_then(|v| v.as_object()) {
+        for (name, value) in obj {
+            if !value.is_object() {
+                eprintln!("Agent '{}' is not an object, keeping it as written", name);
+            }
+            if let Some(agent) = AgentProfile::from_value(value) {
+                result.insert(name.clone(), agent);
+            }
+        }
+    }
+    result
+}
+
+/// Deserialize the `agents` field of a profile through `parse_agents`
+/// so one bad agent or field does not fail the whole config
+pub fn deserialize_agents<'de, D>(deserializer: D) -> Result<Option<IndexMap<String, AgentProfile>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Value>::deserialize(deserializer)?;
+    Ok(value.filter(|v| !v.is_null()).map(|v| parse_agents(Some(&v))))
+}
+
+/// Serialize the `agents` field of a profile through `agents_to_value`, so `raw` agents survive
+pub fn serialize_agents<S>(agents: &Option<IndexMap<String, AgentProfile>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    agents.as_ref().map(agents_to_value).serialize(serializer)
+}
+
+/// Convert typed agents back to the `agents` JSON of a profile
+pub fn agents_to_value(agents: &IndexMap<String, AgentProfile>) -> Value {
+    let mut map = Map::new();
+    for (name, agent) in agents {
+        map.insert(name.clone(), agent.to_value());
+    }
+    Value::Object(map)
+}
+
+/// Run a mutation on the typed agents of a profile and write the result back
+pub fn with_agents_mut<T>(
+    config: &mut OhMyOpenCodeConfig,
+    f: impl FnOnce(&mut IndexMap<String, AgentProfile>) -> T,
+) -> T {
+    let mut agents = config.agents.take().unwrap_or_default();
+    let result = f(&mut agents);
+    config.agents = Some(agents);
+    result
+}
+
+/// Remove AI Toolbox-only fields from the `agents` JSON before it is written to file
+pub fn strip_ui_fields(agents: &mut Value) {
+    if let Some(obj) = agents.as_object_mut() {
+        for agent in obj.values_mut() {
+            if let Some(agent_obj) = agent.as_object_mut() {
+                for field in UI_ONLY_FIELDS {
+                    agent_obj.remove(*field);
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Agent Groups
+// ============================================================================
+
+/// Group agent names by their `group` field
+/// Agents without a group are listed under `DEFAULT_AGENT_GROUP`
+pub fn group_agents(config: &OhMyOpenCodeConfig) -> BTreeMap<String, Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (name, agent) in config.typed_agents() {
+        let group = agent
+            .group
+            .unwrap_or_else(|| DEFAULT_AGENT_GROUP.to_string());
+        groups.entry(group).or_default().push(name);
+    }
+    for names in groups.values_mut() {
+        names.sort();
+    }
+    groups
+}
+
+/// Set or clear the group of an agent
+/// An empty group name clears the group
+pub fn set_agent_group(
+    config: &mut OhMyOpenCodeConfig,
+    agent_name: &str,
+    group: Option<String>,
+) -> Result<(), String> {
+    let group = group
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty());
+
+    with_agents_mut(config, |agents| match agents.get_mut(agent_name) {
+        Some(agent) => {
+            agent.group = group;
+            Ok(())
+        }
+        None => Err(format!("Agent '{}' not found", agent_name)),
+    })
+}
+
+// ============================================================================
+// Name Collisions
+// ============================================================================
+
+/// Comparison form of an agent name: trimmed, NFKC-normalized and lowercased
+pub fn normalize_agent_name(name: &str) -> String {
+    name.trim().nfkc().collect::<String>().to_lowercase()
+}
+
+/// Groups of agent keys that are distinct but equal once normalized, in profile order
+/// Meant for prompting a rename, opencode itself treats them as different agents
+pub fn find_agent_name_collisions(config: &OhMyOpenCodeConfig) -> Vec<Vec<String>> {
+    let mut groups: IndexMap<String, Vec<String>> = IndexMap::new();
+    for name in config.typed_agents().into_keys() {
+        groups.entry(normalize_agent_name(&name)).or_default().push(name);
+    }
+    groups.into_values().filter(|names| names.len() > 1).collect()
+}
+
+// ============================================================================
+// Content ID
+// ============================================================================
+
+/// Stable content-based id of an agent, independent of its key in the `agents` map
+/// Two agents with the same meaningful content get the same id
+pub fn agent_content_id(agent: &AgentProfile) -> String {
+    let mut value = agent.to_value();
+    if let Some(obj) = value.as_object_mut() {
+        for field in DISPLAY_ONLY_FIELDS {
+            obj.remove(*field);
+        }
+    }
+    fingerprint::fingerprint(&value)
+}
+
+// ============================================================================
+// Bulk Model Update
+// ============================================================================
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SetModelError {
+    #[error("Invalid model id '{0}', expected 'provider_id/model_id'")]
+    InvalidModel(String),
+    #[error("Agents not found: {}", .0.join(", "))]
+    NotFound(Vec<String>),
+    #[error("Agents are locked: {}", .0.join(", "))]
+    Locked(Vec<String>),
+}
+
+/// Check that a model id has the `provider_id/model_id` format used by OpenCode
+pub fn is_valid_model_id(model: &str) -> bool {
+    if model.chars().any(char::is_whitespace) {
+        return false;
+    }
+    match model.split_once('/') {
+        Some((provider_id, model_id)) => !provider_id.is_empty() && !model_id.is_empty(),
+        None => false,
+    }
+}
+
+/// Set the same model on several agents at once
+/// Nothing is changed unless every name exists and none of them is locked
+/// Returns the number of agents whose model actually changed
+pub fn set_model_for_agents(
+    config: &mut OhMyOpenCodeConfig,
+    names: &[String],
+    model: &str,
+) -> Result<usize, SetModelError> {
+    let model = model.trim();
+    if !is_valid_model_id(model) {
+        return Err(SetModelError::InvalidModel(model.to_string()));
+    }
+
+    let mut agents = config.typed_agents();
+
+    let missing: Vec<String> = names
+        .iter()
+        .filter(|name| !agents.contains_key(name.as_str()))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(SetModelError::NotFound(missing));
+    }
+
+    let locked: Vec<String> = names
+        .iter()
+        .filter(|name| agents[name.as_str()].locked)
+        .cloned()
+        .collect();
+    if !locked.is_empty() {
+        return Err(SetModelError::Locked(locked));
+    }
+
+    let mut changed = 0;
+    for name in names {
+        let agent = agents.get_mut(name.as_str()).expect("checked above");
+        if agent.model.as_deref() != Some(model) {
+            agent.model = Some(model.to_string());
+            changed += 1;
+        }
+    }
+
+    config.agents = Some(agents);
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config_with_agents(agents: Value) -> OhMyOpenCodeConfig {
+        OhMyOpenCodeConfig {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            is_applied: false,
+            agents: Some(parse_agents(Some(&agents))),
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_group_agents() {
+        let mut config = config_with_agents(json!({
+            "Sisyphus": { "model": "openai/gpt-4o" },
+            "oracle": { "model": "openai/o3" },
+            "explore": { "model": "qwen/qwen3", "temperature": 0.2 }
+        }));
+
+        set_agent_group(&mut config, "Sisyphus", Some("planning".to_string())).unwrap();
+        set_agent_group(&mut config, "oracle", Some("planning".to_string())).unwrap();
+
+        let groups = group_agents(&config);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["planning"], vec!["Sisyphus", "oracle"]);
+        assert_eq!(groups[DEFAULT_AGENT_GROUP], vec!["explore"]);
+
+        // Other fields survive regrouping
+        let explore = &config.typed_agents()["explore"];
+        assert_eq!(explore.temperature, Some(0.2));
+
+        assert!(set_agent_group(&mut config, "missing", Some("x".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_strip_ui_fields() {
+        let mut agents = json!({
+            "oracle": { "model": "openai/o3", "group": "planning" }
+        });
+        strip_ui_fields(&mut agents);
+        assert_eq!(agents, json!({ "oracle": { "model": "openai/o3" } }));
+    }
+
+    #[test]
+    fn test_set_model_for_agents() {
+        let mut config = config_with_agents(json!({
+            "Sisyphus": { "model": "openai/gpt-4o" },
+            "oracle": { "model": "openai/o3" },
+            "explore": { "model": "qwen/qwen3" }
+        }));
+
+        let names = vec!["Sisyphus".to_string(), "oracle".to_string()];
+        let changed = set_model_for_agents(&mut config, &names, "anthropic/claude-sonnet-4").unwrap();
+        assert_eq!(changed, 2);
+
+        let agents = config.typed_agents();
+        assert_eq!(agents["Sisyphus"].model.as_deref(), Some("anthropic/claude-sonnet-4"));
+        assert_eq!(agents["oracle"].model.as_deref(), Some("anthropic/claude-sonnet-4"));
+        assert_eq!(agents["explore"].model.as_deref(), Some("qwen/qwen3"));
+
+        // Already set, nothing changes
+        assert_eq!(set_model_for_agents(&mut config, &names, "anthropic/claude-sonnet-4").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_set_model_for_agents_errors() {
+        let mut config = config_with_agents(json!({
+            "Sisyphus": { "model": "openai/gpt-4o", "locked": true },
+            "oracle": { "model": "openai/o3" }
+        }));
+
+        assert_eq!(
+            set_model_for_agents(&mut config, &["oracle".to_string()], "gpt-4o"),
+            Err(SetModelError::InvalidModel("gpt-4o".to_string()))
+        );
+        assert_eq!(
+            set_model_for_agents(
+                &mut config,
+                &["oracle".to_string(), "ghost".to_string()],
+                "openai/gpt-4o"
+            ),
+            Err(SetModelError::NotFound(vec!["ghost".to_string()]))
+        );
+        assert_eq!(
+            set_model_for_agents(
+                &mut config,
+                &["Sisyphus".to_string(), "oracle".to_string()],
+                "openai/gpt-4.1"
+            ),
+            Err(SetModelError::Locked(vec!["Sisyphus".to_string()]))
+        );
+        // Failed calls leave the config untouched
+        let agents = config.typed_agents();
+        assert_eq!(agents["oracle"].model.as_deref(), Some("openai/o3"));
+    }
+
+    #[test]
+    fn test_agent_content_id() {
+        let a = AgentProfile::from_value(&json!({
+            "model": "openai/gpt-4o",
+            "temperature": 0.3,
+            "group": "coding"
+        }))
+        .unwrap();
+        let b = AgentProfile::from_value(&json!({
+            "temperature": 0.3,
+            "model": "openai/gpt-4o",
+            "color": "#ff0000"
+        }))
+        .unwrap();
+        assert_eq!(agent_content_id(&a), agent_content_id(&b));
+
+        let mut described = a.clone();
+        described.extra.insert("description".to_string(), json!("Reviews pull requests"));
+        assert_ne!(agent_content_id(&a), agent_content_id(&described));
+
+        let mut c = a.clone();
+        c.model = Some("openai/gpt-4.1".to_string());
+        assert_ne!(agent_content_id(&a), agent_content_id(&c));
+    }
+
+    #[test]
+    fn test_find_agent_name_collisions() {
+        let config = config_with_agents(json!({
+            "Builder": { "model": "openai/gpt-4o" },
+            "builder": {},
+            "Planner": {}
+        }));
+
+        assert_eq!(
+            find_agent_name_collisions(&config),
+            vec![vec!["Builder".to_string(), "builder".to_string()]]
+        );
+        // Surrounding whitespace and full-width forms collide as well
+        assert_eq!(normalize_agent_name(" ＢＵＩＬＤＥＲ "), "builder");
+    }
+
+    #[test]
+    fn test_typed_agents_from_db_value() {
+        let config = crate::coding::oh_my_opencode::adapter::from_db_value(json!({
+            "id": "oh_my_opencode_config:work",
+            "name": "Work",
+            "agents": {
+                "oracle": {
+                    "model": "openai/o3",
+                    "prompt": "Think hard.",
+                    "tools": { "bash": false },
+                    "env": { "GITHUB_TOKEN": "{env:GITHUB_TOKEN}" },
+                    "extends": "base",
+                    "group": "review",
+                    "temperature": 0.2,
+                    "top_p": 0.9,
+                    "color": "#ff0000"
+                },
+                "explore": { "model": "openai/gpt-4o", "temperature": "hot" },
+                "broken": "not an agent"
+            }
+        }));
+
+        let agents = config.typed_agents();
+        assert_eq!(agents.keys().collect::<Vec<_>>(), vec!["oracle", "explore", "broken"]);
+        let oracle = &agents["oracle"];
+        assert_eq!(oracle.model.as_deref(), Some("openai/o3"));
+        assert_eq!(oracle.env.as_ref().unwrap()["GITHUB_TOKEN"], "{env:GITHUB_TOKEN}");
+        assert_eq!(oracle.extends.as_deref(), Some("base"));
+        assert_eq!(oracle.group.as_deref(), Some("review"));
+        assert_eq!((oracle.temperature, oracle.top_p), (Some(0.2), Some(0.9)));
+        assert_eq!(oracle.extra.get("color"), Some(&json!("#ff0000")));
+
+        // The invalid field is kept raw, the rest is still typed
+        let explore = &agents["explore"];
+        assert_eq!(explore.model.as_deref(), Some("openai/gpt-4o"));
+        assert_eq!(explore.temperature, None);
+        assert_eq!(explore.extra.get("temperature"), Some(&json!("hot")));
+        assert_eq!(explore.to_value(), json!({ "model": "openai/gpt-4o", "temperature": "hot" }));
+
+        // A non-object agent is written back as it was, on save and when sent to the UI
+        assert_eq!(agents["broken"].raw, Some(json!("not an agent")));
+        assert_eq!(config.agents_value().unwrap()["broken"], json!("not an agent"));
+        assert_eq!(serde_json::to_value(&config).unwrap()["agents"]["broken"], json!("not an agent"));
+    }
+}
@@ -0,0 +1,601 @@
+//! Oh My OpenCode Backups Module
+//!
+//! Every apply first copies the live oh-my-opencode.json byte for byte into the backup
+//! directory and records an apply-log entry pointing at that copy, so the last apply can
+//! be reverted exactly, including external edits made to the file.
+//! A backup is removed with its log entry once the log drops it, `compact_backups` prunes further.
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// File of the apply log inside the backup directory
+const APPLY_LOG_FILE: &str = "apply-log.json";
+/// Oldest entries are dropped beyond this
+const MAX_APPLY_LOG_ENTRIES: usize = 50;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum BackupError {
+    #[error("Failed to access {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("Invalid apply log: {0}")]
+    InvalidLog(String),
+    #[error("Nothing to revert")]
+    NothingToRevert,
+    #[error("Backup '{0}' not found")]
+    MissingBackup(String),
+}
+
+fn io_error(path: &Path, e: impl ToString) -> BackupError {
+    BackupError::Io {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    }
+}
+
+/// A byte-exact copy of the live config file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Backup {
+    pub id: String,
+    pub created_at: String,
+    /// Config that was applied when the copy was taken, if known
+    pub config_id: Option<String>,
+    pub size: u64,
+}
+
+/// Which file an apply wrote
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApplyKind {
+    /// The main config file, the one the applied config describes
+    #[default]
+    Main,
+    /// An extra target such as a project config, see `targets`
+    Target,
+}
+
+/// One apply, newest last in the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyLogEntry {
+    pub applied_at: String,
+    pub config_id: String,
+    pub previous_config_id: Option<String>,
+    /// Copy of the file before the apply, `None` when there was no file
+    pub backup_id: Option<String>,
+    pub target_path: String,
+    /// Entries written before targets existed are main applies
+    #[serde(default)]
+    pub kind: ApplyKind,
+}
+
+/// Position of the newest apply to the main config file
+fn last_main_apply(log: &[ApplyLogEntry]) -> Option<usize> {
+    log.iter().rposition(|entry| entry.kind == ApplyKind::Main)
+}
+
+/// Backup directory inside the app data directory (`app.path().app_data_dir()`)
+pub fn backup_dir_in(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("oh-my-opencode").join("backups")
+}
+
+fn backup_path(backup_dir: &Path, id: &str) -> PathBuf {
+    backup_dir.join(format!("{}.bak", id))
+}
+
+/// Copy the live file into the backup directory
+/// Returns `None` when the live file does not exist
+pub fn backup_config(
+    live_path: &Path,
+    backup_dir: &Path,
+    config_id: Option<&str>,
+) -> Result<Option<Backup>, BackupError> {
+    let content = match fs::read(live_path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(io_error(live_path, e)),
+    };
+    fs::create_dir_all(backup_dir).map_err(|e| io_error(backup_dir, e))?;
+
+    let now = Local::now();
+    let id = format!(
+        "{}-{}",
+        now.format("%Y%m%dT%H%M%S%.3f"),
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let path = backup_path(backup_dir, &id);
+    fs::write(&path, &content).map_err(|e| io_error(&path, e))?;
+
+    Ok(Some(Backup {
+        id,
+        created_at: now.to_rfc3339(),
+        config_id: config_id.map(str::to_string),
+        size: content.len() as u64,
+    }))
+}
+
+/// Stored bytes of a backup
+pub fn read_backup(backup_dir: &Path, id: &str) -> Result<Vec<u8>, BackupError> {
+    let path = backup_path(backup_dir, id);
+    if !path.exists() {
+        return Err(BackupError::MissingBackup(id.to_string()));
+    }
+    fs::read(&path).map_err(|e| io_error(&path, e))
+}
+
+pub fn read_apply_log(backup_dir: &Path) -> Result<Vec<ApplyLogEntry>, BackupError> {
+    let path = backup_dir.join(APPLY_LOG_FILE);
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| BackupError::InvalidLog(e.to_string())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(io_error(&path, e)),
+    }
+}
+
+fn write_apply_log(backup_dir: &Path, entries: &[ApplyLogEntry]) -> Result<(), BackupError> {
+    fs::create_dir_all(backup_dir).map_err(|e| io_error(backup_dir, e))?;
+    let path = backup_dir.join(APPLY_LOG_FILE);
+    let content = serde_json::to_string_pretty(entries).map_err(|e| BackupError::InvalidLog(e.to_string()))?;
+    fs::write(&path, content).map_err(|e| io_error(&path, e))
+}
+
+/// Append an apply of the main config file to the log, linked to the backup taken just before it
+pub fn record_apply(
+    live_path: &Path,
+    backup_dir: &Path,
+    previous_config_id: Option<&str>,
+    config_id: &str,
+    backup: Option<Backup>,
+) -> Result<ApplyLogEntry, BackupError> {
+    push_entry(backup_dir, ApplyLogEntry {
+        applied_at: Local::now().to_rfc3339(),
+        config_id: config_id.to_string(),
+        previous_config_id: previous_config_id.map(str::to_string),
+        backup_id: backup.map(|b| b.id),
+        target_path: live_path.to_string_lossy().to_string(),
+        kind: ApplyKind::Main,
+    })
+}
+
+/// Append an apply to an extra target, never reverted or protected as the current apply
+pub fn record_target_apply(
+    target_path: &Path,
+    backup_dir: &Path,
+    config_id: &str,
+    backup: Option<Backup>,
+) -> Result<ApplyLogEntry, BackupError> {
+    push_entry(backup_dir, ApplyLogEntry {
+        applied_at: Local::now().to_rfc3339(),
+        config_id: config_id.to_string(),
+        previous_config_id: None,
+        backup_id: backup.map(|b| b.id),
+        target_path: target_path.to_string_lossy().to_string(),
+        kind: ApplyKind::Target,
+    })
+}
+
+fn push_entry(backup_dir: &Path, entry: ApplyLogEntry) -> Result<ApplyLogEntry, BackupError> {
+    let mut log = read_apply_log(backup_dir)?;
+    log.push(entry.clone());
+    let excess = log.len().saturating_sub(MAX_APPLY_LOG_ENTRIES);
+    let dropped: Vec<ApplyLogEntry> = log.drain(..excess).collect();
+    write_apply_log(backup_dir, &log)?;
+
+    // Nothing can revert to the backups of dropped entries anymore
+    for backup_id in dropped.into_iter().filter_map(|entry| entry.backup_id) {
+        let path = backup_path(backup_dir, &backup_id);
+        if let Err(e) = fs::remove_file(&path) {
+            eprintln!("Failed to remove backup {}: {}", path.display(), e);
+        }
+    }
+    Ok(entry)
+}
+
+/// Back up the live file, run `write` and log the apply
+/// Only a failing `write` is an error. A failed backup is logged and the file is written
+/// anyway, without a log entry since the apply could not be reverted; a failure to record
+/// the apply is logged too. Returns the log entry when the apply was recorded.
+pub fn apply_with_backup(
+    live_path: &Path,
+    backup_dir: &Path,
+    previous_config_id: Option<&str>,
+    config_id: &str,
+    write: impl FnOnce() -> Result<(), String>,
+) -> Result<Option<ApplyLogEntry>, String> {
+    let backup = match backup_config(live_path, backup_dir, previous_config_id) {
+        Ok(backup) => Some(backup),
+        Err(e) => {
+            eprintln!("Failed to back up {}: {}", live_path.display(), e);
+            None
+        }
+    };
+    write()?;
+
+    let Some(backup) = backup else {
+        return Ok(None);
+    };
+    match record_apply(live_path, backup_dir, previous_config_id, config_id, backup) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(e) => {
+            eprintln!("Failed to record apply of {}: {}", live_path.display(), e);
+            Ok(None)
+        }
+    }
+}
+
+/// Restore the main config file as it was before its last apply and drop that log entry
+/// A file that did not exist before the apply is removed. Target applies are skipped, and so
+/// are applies whose backup is gone: they can never be reverted and are dropped from the log.
+pub fn revert_last_apply(backup_dir: &Path) -> Result<ApplyLogEntry, BackupError> {
+    let mut log = read_apply_log(backup_dir)?;
+    let dangling = |entry: &ApplyLogEntry| {
+        entry.backup_id.as_deref().is_some_and(|id| !backup_path(backup_dir, id).exists())
+    };
+    let before = log.len();
+    log.retain(|entry| {
+        let keep = entry.kind != ApplyKind::Main || !dangling(entry);
+        if !keep {
+            eprintln!("Dropping apply of {} at {}, its backup is gone", entry.target_path, entry.applied_at);
+        }
+        keep
+    });
+    let Some(index) = last_main_apply(&log) else {
+        if log.len() != before {
+            write_apply_log(backup_dir, &log)?;
+        }
+        return Err(BackupError::NothingToRevert);
+    };
+    let entry = log.remove(index);
+    let target = PathBuf::from(&entry.target_path);
+
+    match &entry.backup_id {
+        Some(id) => {
+            let content = read_backup(backup_dir, id)?;
+            fs::write(&target, content).map_err(|e| io_error(&target, e))?;
+        }
+        None => {
+            if target.exists() {
+                fs::remove_file(&target).map_err(|e| io_error(&target, e))?;
+            }
+        }
+    }
+
+    write_apply_log(backup_dir, &log)?;
+    Ok(entry)
+}
+
+// ============================================================================
+// Compaction
+// ============================================================================
+
+/// Outcome of `compact_backups`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactReport {
+    /// Removed for being beyond the per-config retention
+    pub removed_by_retention: usize,
+    /// Removed afterwards to get under the byte budget
+    pub removed_by_size: usize,
+    pub freed_bytes: u64,
+    pub remaining_bytes: u64,
+    /// Kept although over budget, it is needed to revert the current apply
+    pub protected_backup_id: Option<String>,
+}
+
+/// Every stored backup, oldest first (ids start with their timestamp)
+/// The config of a backup is known while its apply is still in the log
+pub fn list_backups(backup_dir: &Path) -> Result<Vec<Backup>, BackupError> {
+    let entries = match fs::read_dir(backup_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(io_error(backup_dir, e)),
+    };
+    let config_of: HashMap<String, Option<String>> = read_apply_log(backup_dir)?
+        .into_iter()
+        .filter_map(|entry| Some((entry.backup_id?, entry.previous_config_id)))
+        .collect();
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| io_error(backup_dir, e))?.path();
+        let Some(id) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".bak")) else {
+            continue;
+        };
+        let metadata = fs::metadata(&path).map_err(|e| io_error(&path, e))?;
+        let created_at = metadata
+            .modified()
+            .map(|time| chrono::DateTime::<Local>::from(time).to_rfc3339())
+            .unwrap_or_default();
+        backups.push(Backup {
+            id: id.to_string(),
+            created_at,
+            config_id: config_of.get(id).cloned().flatten(),
+            size: metadata.len(),
+        });
+    }
+    backups.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(backups)
+}
+
+/// Keep the newest `retention_per_config` backups of every config (backups of unknown
+/// configs count as one group), then remove the oldest ones until the rest fit in
+/// `max_total_bytes`
+/// The backup of the last main apply is never removed, so the current apply can always be reverted.
+/// Log entries of removed backups are pruned with them, `revert_last_apply` could not use them.
+pub fn compact_backups(
+    backup_dir: &Path,
+    retention_per_config: usize,
+    max_total_bytes: u64,
+) -> Result<CompactReport, BackupError> {
+    let backups = list_backups(backup_dir)?;
+    let log = read_apply_log(backup_dir)?;
+    let protected = last_main_apply(&log).and_then(|index| log[index].backup_id.clone());
+    let is_protected = |backup: &Backup| protected.as_deref() == Some(backup.id.as_str());
+
+    let mut kept_per_config: HashMap<Option<&str>, usize> = HashMap::new();
+    let mut by_retention = Vec::new();
+    for backup in backups.iter().rev() {
+        let kept = kept_per_config.entry(backup.config_id.as_deref()).or_default();
+        if *kept < retention_per_config || is_protected(backup) {
+            *kept += 1;
+        } else {
+            by_retention.push(backup);
+        }
+    }
+
+    let mut remaining_bytes: u64 = backups.iter().map(|b| b.size).sum();
+    remaining_bytes -= by_retention.iter().map(|b| b.size).sum::<u64>();
+    let mut by_size = Vec::new();
+    for backup in backups.iter().filter(|b| !by_retention.iter().any(|r| r.id == b.id)) {
+        if remaining_bytes <= max_total_bytes {
+            break;
+        }
+        if !is_protected(backup) {
+            remaining_bytes -= backup.size;
+            by_size.push(backup);
+        }
+    }
+
+    let mut report = CompactReport {
+        removed_by_retention: by_retention.len(),
+        removed_by_size: by_size.len(),
+        remaining_bytes,
+        protected_backup_id: protected.filter(|_| remaining_bytes > max_total_bytes),
+        ..Default::default()
+    };
+    let removed: Vec<&Backup> = by_retention.into_iter().chain(by_size).collect();
+    let is_removed = |entry: &ApplyLogEntry| {
+        entry.backup_id.as_deref().is_some_and(|id| removed.iter().any(|b| b.id == id))
+    };
+    if log.iter().any(is_removed) {
+        let kept: Vec<ApplyLogEntry> = log.iter().filter(|entry| !is_removed(*entry)).cloned().collect();
+        write_apply_log(backup_dir, &kept)?;
+    }
+    for backup in removed {
+        let path = backup_path(backup_dir, &backup.id);
+        fs::remove_file(&path).map_err(|e| io_error(&path, e))?;
+        report.freed_bytes += backup.size;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revert_restores_prior_live_content() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("oh-my-opencode.jsonc");
+        let backups = dir.join("backups");
+
+        // Edited outside AI Toolbox: comments and formatting must survive
+        let original = "{\n  // tuned by hand\n  \"agents\": { \"oracle\": { \"model\": \"openai/o3\" } }\n}\n";
+        fs::write(&live, original).unwrap();
+
+        let entry = apply_with_backup(&live, &backups, Some("old"), "new", || {
+            fs::write(&live, "{\"agents\":{}}").map_err(|e| e.to_string())
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(entry.previous_config_id.as_deref(), Some("old"));
+        let backup_id = entry.backup_id.clone().unwrap();
+        assert_eq!(read_backup(&backups, &backup_id).unwrap(), original.as_bytes());
+
+        let reverted = revert_last_apply(&backups).unwrap();
+        assert_eq!(reverted, entry);
+        assert_eq!(fs::read_to_string(&live).unwrap(), original);
+        assert_eq!(revert_last_apply(&backups), Err(BackupError::NothingToRevert));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_revert_removes_file_created_by_apply() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        let live = dir.join("oh-my-opencode.json");
+        let backups = dir.join("backups");
+
+        let entry = apply_with_backup(&live, &backups, None, "new", || {
+            fs::write(&live, "{}").map_err(|e| e.to_string())
+        });
+        assert!(entry.is_err(), "parent directory does not exist yet");
+
+        fs::create_dir_all(&dir).unwrap();
+        let entry = apply_with_backup(&live, &backups, None, "new", || {
+            fs::write(&live, "{}").map_err(|e| e.to_string())
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(entry.backup_id, None);
+        assert_eq!(read_apply_log(&backups).unwrap().len(), 1);
+
+        revert_last_apply(&backups).unwrap();
+        assert!(!live.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_backup_does_not_block_apply() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("oh-my-opencode.json");
+        fs::write(&live, "{}").unwrap();
+        // A file where the backup directory should be makes every backup fail
+        let backups = dir.join("backups");
+        fs::write(&backups, "").unwrap();
+
+        let entry = apply_with_backup(&live, &backups, None, "new", || {
+            fs::write(&live, "{\"agents\":{}}").map_err(|e| e.to_string())
+        });
+        assert_eq!(entry, Ok(None));
+        assert_eq!(fs::read_to_string(&live).unwrap(), "{\"agents\":{}}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_backups_removed_with_dropped_log_entries() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("oh-my-opencode.json");
+        let backups = dir.join("backups");
+        fs::write(&live, "{}").unwrap();
+
+        for _ in 0..MAX_APPLY_LOG_ENTRIES + 2 {
+            apply_with_backup(&live, &backups, None, "new", || Ok(())).unwrap().unwrap();
+        }
+        let log = read_apply_log(&backups).unwrap();
+        assert_eq!(log.len(), MAX_APPLY_LOG_ENTRIES);
+        let stored: Vec<String> = list_backups(&backups).unwrap().into_iter().map(|b| b.id).collect();
+        let mut logged: Vec<String> = log.into_iter().filter_map(|entry| entry.backup_id).collect();
+        logged.sort();
+        assert_eq!(stored, logged);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_target_applies_are_not_the_current_apply() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("oh-my-opencode.json");
+        let project = dir.join("project.json");
+        let backups = dir.join("backups");
+        fs::write(&live, "main before").unwrap();
+        fs::write(&project, "project before").unwrap();
+
+        let main = apply_with_backup(&live, &backups, Some("old"), "new", || {
+            fs::write(&live, "main after").map_err(|e| e.to_string())
+        })
+        .unwrap()
+        .unwrap();
+        let backup = backup_config(&project, &backups, None).unwrap();
+        fs::write(&project, "project after").unwrap();
+        let target = record_target_apply(&project, &backups, "new", backup).unwrap();
+        assert_eq!(target.kind, ApplyKind::Target);
+
+        // Compaction keeps the main apply's backup, revert restores the main file
+        let report = compact_backups(&backups, 0, 0).unwrap();
+        assert_eq!(report.protected_backup_id, main.backup_id);
+        assert_eq!(revert_last_apply(&backups).unwrap(), main);
+        assert_eq!(fs::read_to_string(&live).unwrap(), "main before");
+        assert_eq!(fs::read_to_string(&project).unwrap(), "project after");
+        // The target apply went with its compacted backup
+        assert_eq!(read_apply_log(&backups).unwrap(), Vec::new());
+        assert_eq!(revert_last_apply(&backups), Err(BackupError::NothingToRevert));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_compact_backups_by_retention_then_size() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        let live = dir.join("oh-my-opencode.json");
+        fs::create_dir_all(&dir).unwrap();
+        // Oldest first: four of "a" (the newest one is linked to the last apply), two of "b"
+        let stored = [
+            ("20260101T000000.001-00000001", "a", 100),
+            ("20260101T000000.002-00000002", "b", 100),
+            ("20260101T000000.003-00000003", "a", 100),
+            ("20260101T000000.004-00000004", "b", 100),
+            ("20260101T000000.005-00000005", "a", 100),
+            ("20260101T000000.006-00000006", "a", 300),
+        ];
+        for (id, config_id, size) in stored {
+            fs::write(backup_path(&dir, id), vec![b'x'; size]).unwrap();
+            let backup = Backup {
+                id: id.to_string(),
+                created_at: String::new(),
+                config_id: Some(config_id.to_string()),
+                size: size as u64,
+            };
+            record_apply(&live, &dir, Some(config_id), "next", Some(backup)).unwrap();
+        }
+
+        // Retention 2 drops a's two oldest and leaves 600 bytes, the budget of 350 then drops
+        // the oldest of the rest until only the 300 byte backup of the last apply is left
+        let report = compact_backups(&dir, 2, 350).unwrap();
+        assert_eq!(
+            report,
+            CompactReport {
+                removed_by_retention: 2,
+                removed_by_size: 3,
+                freed_bytes: 500,
+                remaining_bytes: 300,
+                protected_backup_id: None,
+            }
+        );
+        let left: Vec<String> = list_backups(&dir).unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(left, vec!["20260101T000000.006-00000006"]);
+        let logged: Vec<Option<String>> = read_apply_log(&dir).unwrap().into_iter().map(|e| e.backup_id).collect();
+        assert_eq!(logged, vec![Some("20260101T000000.006-00000006".to_string())]);
+
+        // Even a zero budget keeps the backup the current apply reverts to
+        let report = compact_backups(&dir, 0, 0).unwrap();
+        assert_eq!(report.freed_bytes, 0);
+        assert_eq!(report.protected_backup_id.as_deref(), Some("20260101T000000.006-00000006"));
+        assert!(revert_last_apply(&dir).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_revert_after_compact_skips_removed_backups() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-backups-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let live = dir.join("oh-my-opencode.json");
+        let backups = dir.join("backups");
+        for content in ["first", "second", "third"] {
+            fs::write(&live, content).unwrap();
+            apply_with_backup(&live, &backups, None, "new", || Ok(())).unwrap().unwrap();
+        }
+
+        // Only the backup of the last apply survives, the log keeps just that apply
+        compact_backups(&backups, 0, 0).unwrap();
+        assert_eq!(read_apply_log(&backups).unwrap().len(), 1);
+
+        fs::write(&live, "edited").unwrap();
+        revert_last_apply(&backups).unwrap();
+        assert_eq!(fs::read_to_string(&live).unwrap(), "third");
+        assert_eq!(revert_last_apply(&backups), Err(BackupError::NothingToRevert));
+
+        // A backup deleted behind our back does not block the applies before it
+        fs::write(&live, "fourth").unwrap();
+        apply_with_backup(&live, &backups, None, "new", || Ok(())).unwrap().unwrap();
+        fs::write(&live, "fifth").unwrap();
+        let gone = apply_with_backup(&live, &backups, None, "new", || Ok(())).unwrap().unwrap();
+        fs::remove_file(backup_path(&backups, gone.backup_id.as_deref().unwrap())).unwrap();
+        revert_last_apply(&backups).unwrap();
+        assert_eq!(fs::read_to_string(&live).unwrap(), "fourth");
+        assert_eq!(revert_last_apply(&backups), Err(BackupError::NothingToRevert));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,159 @@
+//! Oh My OpenCode Binary Cache Module
+//!
+//! MessagePack copy of the typed structs parsed from a JSON(C) config file, tagged with
+//! the SHA-256 of the JSON bytes. While the JSON is unchanged the cache is loaded instead
+//! of parsing it again; any edit changes the fingerprint and the cache is rebuilt.
+//! Caches live under the app cache dir, never next to the config, so they do not end up
+//! in the opencode config dir, WSL sync or dotfile repos.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bumped when the cached layout changes, older caches are ignored
+const CACHE_VERSION: u32 = 1;
+/// Directory under the app cache dir holding the caches
+const CACHE_DIR: &str = "config-cache";
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile<T> {
+    version: u32,
+    fingerprint: String,
+    data: T,
+}
+
+/// Whether a load was served from the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheSource {
+    Binary,
+    Json,
+}
+
+/// Directory of the caches in the app cache dir, `None` when it cannot be resolved
+pub fn cache_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Option<PathBuf> {
+    use tauri::Manager;
+    Some(app.path().app_cache_dir().ok()?.join(CACHE_DIR))
+}
+
+/// Cache file of a JSON file in `cache_dir`, named after the hash of its path
+/// so same-named configs in different dirs do not share a cache
+pub fn cache_path(cache_dir: &Path, json_path: &Path) -> PathBuf {
+    let path_hash = content_fingerprint(json_path.to_string_lossy().as_bytes());
+    cache_dir.join(format!("{}.msgpack", &path_hash[..16]))
+}
+
+fn content_fingerprint(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn read_cache<T: DeserializeOwned>(path: &Path, fingerprint: &str) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    let cache: CacheFile<T> = rmp_serde::from_slice(&bytes).ok()?;
+    (cache.version == CACHE_VERSION && cache.fingerprint == fingerprint).then_some(cache.data)
+}
+
+fn write_cache<T: Serialize>(path: &Path, fingerprint: String, data: &T) -> Result<(), String> {
+    let cache = CacheFile {
+        version: CACHE_VERSION,
+        fingerprint,
+        data,
+    };
+    // Named (map) encoding: skipped optional fields and flattened maps need field names
+    let bytes = rmp_serde::to_vec_named(&cache).map_err(|e| e.to_string())?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+/// Load a parsed JSON file through its binary cache in `cache_dir`, see `cache_dir()`
+/// Without a cache dir the JSON is parsed every time
+pub fn load_with_cache<T, F, E>(cache_dir: Option<&Path>, json_path: &Path, parse: F) -> Result<(T, CacheSource), E>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> Result<T, E>,
+    E: From<String>,
+{
+    match cache_dir {
+        Some(cache_dir) => load_with_cache_in(cache_dir, json_path, parse),
+        None => {
+            let text = fs::read_to_string(json_path)
+                .map_err(|e| E::from(format!("Failed to read {}: {}", json_path.display(), e)))?;
+            Ok((parse(&text)?, CacheSource::Json))
+        }
+    }
+}
+
+/// Load a parsed JSON file through its binary cache in `cache_dir`
+/// On a missing, stale or unreadable cache the JSON is parsed with `parse` and the cache
+/// rewritten; failing to write the cache is not an error
+pub fn load_with_cache_in<T, F, E>(cache_dir: &Path, json_path: &Path, parse: F) -> Result<(T, CacheSource), E>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(&str) -> Result<T, E>,
+    E: From<String>,
+{
+    let content =
+        fs::read(json_path).map_err(|e| E::from(format!("Failed to read {}: {}", json_path.display(), e)))?;
+    let fingerprint = content_fingerprint(&content);
+    let cache = cache_path(cache_dir, json_path);
+
+    if let Some(data) = read_cache(&cache, &fingerprint) {
+        return Ok((data, CacheSource::Binary));
+    }
+
+    let text =
+        String::from_utf8(content).map_err(|e| E::from(format!("Failed to read {}: {}", json_path.display(), e)))?;
+    let data = parse(&text)?;
+    if let Err(e) = write_cache(&cache, fingerprint, &data) {
+        eprintln!("Failed to write config cache {}: {}", cache.display(), e);
+    }
+    Ok((data, CacheSource::Json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::types::OhMyOpenCodeConfig;
+
+    fn parse_config(text: &str) -> Result<OhMyOpenCodeConfig, String> {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_binary_cache_round_trip_and_invalidation() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-cache-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let json_path = dir.join("config.json");
+        let cache_dir = dir.join("cache");
+        fs::write(
+            &json_path,
+            r#"{"id":"p1","name":"Profile","isApplied":true,
+                "agents":{"oracle":{"model":"openai/o3","tools":{"bash":false},"temperature":0.2}},
+                "otherFields":{"mcp":{"exa":{"type":"remote"}}}}"#,
+        )
+        .unwrap();
+
+        let (parsed, source) = load_with_cache_in(&cache_dir, &json_path, parse_config).unwrap();
+        assert_eq!(source, CacheSource::Json);
+        assert!(cache_path(&cache_dir, &json_path).exists());
+        // Nothing is written next to the config
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        let (cached, source) =
+            load_with_cache_in(&cache_dir, &json_path, |_| Err("JSON parsed again".to_string())).unwrap();
+        assert_eq!(source, CacheSource::Binary);
+        assert_eq!(serde_json::to_value(&cached).unwrap(), serde_json::to_value(&parsed).unwrap());
+        assert_eq!(cached.created_at, None);
+
+        // Any edit of the JSON invalidates the cache
+        fs::write(&json_path, r#"{"id":"p1","name":"Renamed","isApplied":false,"agents":null}"#).unwrap();
+        let (edited, source) = load_with_cache_in(&cache_dir, &json_path, parse_config).unwrap();
+        assert_eq!(source, CacheSource::Json);
+        assert_eq!(edited.name, "Renamed");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
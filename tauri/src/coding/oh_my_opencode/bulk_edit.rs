@@ -0,0 +1,207 @@
+//! Oh My OpenCode Bulk Edit Module
+//!
+//! Apply the same field changes to every agent matching a filter, e.g. set
+//! `temperature` to 0.3 on all agents using `openai/gpt-4o`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::agent::{self, AgentProfile, DEFAULT_AGENT_GROUP};
+use super::types::OhMyOpenCodeConfig;
+
+/// Fields a bulk edit may not touch
+const PROTECTED_FIELDS: &[&str] = &["locked", "extends"];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum BulkEditError {
+    #[error("Field '{0}' cannot be bulk edited")]
+    ProtectedField(String),
+    #[error("Invalid result for agent '{agent}': {reason}")]
+    InvalidResult { agent: String, reason: String },
+}
+
+/// Agents to edit, every set criterion must match; an empty filter matches all agents
+/// `model` and `name` accept `*` (any run) and `?` (one character) wildcards
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentFilter {
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Field changes, a `null` value removes the field
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentEdit {
+    #[serde(default)]
+    pub set: Map<String, Value>,
+}
+
+/// Glob match with `*` and `?`
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+impl AgentFilter {
+    pub fn matches(&self, name: &str, agent: &AgentProfile) -> bool {
+        let model_matches = match (&self.model, &agent.model) {
+            (None, _) => true,
+            (Some(pattern), Some(model)) => wildcard_match(pattern, model),
+            (Some(_), None) => false,
+        };
+        let group = agent.group.as_deref().unwrap_or(DEFAULT_AGENT_GROUP);
+        model_matches
+            && self.group.as_deref().is_none_or(|g| g == group)
+            && self.name.as_deref().is_none_or(|pattern| wildcard_match(pattern, name))
+    }
+}
+
+/// Checks on an edited agent beyond what the typed fields enforce
+fn validate_agent(value: &Value) -> Result<AgentProfile, String> {
+    let agent: AgentProfile = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+    if let Some(model) = &agent.model {
+        if !agent::is_valid_model_id(model) {
+            return Err(format!("invalid model '{}', expected 'provider/model'", model));
+        }
+    }
+    for (field, max) in [("temperature", 2.0), ("top_p", 1.0)] {
+        if let Some(v) = value.get(field) {
+            match v.as_f64() {
+                Some(n) if (0.0..=max).contains(&n) => {}
+                _ => return Err(format!("{} must be a number between 0 and {}", field, max)),
+            }
+        }
+    }
+    Ok(agent)
+}
+
+/// Apply `edit` to every unlocked agent matching `filter`
+/// Nothing is changed unless every edited agent is valid
+/// Returns the number of agents that actually changed
+pub fn bulk_edit_agents(
+    config: &mut OhMyOpenCodeConfig,
+    filter: &AgentFilter,
+    edit: &AgentEdit,
+) -> Result<usize, BulkEditError> {
+    if let Some(field) = edit.set.keys().find(|key| PROTECTED_FIELDS.contains(&key.as_str())) {
+        return Err(BulkEditError::ProtectedField(field.clone()));
+    }
+
+    let mut agents = config.typed_agents();
+    let mut changed = 0;
+    for (name, agent) in agents.iter_mut() {
+        if agent.locked || !filter.matches(name, agent) {
+            continue;
+        }
+        let before = agent.to_value();
+        let mut value = before.clone();
+        if let Some(obj) = value.as_object_mut() {
+            for (field, new_value) in &edit.set {
+                if new_value.is_null() {
+                    obj.shift_remove(field);
+                } else {
+                    obj.insert(field.clone(), new_value.clone());
+                }
+            }
+        }
+        if value == before {
+            continue;
+        }
+        *agent = validate_agent(&value).map_err(|reason| BulkEditError::InvalidResult {
+            agent: name.clone(),
+            reason,
+        })?;
+        changed += 1;
+    }
+
+    if changed > 0 {
+        config.agents = Some(agents);
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> OhMyOpenCodeConfig {
+        OhMyOpenCodeConfig::test_with_agents(json!({
+            "Sisyphus": { "model": "openai/gpt-4o", "temperature": 0.7 },
+            "explore": { "model": "openai/gpt-4o-mini" },
+            "oracle": { "model": "openai/gpt-4o", "locked": true },
+            "librarian": { "model": "anthropic/claude-sonnet-4" },
+            "frontend": { "model": "openai/gpt-4o", "temperature": 0.3 }
+        }))
+    }
+
+    #[test]
+    fn test_bulk_edit_temperature_by_model() {
+        let mut config = config();
+        let filter = AgentFilter {
+            model: Some("openai/gpt-4o".to_string()),
+            ..Default::default()
+        };
+        let edit = AgentEdit {
+            set: Map::from_iter([("temperature".to_string(), json!(0.3))]),
+        };
+
+        // frontend already has it, the locked oracle is skipped
+        assert_eq!(bulk_edit_agents(&mut config, &filter, &edit), Ok(1));
+        let agents = config.typed_agents();
+        assert_eq!(agents["Sisyphus"].temperature, Some(0.3));
+        assert_eq!(agents["oracle"].temperature, None);
+        assert_eq!(agents["explore"].temperature, None);
+
+        let wildcard = AgentFilter {
+            model: Some("openai/*".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(bulk_edit_agents(&mut config, &wildcard, &edit), Ok(1));
+    }
+
+    #[test]
+    fn test_bulk_edit_rejects_invalid_result() {
+        let mut config = config();
+        let before = config.agents.clone();
+        let edit = AgentEdit {
+            set: Map::from_iter([("temperature".to_string(), json!(5))]),
+        };
+
+        let result = bulk_edit_agents(&mut config, &AgentFilter::default(), &edit);
+        assert!(matches!(result, Err(BulkEditError::InvalidResult { .. })));
+        assert_eq!(config.agents, before);
+
+        let edit = AgentEdit {
+            set: Map::from_iter([("locked".to_string(), json!(false))]),
+        };
+        assert_eq!(
+            bulk_edit_agents(&mut config, &AgentFilter::default(), &edit),
+            Err(BulkEditError::ProtectedField("locked".to_string()))
+        );
+    }
+}
@@ -0,0 +1,526 @@
+//! Oh My OpenCode Bundle Module
+//!
+//! A bundle is the global config plus a list of configs in one JSON document,
+//! used to share a team setup. It can be wrapped in a passphrase-protected zip (AES-256).
+//! The global config can also be shared on its own, without any configs.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use zip::result::ZipError;
+use zip::{AesMode, ZipArchive, ZipWriter};
+
+use super::adapter;
+use super::import::ImportError;
+use super::key_case::{self, KeyCase};
+use super::migration;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig, OhMyOpenCodeGlobalConfigContent};
+use crate::coding::redact::redact_secrets;
+
+/// Current bundle format version
+pub const BUNDLE_VERSION: u32 = 1;
+/// Name of the bundle document inside an encrypted archive
+const BUNDLE_ENTRY_NAME: &str = "bundle.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBundle {
+    pub version: u32,
+    pub global: Value,
+    #[serde(default)]
+    pub configs: Vec<Value>,
+}
+
+/// Build a bundle from the global config and configs, record keys in the given casing
+/// (camelCase gives the layout of bundles exported before key casing was configurable)
+pub fn export_bundle(
+    global: &OhMyOpenCodeGlobalConfig,
+    configs: &[OhMyOpenCodeConfig],
+    case: KeyCase,
+) -> Value {
+    let bundle = ConfigBundle {
+        version: BUNDLE_VERSION,
+        global: serde_json::to_value(global).unwrap_or_default(),
+        configs: configs
+            .iter()
+            .map(|config| serde_json::to_value(config).unwrap_or_default())
+            .collect(),
+    };
+    let mut bundle = serde_json::to_value(bundle).unwrap_or_default();
+    key_case::convert_bundle_keys(&mut bundle, case);
+    bundle
+}
+
+/// Read a bundle, configs are parsed with the same fault tolerance as database records
+/// Imported configs are never marked as applied
+pub fn import_bundle(
+    value: &Value,
+) -> Result<(OhMyOpenCodeGlobalConfig, Vec<OhMyOpenCodeConfig>), ImportError> {
+    let bundle: ConfigBundle = serde_json::from_value(value.clone())
+        .map_err(|e| ImportError::InvalidBundle(e.to_string()))?;
+    if bundle.version > BUNDLE_VERSION {
+        return Err(ImportError::InvalidBundle(format!(
+            "Unsupported bundle version {}",
+            bundle.version
+        )));
+    }
+
+    let global = adapter::global_config_from_db_value(bundle.global);
+    let configs = bundle
+        .configs
+        .into_iter()
+        .filter(|config| config.is_object())
+        .map(parse_bundle_config)
+        .collect::<Result<Vec<_>, ImportError>>()?;
+
+    Ok((global, configs))
+}
+
+/// Parse one `configs` entry of a bundle, migrated to the current schema and not applied
+pub fn parse_bundle_config(config: Value) -> Result<OhMyOpenCodeConfig, ImportError> {
+    let config = migration::migrate(config).map_err(|e| ImportError::InvalidBundle(e.to_string()))?;
+    Ok(OhMyOpenCodeConfig {
+        is_applied: false,
+        ..adapter::from_db_value(config)
+    })
+}
+
+/// How an imported config whose id already exists is handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictResolution {
+    /// Leave the existing config, drop the imported one
+    Skip,
+    /// Replace the existing config, keeping its id and applied state
+    Overwrite,
+    /// Import under a new id with " (imported)" appended to the name
+    RenameImported,
+    /// Import under a new id with the name unchanged
+    KeepBoth,
+}
+
+/// What happened to one config of the bundle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportAction {
+    Created,
+    Skipped,
+    Overwritten,
+    Renamed,
+    KeptBoth,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOutcome {
+    /// Id of the config in the bundle
+    pub source_id: String,
+    /// Id the config is stored under, `None` when skipped
+    pub id: Option<String>,
+    pub name: String,
+    pub action: ImportAction,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportResult {
+    /// Configs to store, in bundle order
+    pub configs: Vec<OhMyOpenCodeConfig>,
+    /// One entry per config of the bundle
+    pub outcomes: Vec<ImportOutcome>,
+}
+
+const IMPORTED_NAME_SUFFIX: &str = " (imported)";
+
+/// `name` with the imported suffix, numbered until it no longer clashes with `taken`
+fn imported_name(name: &str, taken: &[String]) -> String {
+    let base = format!("{}{}", name, IMPORTED_NAME_SUFFIX);
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{} {}", base, n);
+        n += 1;
+    }
+    candidate
+}
+
+/// Whether a bundle-supplied id can be used as a record id as-is
+/// Only ASCII letters, digits, `-` and `_`, like the uuids the app generates
+pub fn is_valid_config_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Read a bundle and decide per config how it is stored next to `existing`
+/// Configs without an id collision are created as-is, colliding ones follow `resolution`.
+/// Invalid ids and ids repeated within the bundle are replaced by new ones.
+/// The global config of the bundle is not part of the result
+pub fn import_bundle_resolved(
+    value: &Value,
+    existing: &[OhMyOpenCodeConfig],
+    resolution: ConflictResolution,
+) -> Result<ImportResult, ImportError> {
+    let (_, configs) = import_bundle(value)?;
+    let mut names: Vec<String> = existing.iter().map(|c| c.name.clone()).collect();
+    let mut seen_ids: Vec<String> = Vec::new();
+    let mut result = ImportResult::default();
+
+    for mut config in configs {
+        let source_id = config.id.clone();
+        if !is_valid_config_id(&source_id) || seen_ids.contains(&source_id) {
+            config.id = uuid::Uuid::new_v4().simple().to_string();
+        }
+        seen_ids.push(source_id.clone());
+        let collision = existing.iter().find(|c| c.id == config.id);
+        let action = match (collision, resolution) {
+            (None, _) => ImportAction::Created,
+            (Some(_), ConflictResolution::Skip) => ImportAction::Skipped,
+            (Some(current), ConflictResolution::Overwrite) => {
+                config.is_applied = current.is_applied;
+                config.created_at = current.created_at.clone();
+                ImportAction::Overwritten
+            }
+            (Some(_), ConflictResolution::RenameImported) => {
+                config.id = uuid::Uuid::new_v4().simple().to_string();
+                config.name = imported_name(&config.name, &names);
+                ImportAction::Renamed
+            }
+            (Some(_), ConflictResolution::KeepBoth) => {
+                config.id = uuid::Uuid::new_v4().simple().to_string();
+                ImportAction::KeptBoth
+            }
+        };
+
+        let stored = action != ImportAction::Skipped;
+        result.outcomes.push(ImportOutcome {
+            source_id,
+            id: stored.then(|| config.id.clone()),
+            name: config.name.clone(),
+            action,
+        });
+        if stored {
+            names.push(config.name.clone());
+            result.configs.push(config);
+        }
+    }
+    Ok(result)
+}
+
+fn io_error(path: &Path, e: impl ToString) -> ImportError {
+    ImportError::Io {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    }
+}
+
+/// Write a bundle as an AES-256 encrypted zip archive
+pub fn export_encrypted_bundle(
+    path: &Path,
+    passphrase: &str,
+    global: &OhMyOpenCodeGlobalConfig,
+    configs: &[OhMyOpenCodeConfig],
+    case: KeyCase,
+) -> Result<(), ImportError> {
+    if passphrase.is_empty() {
+        return Err(ImportError::InvalidBundle("Passphrase must not be empty".to_string()));
+    }
+    let content = serde_json::to_vec_pretty(&export_bundle(global, configs, case))
+        .map_err(|e| ImportError::InvalidBundle(e.to_string()))?;
+
+    let file = File::create(path).map_err(|e| io_error(path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .with_aes_encryption(AesMode::Aes256, passphrase);
+
+    zip.start_file(BUNDLE_ENTRY_NAME, options)
+        .map_err(|e| io_error(path, e))?;
+    zip.write_all(&content).map_err(|e| io_error(path, e))?;
+    zip.finish().map_err(|e| io_error(path, e))?;
+    Ok(())
+}
+
+/// Decrypt and import a bundle written by `export_encrypted_bundle`
+/// A wrong passphrase gives `DecryptionFailed`
+pub fn import_encrypted_bundle(
+    path: &Path,
+    passphrase: &str,
+) -> Result<(OhMyOpenCodeGlobalConfig, Vec<OhMyOpenCodeConfig>), ImportError> {
+    let file = File::open(path).map_err(|e| io_error(path, e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| ImportError::InvalidBundle(format!("Not a bundle archive: {}", e)))?;
+
+    let mut entry = match archive.by_name_decrypt(BUNDLE_ENTRY_NAME, passphrase.as_bytes()) {
+        Ok(entry) => entry,
+        Err(ZipError::InvalidPassword) => return Err(ImportError::DecryptionFailed),
+        Err(ZipError::FileNotFound) => {
+            return Err(ImportError::InvalidBundle(format!(
+                "Archive has no {}",
+                BUNDLE_ENTRY_NAME
+            )))
+        }
+        Err(e) => return Err(io_error(path, e)),
+    };
+
+    // The AES authentication code is checked while reading, a failure means a wrong key or tampering
+    let mut content = Vec::new();
+    entry
+        .read_to_end(&mut content)
+        .map_err(|_| ImportError::DecryptionFailed)?;
+
+    let value: Value =
+        serde_json::from_slice(&content).map_err(|e| ImportError::InvalidBundle(e.to_string()))?;
+    import_bundle(&value)
+}
+
+/// The global config shared on its own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalConfigExport {
+    pub version: u32,
+    pub global: Value,
+}
+
+fn global_content(global: &OhMyOpenCodeGlobalConfig) -> OhMyOpenCodeGlobalConfigContent {
+    OhMyOpenCodeGlobalConfigContent {
+        schema: global.schema.clone(),
+        sisyphus_agent: global.sisyphus_agent.clone(),
+        disabled_agents: global.disabled_agents.clone(),
+        disabled_mcps: global.disabled_mcps.clone(),
+        disabled_hooks: global.disabled_hooks.clone(),
+        lsp: global.lsp.clone(),
+        experimental: global.experimental.clone(),
+        other_fields: global.other_fields.clone(),
+        updated_at: global.updated_at.clone().unwrap_or_default(),
+    }
+}
+
+/// Export only the global config, in its database record form with secrets redacted
+pub fn export_global_config(global: &OhMyOpenCodeGlobalConfig) -> Value {
+    let mut record = adapter::global_config_to_db_value(&global_content(global));
+    redact_secrets(&mut record);
+    serde_json::to_value(GlobalConfigExport {
+        version: BUNDLE_VERSION,
+        global: record,
+    })
+    .unwrap_or_default()
+}
+
+/// Read a global config export, parsed with the same fault tolerance as the database record
+/// Redacted secrets stay redacted and have to be filled in again
+pub fn import_global_config(value: &Value) -> Result<OhMyOpenCodeGlobalConfigContent, ImportError> {
+    let export: GlobalConfigExport = serde_json::from_value(value.clone())
+        .map_err(|e| ImportError::InvalidBundle(e.to_string()))?;
+    if export.version > BUNDLE_VERSION {
+        return Err(ImportError::InvalidBundle(format!(
+            "Unsupported bundle version {}",
+            export.version
+        )));
+    }
+    if !export.global.is_object() {
+        return Err(ImportError::InvalidBundle("global config is not an object".to_string()));
+    }
+    Ok(global_content(&adapter::global_config_from_db_value(export.global)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use serde_json::json;
+
+    fn sample() -> (OhMyOpenCodeGlobalConfig, Vec<OhMyOpenCodeConfig>) {
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_mcps: Some(vec!["websearch".to_string()]),
+            ..Default::default()
+        };
+        let configs = vec![OhMyOpenCodeConfig {
+            id: "team".to_string(),
+            name: "Team".to_string(),
+            is_applied: true,
+            agents: Some(parse_agents(Some(&json!({ "oracle": { "model": "openai/o3" } })))),
+            other_fields: None,
+            created_at: Some("2026-01-01T00:00:00+00:00".to_string()),
+            updated_at: None,
+        }];
+        (global, configs)
+    }
+
+    fn temp_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ai-toolbox-bundle-{}.zip", uuid::Uuid::new_v4()))
+    }
+
+    fn existing() -> Vec<OhMyOpenCodeConfig> {
+        vec![OhMyOpenCodeConfig {
+            id: "team".to_string(),
+            name: "Team".to_string(),
+            is_applied: true,
+            agents: Some(parse_agents(Some(&json!({ "oracle": { "model": "anthropic/claude-opus-4" } })))),
+            other_fields: None,
+            created_at: Some("2025-06-01T00:00:00+00:00".to_string()),
+            updated_at: None,
+        }]
+    }
+
+    #[test]
+    fn test_export_bundle_key_casing() {
+        let (global, mut configs) = sample();
+        configs[0].other_fields = Some(json!({ "small_model": "openai/gpt-4o-mini", "customKey": true }));
+
+        let snake = export_bundle(&global, &configs, KeyCase::SnakeCase);
+        let camel = export_bundle(&global, &configs, KeyCase::CamelCase);
+
+        assert_eq!(snake["global"]["disabled_mcps"], json!(["websearch"]));
+        assert_eq!(camel["global"]["disabledMcps"], json!(["websearch"]));
+        assert_eq!(snake["configs"][0]["created_at"], json!("2026-01-01T00:00:00+00:00"));
+        assert_eq!(camel["configs"][0]["createdAt"], json!("2026-01-01T00:00:00+00:00"));
+        assert!(snake["configs"][0].get("isApplied").is_none());
+        assert!(camel["configs"][0].get("is_applied").is_none());
+        // Unknown keys inside other_fields and agents are never converted
+        let other_fields = json!({ "small_model": "openai/gpt-4o-mini", "customKey": true });
+        assert_eq!(snake["configs"][0]["other_fields"], other_fields);
+        assert_eq!(camel["configs"][0]["otherFields"], other_fields);
+        assert_eq!(camel["configs"][0]["agents"], snake["configs"][0]["agents"]);
+
+        // Both casings import to the same configs
+        let (_, from_snake) = import_bundle(&snake).unwrap();
+        let (_, from_camel) = import_bundle(&camel).unwrap();
+        assert_eq!(from_snake[0].other_fields, from_camel[0].other_fields);
+        assert_eq!(from_snake[0].created_at, from_camel[0].created_at);
+    }
+
+    fn resolve(resolution: ConflictResolution) -> ImportResult {
+        let (global, mut configs) = sample();
+        let solo = OhMyOpenCodeConfig {
+            id: "solo".to_string(),
+            name: "Solo".to_string(),
+            ..configs[0].clone()
+        };
+        configs.push(solo);
+        import_bundle_resolved(&export_bundle(&global, &configs, KeyCase::default()), &existing(), resolution).unwrap()
+    }
+
+    #[test]
+    fn test_import_resolved_skip() {
+        let result = resolve(ConflictResolution::Skip);
+        assert_eq!(result.outcomes[0].action, ImportAction::Skipped);
+        assert_eq!(result.outcomes[0].id, None);
+        assert_eq!(result.outcomes[1].action, ImportAction::Created);
+        assert_eq!(result.configs.len(), 1);
+        assert_eq!(result.configs[0].id, "solo");
+    }
+
+    #[test]
+    fn test_import_resolved_overwrite() {
+        let result = resolve(ConflictResolution::Overwrite);
+        assert_eq!(result.outcomes[0].action, ImportAction::Overwritten);
+        assert_eq!(result.configs[0].id, "team");
+        assert_eq!(result.configs[0].agents_value(), Some(json!({ "oracle": { "model": "openai/o3" } })));
+        // Applied state and creation time stay with the stored record
+        assert!(result.configs[0].is_applied);
+        assert_eq!(result.configs[0].created_at.as_deref(), Some("2025-06-01T00:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_import_resolved_rename_imported() {
+        let result = resolve(ConflictResolution::RenameImported);
+        assert_eq!(result.outcomes[0].action, ImportAction::Renamed);
+        assert_ne!(result.configs[0].id, "team");
+        assert_eq!(result.outcomes[0].id.as_ref(), Some(&result.configs[0].id));
+        assert_eq!(result.configs[0].name, "Team (imported)");
+        assert!(!result.configs[0].is_applied);
+    }
+
+    #[test]
+    fn test_import_resolved_keep_both() {
+        let result = resolve(ConflictResolution::KeepBoth);
+        assert_eq!(result.outcomes[0].action, ImportAction::KeptBoth);
+        assert_ne!(result.configs[0].id, "team");
+        assert_eq!(result.configs[0].name, "Team");
+        assert_eq!(result.configs.len(), 2);
+    }
+
+    #[test]
+    fn test_import_resolved_replaces_unsafe_and_repeated_ids() {
+        let (global, configs) = sample();
+        let with_id = |id: &str| OhMyOpenCodeConfig {
+            id: id.to_string(),
+            ..configs[0].clone()
+        };
+        let bundle = export_bundle(
+            &global,
+            &[with_id("x`; DELETE oh_my_opencode_config; `"), with_id(""), with_id("dup"), with_id("dup")],
+            KeyCase::default(),
+        );
+
+        let result = import_bundle_resolved(&bundle, &[], ConflictResolution::Skip).unwrap();
+
+        let ids: Vec<&str> = result.configs.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids.len(), 4);
+        assert!(ids.iter().all(|id| is_valid_config_id(id)), "{ids:?}");
+        assert_eq!(ids[2], "dup");
+        assert_ne!(ids[3], "dup");
+        assert!(result.outcomes.iter().all(|o| o.action == ImportAction::Created));
+        assert_eq!(result.outcomes[3].source_id, "dup");
+    }
+
+    #[test]
+    fn test_encrypted_bundle_round_trip() {
+        let (global, configs) = sample();
+        let path = temp_path();
+        export_encrypted_bundle(&path, "correct horse", &global, &configs, KeyCase::default()).unwrap();
+
+        let (imported_global, imported) = import_encrypted_bundle(&path, "correct horse").unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(imported_global.disabled_mcps, global.disabled_mcps);
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].name, "Team");
+        assert_eq!(imported[0].agents, configs[0].agents);
+        assert!(!imported[0].is_applied);
+    }
+
+    #[test]
+    fn test_encrypted_bundle_wrong_passphrase() {
+        let (global, configs) = sample();
+        let path = temp_path();
+        export_encrypted_bundle(&path, "correct horse", &global, &configs, KeyCase::default()).unwrap();
+
+        let result = import_encrypted_bundle(&path, "battery staple");
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result.unwrap_err(), ImportError::DecryptionFailed);
+    }
+
+    #[test]
+    fn test_global_config_export_round_trip() {
+        let global = OhMyOpenCodeGlobalConfig {
+            sisyphus_agent: Some(json!({ "disabled": false, "planner_enabled": true })),
+            disabled_agents: Some(vec!["oracle".to_string()]),
+            disabled_mcps: Some(vec!["websearch".to_string(), "context7".to_string()]),
+            disabled_hooks: Some(vec!["comment-checker".to_string()]),
+            experimental: Some(json!({ "aggressive_truncation": true })),
+            other_fields: Some(json!({ "mcp": { "exa": { "headers": { "apiKey": "exa-123" } } } })),
+            ..Default::default()
+        };
+
+        let exported = export_global_config(&global);
+        assert!(exported.get("configs").is_none());
+        assert!(!exported.to_string().contains("exa-123"));
+
+        let imported = import_global_config(&exported).unwrap();
+        assert_eq!(imported.sisyphus_agent, global.sisyphus_agent);
+        assert_eq!(imported.disabled_agents, global.disabled_agents);
+        assert_eq!(imported.disabled_mcps, global.disabled_mcps);
+        assert_eq!(imported.disabled_hooks, global.disabled_hooks);
+        assert_eq!(imported.experimental, global.experimental);
+        assert_eq!(
+            imported.other_fields.unwrap()["mcp"]["exa"]["headers"]["apiKey"],
+            json!(crate::coding::redact::REDACTED)
+        );
+
+        assert!(import_global_config(&json!({ "version": 99, "global": {} })).is_err());
+    }
+}
@@ -0,0 +1,353 @@
+//! Oh My OpenCode Bundle Streaming Module
+//!
+//! Import of large bundles without loading them into memory: when the envelope (`version`,
+//! `global`) comes first, as in exported bundles, `configs` is parsed one element at a
+//! time and each config is handed to a callback before the next one is read. Bundles
+//! with `configs` before the envelope are accepted too, their configs are held until
+//! the envelope has been read.
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserializer, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::io::{BufReader, Read};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::adapter;
+use super::bundle::{parse_bundle_config, BUNDLE_VERSION};
+use super::import::ImportError;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Bundle metadata, available before any config is read
+#[derive(Debug, Clone)]
+pub struct BundleEnvelope {
+    pub version: u32,
+    pub global: OhMyOpenCodeGlobalConfig,
+}
+
+/// Result of a streaming import, the global config is returned for the caller to apply
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamImportSummary {
+    pub version: u32,
+    pub imported: usize,
+    pub global: OhMyOpenCodeGlobalConfig,
+}
+
+/// Progress of a streaming import, sent as `oh-my-opencode-import-progress`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProgress {
+    pub imported: usize,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+}
+
+/// Reader counting the bytes read through it, shared with a progress reporter
+pub struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: Read> CountingReader<R> {
+    pub fn new(inner: R) -> Self {
+        CountingReader {
+            inner,
+            count: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Handle to the byte count, readable while the reader is in use elsewhere
+    pub fn counter(&self) -> Arc<AtomicU64> {
+        self.count.clone()
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+/// Keep the real error for the caller, serde only carries its message
+fn fail<E: de::Error>(slot: &mut Option<ImportError>, error: ImportError) -> E {
+    let message = error.to_string();
+    *slot = Some(error);
+    E::custom(message)
+}
+
+struct ConfigsSeed<'a, F> {
+    envelope: &'a BundleEnvelope,
+    on_config: &'a mut F,
+    error: &'a mut Option<ImportError>,
+}
+
+impl<'de, F> DeserializeSeed<'de> for ConfigsSeed<'_, F>
+where
+    F: FnMut(&BundleEnvelope, OhMyOpenCodeConfig) -> Result<(), ImportError>,
+{
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F> Visitor<'de> for ConfigsSeed<'_, F>
+where
+    F: FnMut(&BundleEnvelope, OhMyOpenCodeConfig) -> Result<(), ImportError>,
+{
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of configs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<usize, A::Error> {
+        let error = self.error;
+        let mut imported = 0;
+        while let Some(value) = seq.next_element::<Value>()? {
+            if !value.is_object() {
+                continue;
+            }
+            let config = parse_bundle_config(value).map_err(|e| fail(&mut *error, e))?;
+            (self.on_config)(self.envelope, config).map_err(|e| fail(&mut *error, e))?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+}
+
+struct BundleVisitor<'a, F> {
+    on_config: &'a mut F,
+    error: &'a mut Option<ImportError>,
+}
+
+impl<'de, F> Visitor<'de> for BundleVisitor<'_, F>
+where
+    F: FnMut(&BundleEnvelope, OhMyOpenCodeConfig) -> Result<(), ImportError>,
+{
+    type Value = StreamImportSummary;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a config bundle object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<StreamImportSummary, A::Error> {
+        let error = self.error;
+        let mut version = None;
+        let mut global = None;
+        let mut imported = 0;
+        // Configs read before the envelope was complete
+        let mut pending: Vec<Value> = Vec::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => {
+                    let value: u32 = map.next_value()?;
+                    if value > BUNDLE_VERSION {
+                        return Err(fail(
+                            error,
+                            ImportError::InvalidBundle(format!("Unsupported bundle version {}", value)),
+                        ));
+                    }
+                    version = Some(value);
+                }
+                "global" => {
+                    global = Some(adapter::global_config_from_db_value(map.next_value::<Value>()?));
+                }
+                "configs" => match (version, global.clone()) {
+                    (Some(version), Some(global)) => {
+                        let envelope = BundleEnvelope { version, global };
+                        imported += map.next_value_seed(ConfigsSeed {
+                            envelope: &envelope,
+                            on_config: &mut *self.on_config,
+                            error: &mut *error,
+                        })?;
+                    }
+                    _ => pending.extend(map.next_value::<Vec<Value>>()?),
+                },
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let Some(version) = version else {
+            return Err(fail(
+                error,
+                ImportError::InvalidBundle("Bundle has no version".to_string()),
+            ));
+        };
+        let Some(global) = global else {
+            return Err(fail(
+                error,
+                ImportError::InvalidBundle("Bundle has no global config".to_string()),
+            ));
+        };
+
+        let envelope = BundleEnvelope { version, global };
+        for value in pending.into_iter().filter(|value| value.is_object()) {
+            let config = parse_bundle_config(value).map_err(|e| fail(&mut *error, e))?;
+            (self.on_config)(&envelope, config).map_err(|e| fail(&mut *error, e))?;
+            imported += 1;
+        }
+        Ok(StreamImportSummary {
+            version,
+            imported,
+            global: envelope.global,
+        })
+    }
+}
+
+/// Stream the configs of a bundle to `on_config`, one at a time in bundle order
+/// Memory use is bounded by the largest single config, not the bundle size, as long as
+/// `version` and `global` come before `configs`
+/// An error from `on_config` stops the import and is returned as-is
+pub fn import_bundle_streaming<R, F>(reader: R, mut on_config: F) -> Result<StreamImportSummary, ImportError>
+where
+    R: Read,
+    F: FnMut(&BundleEnvelope, OhMyOpenCodeConfig) -> Result<(), ImportError>,
+{
+    let mut error = None;
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+    let result = (&mut deserializer).deserialize_map(BundleVisitor {
+        on_config: &mut on_config,
+        error: &mut error,
+    });
+
+    match result {
+        Ok(summary) => {
+            deserializer
+                .end()
+                .map_err(|e| ImportError::InvalidBundle(e.to_string()))?;
+            Ok(summary)
+        }
+        Err(e) => Err(error.unwrap_or_else(|| ImportError::InvalidBundle(e.to_string()))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use crate::coding::oh_my_opencode::bundle::export_bundle;
+    use crate::coding::oh_my_opencode::key_case::KeyCase;
+    use serde_json::json;
+
+    fn large_bundle(count: usize) -> Vec<u8> {
+        large_bundle_in(count, KeyCase::default())
+    }
+
+    fn large_bundle_in(count: usize, case: KeyCase) -> Vec<u8> {
+        let configs: Vec<OhMyOpenCodeConfig> = (0..count)
+            .map(|i| OhMyOpenCodeConfig {
+                id: format!("config-{}", i),
+                name: format!("Config {}", i),
+                is_applied: false,
+                agents: Some(parse_agents(Some(&json!({ "oracle": { "model": "openai/o3", "prompt": "x".repeat(200) } })))),
+                other_fields: None,
+                created_at: None,
+                updated_at: None,
+            })
+            .collect();
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_mcps: Some(vec!["websearch".to_string()]),
+            ..Default::default()
+        };
+        serde_json::to_vec(&export_bundle(&global, &configs, case)).unwrap()
+    }
+
+    #[test]
+    fn test_streaming_import_bounded_memory() {
+        let bundle = large_bundle(2000);
+        let total = bundle.len() as u64;
+        let reader = CountingReader::new(bundle.as_slice());
+        let bytes_read = reader.counter();
+
+        let mut names = Vec::new();
+        let mut read_at_first = None;
+        let summary = import_bundle_streaming(reader, |envelope, config| {
+            assert_eq!(envelope.global.disabled_mcps, Some(vec!["websearch".to_string()]));
+            read_at_first.get_or_insert(bytes_read.load(Ordering::Relaxed));
+            names.push(config.name);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!((summary.version, summary.imported), (BUNDLE_VERSION, 2000));
+        assert_eq!(summary.global.disabled_mcps, Some(vec!["websearch".to_string()]));
+        assert_eq!(names[1999], "Config 1999");
+        // Only a buffer's worth was read when the first config was handed over
+        assert!(read_at_first.unwrap() < total / 10);
+        assert_eq!(bytes_read.load(Ordering::Relaxed), total);
+    }
+
+    #[test]
+    fn test_streaming_import_reads_camel_case_bundles() {
+        // Bundles exported before the snake_case default are camelCase
+        let bundle = large_bundle_in(3, KeyCase::CamelCase);
+        assert!(String::from_utf8_lossy(&bundle).contains("disabledMcps"));
+
+        let mut names = Vec::new();
+        let summary = import_bundle_streaming(bundle.as_slice(), |_, config| {
+            assert!(!config.is_applied);
+            names.push(config.name);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(names, vec!["Config 0", "Config 1", "Config 2"]);
+        assert_eq!(summary.global.disabled_mcps, Some(vec!["websearch".to_string()]));
+    }
+
+    #[test]
+    fn test_streaming_import_accepts_configs_before_envelope() {
+        let bundle = json!({
+            "configs": [
+                { "id": "a", "name": "A", "isApplied": false, "agents": { "oracle": { "model": "openai/o3" } } },
+                { "id": "b", "name": "B", "isApplied": false }
+            ],
+            "global": { "disabledMcps": ["websearch"] },
+            "version": BUNDLE_VERSION
+        });
+
+        let mut names = Vec::new();
+        let summary = import_bundle_streaming(bundle.to_string().as_bytes(), |envelope, config| {
+            assert_eq!(envelope.global.disabled_mcps, Some(vec!["websearch".to_string()]));
+            names.push(config.name);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(names, vec!["A", "B"]);
+        assert_eq!(summary.imported, 2);
+        assert_eq!(summary.global.disabled_mcps, Some(vec!["websearch".to_string()]));
+
+        let no_global = json!({ "configs": [], "version": BUNDLE_VERSION });
+        assert_eq!(
+            import_bundle_streaming(no_global.to_string().as_bytes(), |_, _| Ok(())).map(|s| s.imported),
+            Err(ImportError::InvalidBundle("Bundle has no global config".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_streaming_import_stops_on_callback_error() {
+        let bundle = large_bundle(10);
+        let mut seen = 0;
+        let result = import_bundle_streaming(bundle.as_slice(), |_, _| {
+            seen += 1;
+            if seen == 3 {
+                return Err(ImportError::InvalidBundle("disk full".to_string()));
+            }
+            Ok(())
+        })
+        .map(|summary| summary.imported);
+
+        assert_eq!(result, Err(ImportError::InvalidBundle("disk full".to_string())));
+        assert_eq!(seen, 3);
+    }
+}
@@ -0,0 +1,110 @@
+//! Oh My OpenCode Capabilities Module
+//!
+//! Checks that the effective model of an agent (after `extends`) supports what the
+//! agent uses: tool calling when it enables tools, image input when it uses vision.
+//! Models without known capabilities are not checked.
+
+use crate::coding::open_code::model_metadata::{model_capabilities, ModelCapability};
+
+use super::agent::AgentProfile;
+use super::inheritance;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const CAPABILITY_RULE: &str = "model-capabilities";
+
+/// Tools that send images to the model
+const VISION_TOOLS: &[&str] = &["look_at"];
+/// Agents that only make sense with image input
+const VISION_AGENTS: &[&str] = &["multimodal-looker"];
+
+pub(crate) fn enables_tools(agent: &AgentProfile) -> bool {
+    agent.tools.as_ref().is_some_and(|tools| tools.values().any(|enabled| *enabled))
+}
+
+pub(crate) fn uses_vision(name: &str, agent: &AgentProfile) -> bool {
+    VISION_AGENTS.contains(&name)
+        || agent
+            .tools
+            .as_ref()
+            .is_some_and(|tools| VISION_TOOLS.iter().any(|tool| tools.get(*tool) == Some(&true)))
+}
+
+fn capability_issue(name: &str, model: &str, feature: &str) -> ValidationIssue {
+    ValidationIssue::new(
+        CAPABILITY_RULE,
+        Severity::Warning,
+        format!("agents.{}.model", name),
+        format!("Agent '{}' uses {} but model '{}' does not support them", name, feature, model),
+    )
+}
+
+/// Capability check with an injectable lookup, `None` from the lookup skips the agent
+pub fn validate_capabilities_with(
+    profile: &OhMyOpenCodeConfig,
+    capabilities: &dyn Fn(&str) -> Option<Vec<ModelCapability>>,
+) -> Vec<ValidationIssue> {
+    let parsed = profile.typed_agents();
+    let agents = inheritance::resolve_agents(&parsed).unwrap_or(parsed);
+
+    let mut issues = Vec::new();
+    for (name, agent) in &agents {
+        let Some(model) = agent.model.as_deref() else {
+            continue;
+        };
+        let Some(supported) = capabilities(model) else {
+            continue;
+        };
+        if enables_tools(agent) && !supported.contains(&ModelCapability::Tool) {
+            issues.push(capability_issue(name, model, "tools"));
+        }
+        if uses_vision(name, agent) && !supported.contains(&ModelCapability::Vision) {
+            issues.push(capability_issue(name, model, "images"));
+        }
+    }
+    issues
+}
+
+/// Validation rule: agents must not rely on features their model lacks
+pub fn validate_model_capabilities(
+    _global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    validate_capabilities_with(profile, &model_capabilities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn capabilities(model: &str) -> Option<Vec<ModelCapability>> {
+        match model {
+            "openai/gpt-4o" => Some(vec![ModelCapability::Tool, ModelCapability::Vision]),
+            "local/tiny" => Some(Vec::new()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_tool_agent_on_model_without_tools() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "base": { "model": "local/tiny" },
+            "coder": { "extends": "base", "tools": { "bash": true } },
+            "unknown": { "model": "acme/mystery", "tools": { "bash": true } }
+        }));
+        let issues = validate_capabilities_with(&config, &capabilities);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "agents.coder.model");
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_tool_agent_on_capable_model() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "coder": { "model": "openai/gpt-4o", "tools": { "bash": true, "look_at": true } },
+            "multimodal-looker": { "model": "openai/gpt-4o" }
+        }));
+        assert!(validate_capabilities_with(&config, &capabilities).is_empty());
+    }
+}
@@ -0,0 +1,196 @@
+//! Oh My OpenCode Changelog Module
+//!
+//! Changes between two versions of a shared config bundle, for release notes:
+//! configs added, removed or changed (by id) and changes of the global config.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::bundle::import_bundle;
+use super::global_apply::{render_global, ConfigDiff};
+use super::import::ImportError;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigRef {
+    pub id: String,
+    pub name: String,
+}
+
+/// A config present in both bundles with a different rendered output
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChange {
+    pub id: String,
+    pub name: String,
+    /// One line per change, see [`summarize_apply`]
+    pub summary: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleChangelog {
+    pub added: Vec<ConfigRef>,
+    pub removed: Vec<ConfigRef>,
+    pub changed: Vec<ConfigChange>,
+    pub global: Vec<ConfigDiff>,
+}
+
+fn short(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn as_object(value: Option<&Value>) -> Map<String, Value> {
+    value.and_then(|v| v.as_object()).cloned().unwrap_or_default()
+}
+
+/// Changed keys of two objects, keys of `before` first
+fn key_diffs(before: &Map<String, Value>, after: &Map<String, Value>) -> Vec<ConfigDiff> {
+    let mut keys: Vec<&String> = before.keys().collect();
+    keys.extend(after.keys().filter(|key| !before.contains_key(*key)));
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| ConfigDiff {
+            key: key.clone(),
+            before: before.get(key).cloned(),
+            after: after.get(key).cloned(),
+        })
+        .collect()
+}
+
+fn describe(prefix: &str, diff: &ConfigDiff) -> String {
+    match (&diff.before, &diff.after) {
+        (None, Some(after)) => format!("{}{} set to {}", prefix, diff.key, short(after)),
+        (Some(_), None) => format!("{}{} removed", prefix, diff.key),
+        (Some(before), Some(after)) => {
+            format!("{}{}: {} -> {}", prefix, diff.key, short(before), short(after))
+        }
+        (None, None) => format!("{}{} changed", prefix, diff.key),
+    }
+}
+
+/// Human readable summary of what applying `after` instead of `before` changes in the written file
+/// Both are rendered with the same global config, so only profile changes show up
+pub fn summarize_apply(
+    global: &OhMyOpenCodeGlobalConfig,
+    before: &OhMyOpenCodeConfig,
+    after: &OhMyOpenCodeConfig,
+) -> Vec<String> {
+    let before = render_opencode_json(global, before);
+    let after = render_opencode_json(global, after);
+    let before_top = as_object(Some(&before));
+    let after_top = as_object(Some(&after));
+
+    let mut lines = Vec::new();
+    for diff in key_diffs(&before_top, &after_top) {
+        if diff.key != "agents" {
+            lines.push(describe("", &diff));
+            continue;
+        }
+        let before_agents = as_object(diff.before.as_ref());
+        let after_agents = as_object(diff.after.as_ref());
+        for agent in key_diffs(&before_agents, &after_agents) {
+            match (&agent.before, &agent.after) {
+                (None, Some(_)) => lines.push(format!("agent {} added", agent.key)),
+                (Some(_), None) => lines.push(format!("agent {} removed", agent.key)),
+                _ => {
+                    let prefix = format!("agent {} ", agent.key);
+                    let fields = key_diffs(&as_object(agent.before.as_ref()), &as_object(agent.after.as_ref()));
+                    lines.extend(fields.iter().map(|field| describe(&prefix, field)));
+                }
+            }
+        }
+    }
+    lines
+}
+
+fn config_ref(config: &OhMyOpenCodeConfig) -> ConfigRef {
+    ConfigRef {
+        id: config.id.clone(),
+        name: config.name.clone(),
+    }
+}
+
+/// Changelog from `old_bundle` to `new_bundle`, configs in the order of the bundle that has them
+pub fn bundle_changelog(old_bundle: &Value, new_bundle: &Value) -> Result<BundleChangelog, ImportError> {
+    let (old_global, old_configs) = import_bundle(old_bundle)?;
+    let (new_global, new_configs) = import_bundle(new_bundle)?;
+
+    let removed = old_configs
+        .iter()
+        .filter(|old| !new_configs.iter().any(|new| new.id == old.id))
+        .map(config_ref)
+        .collect();
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for new in &new_configs {
+        let Some(old) = old_configs.iter().find(|old| old.id == new.id) else {
+            added.push(config_ref(new));
+            continue;
+        };
+        let summary = summarize_apply(&new_global, old, new);
+        if !summary.is_empty() {
+            changed.push(ConfigChange {
+                id: new.id.clone(),
+                name: new.name.clone(),
+                summary,
+            });
+        }
+    }
+
+    Ok(BundleChangelog {
+        added,
+        removed,
+        changed,
+        global: key_diffs(&render_global(&old_global), &render_global(&new_global)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle(global: Value, configs: Vec<Value>) -> Value {
+        json!({ "version": 1, "global": global, "configs": configs })
+    }
+
+    #[test]
+    fn test_bundle_changelog() {
+        let old = bundle(
+            json!({ "id": "global", "disabledMcps": ["websearch"] }),
+            vec![
+                json!({ "id": "work", "name": "Work", "agents": { "oracle": { "model": "openai/o3" } } }),
+                json!({ "id": "legacy", "name": "Legacy", "agents": { "oracle": { "model": "openai/gpt-4" } } }),
+            ],
+        );
+        let new = bundle(
+            json!({ "id": "global", "disabledMcps": ["websearch", "context7"] }),
+            vec![
+                json!({ "id": "work", "name": "Work", "agents": {
+                    "oracle": { "model": "openai/gpt-5" },
+                    "librarian": { "model": "anthropic/claude-sonnet-4" }
+                } }),
+                json!({ "id": "home", "name": "Home", "agents": { "oracle": { "model": "openai/o3" } } }),
+            ],
+        );
+
+        let changelog = bundle_changelog(&old, &new).unwrap();
+        assert_eq!(changelog.added, vec![ConfigRef { id: "home".to_string(), name: "Home".to_string() }]);
+        assert_eq!(changelog.removed, vec![ConfigRef { id: "legacy".to_string(), name: "Legacy".to_string() }]);
+        assert_eq!(changelog.changed.len(), 1);
+        assert_eq!(changelog.changed[0].id, "work");
+        assert_eq!(
+            changelog.changed[0].summary,
+            vec!["agent oracle model: openai/o3 -> openai/gpt-5", "agent librarian added"]
+        );
+        assert_eq!(changelog.global.len(), 1);
+        assert_eq!(changelog.global[0].key, "disabled_mcps");
+        assert_eq!(changelog.global[0].after, Some(json!(["websearch", "context7"])));
+    }
+}
@@ -15,8 +15,21 @@ use tauri::Emitter;
 #[tauri::command]
 pub async fn list_oh_my_opencode_configs(
     state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
 ) -> Result<Vec<OhMyOpenCodeConfig>, String> {
     let db = state.0.lock().await;
+    let cache_dir = super::binary_cache::cache_dir(&app);
+    list_configs(&db, cache_dir.as_deref(), crate::coding::metrics::recorder()).await
+}
+
+/// Configs ordered by name, importing the local config file into an empty database
+/// The local file is read through the binary cache in `cache_dir`, the load time and count go to `recorder`
+pub(crate) async fn list_configs(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    cache_dir: Option<&std::path::Path>,
+    recorder: &crate::coding::metrics::LoadRecorder,
+) -> Result<Vec<OhMyOpenCodeConfig>, String> {
+    let started = std::time::Instant::now();
 
     let records_result: Result<Vec<Value>, _> = db
         .query("SELECT *, type::string(id) as id FROM oh_my_opencode_config")
@@ -28,7 +41,7 @@ pub async fn list_oh_my_opencode_configs(
         Ok(records) => {
             // 如果数据库为空，尝试从本地配置文件导入
             if records.is_empty() {
-                if let Ok(imported_config) = import_local_config_if_exists(&db).await {
+                if let Ok(imported_config) = import_local_config_if_exists(db, cache_dir).await {
                     // 成功导入，返回包含这个配置的列表
                     return Ok(vec![imported_config]);
                 }
@@ -40,6 +53,7 @@ pub async fn list_oh_my_opencode_configs(
                 .collect();
             // Sort by name
             result.sort_by_key(|c| c.name.clone());
+            recorder.record_config_load(started.elapsed(), result.len());
             Ok(result)
         }
         Err(e) => {
@@ -71,51 +85,106 @@ pub fn get_oh_my_opencode_config_path() -> Result<std::path::PathBuf, String> {
     }
 }
 
+/// Top-level keys of the local config file that are not profile content
+const NON_PROFILE_KEYS: &[&str] = &[
+    "agents",
+    "$schema", // 移除 schema 字段，因为它不是配置内容
+    // 属于 Global Config 的字段，不应该放在 Agents Profile 的 other_fields 中
+    "sisyphus_agent",
+    "sisyphusAgent",
+    "disabled_agents",
+    "disabledAgents",
+    "disabled_mcps",
+    "disabledMcps",
+    "disabled_hooks",
+    "disabledHooks",
+    "lsp",
+    "experimental",
+];
+
+/// The local config file split into its Agents Profile and Global Config parts
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct LocalConfigFile {
+    agents: Option<Value>,
+    /// Everything that is neither agents nor a Global Config field
+    other_fields: Option<Value>,
+    schema: Option<String>,
+    sisyphus_agent: Option<Value>,
+    disabled_agents: Option<Vec<String>>,
+    disabled_mcps: Option<Vec<String>>,
+    disabled_hooks: Option<Vec<String>>,
+    lsp: Option<Value>,
+    experimental: Option<Value>,
+}
+
+impl LocalConfigFile {
+    fn from_value(json_value: &Value) -> Self {
+        // Lists written under both casings are merged, not picked one or the other
+        let mut json_value = json_value.clone();
+        super::disabled::merge_disabled_casings(&mut json_value);
+        let json_value = &json_value;
+        let field = |snake: &str, camel: &str| json_value.get(snake).or_else(|| json_value.get(camel)).cloned();
+        let list = |snake: &str, camel: &str| field(snake, camel).and_then(|v| serde_json::from_value(v).ok());
+
+        let mut other_fields = json_value.clone();
+        if let Some(obj) = other_fields.as_object_mut() {
+            for key in NON_PROFILE_KEYS {
+                obj.remove(*key);
+            }
+        }
+
+        Self {
+            agents: json_value.get("agents").and_then(|v| serde_json::from_value(v.clone()).ok()),
+            other_fields: Some(other_fields).filter(|v| v.as_object().map(|o| !o.is_empty()).unwrap_or(false)),
+            schema: json_value.get("$schema").and_then(|v| v.as_str()).map(String::from),
+            sisyphus_agent: field("sisyphus_agent", "sisyphusAgent"),
+            disabled_agents: list("disabled_agents", "disabledAgents"),
+            disabled_mcps: list("disabled_mcps", "disabledMcps"),
+            disabled_hooks: list("disabled_hooks", "disabledHooks"),
+            lsp: json_value.get("lsp").cloned(),
+            experimental: json_value.get("experimental").cloned(),
+        }
+    }
+
+    fn global_content(&self, other_fields: Option<Value>, updated_at: String) -> OhMyOpenCodeGlobalConfigContent {
+        OhMyOpenCodeGlobalConfigContent {
+            schema: self.schema.clone(),
+            sisyphus_agent: self.sisyphus_agent.clone(),
+            disabled_agents: self.disabled_agents.clone(),
+            disabled_mcps: self.disabled_mcps.clone(),
+            disabled_hooks: self.disabled_hooks.clone(),
+            lsp: self.lsp.clone(),
+            experimental: self.experimental.clone(),
+            other_fields,
+            updated_at,
+        }
+    }
+}
+
+/// Parse and split the local config file, through its binary cache while the file is unchanged
+fn load_local_config(
+    cache_dir: Option<&std::path::Path>,
+    config_path: &std::path::Path,
+) -> Result<LocalConfigFile, String> {
+    let (local, _) = super::binary_cache::load_with_cache(cache_dir, config_path, |content| {
+        super::raw_json::parse_linted(content)
+            .map(|value| LocalConfigFile::from_value(&value))
+            .map_err(|e| format!("Failed to parse local config file: {}", e))
+    })?;
+    Ok(local)
+}
+
 /// 从本地配置文件导入配置（如果存在）
 async fn import_local_config_if_exists(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    cache_dir: Option<&std::path::Path>,
 ) -> Result<OhMyOpenCodeConfig, String> {
     let config_path = get_oh_my_opencode_config_path()
         .map_err(|_| "Local config file not found".to_string())?;
 
-    // 读取文件内容
-    let file_content = fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read local config file: {}", e))?;
-
-    // 解析 JSON（使用 json5 支持带注释的 JSONC 格式）
-    let json_value: Value = json5::from_str(&file_content)
-        .map_err(|e| format!("Failed to parse local config file: {}", e))?;
-
-    // 提取 agents 配置
-    let agents = json_value
-        .get("agents")
-        .and_then(|v| serde_json::from_value(v.clone()).ok())
-        .unwrap_or_default();
-
-    // 提取 other_fields（除了 agents 和全局配置字段之外的所有字段）
+    // 解析 JSON（使用 json5 支持带注释的 JSONC 格式），文件未变化时直接读取二进制缓存
     // 全局配置字段会被同时导入到 Global Config 中
-    let mut other_fields = json_value.clone();
-    if let Some(obj) = other_fields.as_object_mut() {
-        obj.remove("agents");
-        obj.remove("$schema"); // 移除 schema 字段，因为它不是配置内容
-        // 移除属于 Global Config 的字段，这些字段不应该放在 Agents Profile 的 other_fields 中
-        obj.remove("sisyphus_agent");
-        obj.remove("sisyphusAgent");
-        obj.remove("disabled_agents");
-        obj.remove("disabledAgents");
-        obj.remove("disabled_mcps");
-        obj.remove("disabledMcps");
-        obj.remove("disabled_hooks");
-        obj.remove("disabledHooks");
-        obj.remove("lsp");
-        obj.remove("experimental");
-    }
-
-    let other_fields_value = if other_fields.as_object().map(|o| o.is_empty()).unwrap_or(true) {
-        None
-    } else {
-        Some(other_fields)
-    };
+    let local = load_local_config(cache_dir, &config_path)?;
 
     let now = Local::now().to_rfc3339();
 
@@ -133,46 +202,7 @@ async fn import_local_config_if_exists(
     };
 
     if should_import_global {
-        // 提取全局配置字段
-        let schema = json_value
-            .get("$schema")
-            .and_then(|v| v.as_str())
-            .map(String::from);
-
-        let sisyphus_agent = json_value
-            .get("sisyphus_agent")
-            .or_else(|| json_value.get("sisyphusAgent"))
-            .cloned();
-
-        let disabled_agents: Option<Vec<String>> = json_value
-            .get("disabled_agents")
-            .or_else(|| json_value.get("disabledAgents"))
-            .and_then(|v| serde_json::from_value(v.clone()).ok());
-
-        let disabled_mcps: Option<Vec<String>> = json_value
-            .get("disabled_mcps")
-            .or_else(|| json_value.get("disabledMcps"))
-            .and_then(|v| serde_json::from_value(v.clone()).ok());
-
-        let disabled_hooks: Option<Vec<String>> = json_value
-            .get("disabled_hooks")
-            .or_else(|| json_value.get("disabledHooks"))
-            .and_then(|v| serde_json::from_value(v.clone()).ok());
-
-        let lsp = json_value.get("lsp").cloned();
-        let experimental = json_value.get("experimental").cloned();
-
-        let global_content = OhMyOpenCodeGlobalConfigContent {
-            schema,
-            sisyphus_agent,
-            disabled_agents,
-            disabled_mcps,
-            disabled_hooks,
-            lsp,
-            experimental,
-            other_fields: None,
-            updated_at: now.clone(),
-        };
+        let global_content = local.global_content(None, now.clone());
 
         let global_json_data = adapter::global_config_to_db_value(&global_content);
 
@@ -190,8 +220,8 @@ async fn import_local_config_if_exists(
     let content = OhMyOpenCodeConfigContent {
         name: "本地配置".to_string(),
         is_applied: true, // 标记为已应用，因为这是从当前使用的配置导入的
-        agents,
-        other_fields: other_fields_value,
+        agents: local.agents,
+        other_fields: local.other_fields,
         created_at: now.clone(),
         updated_at: now,
     };
@@ -360,7 +390,7 @@ pub async fn update_oh_my_opencode_config(
 
     // 如果该配置当前是应用状态，立即重新写入到配置文件
     if is_applied_value {
-        if let Err(e) = apply_config_to_file(&db, &config_id).await {
+        if let Err(e) = apply_config_to_file(&db, &app, &config_id).await {
             eprintln!("Failed to auto-apply updated config: {}", e);
             // 不中断更新流程，只记录错误
         } else {
@@ -376,7 +406,7 @@ pub async fn update_oh_my_opencode_config(
         id: config_id,
         name: content.name,
         is_applied: is_applied_value,
-        agents: content.agents,
+        agents: content.agents.as_ref().map(|agents| super::agent::parse_agents(Some(agents))),
         other_fields: content.other_fields,
         created_at: Some(content.created_at),
         updated_at: Some(content.updated_at),
@@ -384,18 +414,34 @@ pub async fn update_oh_my_opencode_config(
 }
 
 /// Delete an oh-my-opencode config
+/// An applied config is refused unless `reassign_to` names a config to apply instead, or `force` is set
 #[tauri::command]
 pub async fn delete_oh_my_opencode_config(
     state: tauri::State<'_, DbState>,
     app: tauri::AppHandle,
     id: String,
+    reassign_to: Option<String>,
+    force: Option<bool>,
 ) -> Result<(), String> {
     let db = state.0.lock().await;
-
-    db.query(format!("DELETE oh_my_opencode_config:`{}`", id))
+    delete_config(&db, &app, &id, reassign_to.as_deref(), force.unwrap_or(false))
         .await
-        .map_err(|e| format!("Failed to delete config: {}", e))?;
+        .map_err(|e| e.to_string())
+}
+
+async fn delete_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle,
+    id: &str,
+    reassign_to: Option<&str>,
+    force: bool,
+) -> Result<(), super::delete::DeleteError> {
+    super::delete::delete_config_with(db, id, reassign_to, force, |target| async move {
+        apply_config_internal(db, app, &target, false).await
+    })
+    .await?;
 
+    let _ = app.emit("config-deleted", id);
     // Notify to refresh tray menu
     let _ = app.emit("config-changed", "window");
 
@@ -403,16 +449,18 @@ pub async fn delete_oh_my_opencode_config(
 }
 
 /// 内部函数：将指定配置应用到配置文件（不改变数据库中的 is_applied 状态）
-async fn apply_config_to_file(
+async fn apply_config_to_file<R: tauri::Runtime>(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
     config_id: &str,
 ) -> Result<(), String> {
-    apply_config_to_file_public(db, config_id).await
+    apply_config_to_file_public(db, app, config_id).await
 }
 
 /// Public version of apply_config_to_file for tray module
-pub async fn apply_config_to_file_public(
+pub async fn apply_config_to_file_public<R: tauri::Runtime>(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
     config_id: &str,
 ) -> Result<(), String> {
     // Get the config from database using direct ID format (like ClaudeCode)
@@ -446,120 +494,83 @@ pub async fn apply_config_to_file_public(
         }
     }
 
-    // 获取 Global Config
-    let global_records_result: Result<Vec<Value>, _> = db
-        .query("SELECT *, type::string(id) as id FROM oh_my_opencode_global_config:`global` LIMIT 1")
-        .await
-        .map_err(|e| format!("Failed to query global config: {}", e))?
-        .take(0);
-
-    let global_config = match global_records_result {
-        Ok(records) => {
-            if let Some(record) = records.first() {
-                adapter::global_config_from_db_value(record.clone())
-            } else {
-                // 使用默认空配置
-                OhMyOpenCodeGlobalConfig {
-                    id: "global".to_string(),
-                    schema: None,
-                    sisyphus_agent: None,
-                    disabled_agents: None,
-                    disabled_mcps: None,
-                    disabled_hooks: None,
-                    lsp: None,
-                    experimental: None,
-                    other_fields: None,
-                    updated_at: None,
-                }
-            }
-        }
-        Err(_) => {
-            // 使用默认空配置
-            OhMyOpenCodeGlobalConfig {
-                id: "global".to_string(),
-                schema: None,
-                sisyphus_agent: None,
-                disabled_agents: None,
-                disabled_mcps: None,
-                disabled_hooks: None,
-                lsp: None,
-                experimental: None,
-                other_fields: None,
-                updated_at: None,
-            }
-        }
-    };
+    let global_config = load_global_config(db).await?;
+    let final_json = super::render::render_opencode_json(&global_config, &agents_profile);
 
-    // 合并配置的优先级顺序（从低到高）：
-    // 1. 全局配置的明确字段（最低优先级）
-    // 2. 全局配置的 other_fields
-    // 3. Agents Profile 的 agents
-    // 4. Agents Profile 的 other_fields（最高优先级，可以覆盖所有）
+    // Write to file with pretty formatting
+    let json_content = serde_json::to_string_pretty(&final_json)
+        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
 
-    let mut final_json = serde_json::Map::new();
+    let previous_config_id = applied_config_id(db).await;
+    write_with_backup(app, &config_path, previous_config_id.as_deref(), config_id, || {
+        fs::write(&config_path, json_content).map_err(|e| format!("Failed to write config file: {}", e))
+    })
+}
 
-    // 使用保存的 schema 或默认 schema
-    let schema_url = global_config.schema
-        .unwrap_or_else(|| "https://raw.githubusercontent.com/code-yeongyu/oh-my-opencode/master/assets/oh-my-opencode.schema.json".to_string());
-    final_json.insert("$schema".to_string(), serde_json::json!(schema_url));
+/// Id of the config currently marked applied, `None` when there is none or the query fails
+async fn applied_config_id(db: &surrealdb::Surreal<surrealdb::engine::local::Db>) -> Option<String> {
+    let records: Vec<Value> = db
+        .query("SELECT type::string(id) as id FROM oh_my_opencode_config WHERE is_applied = true LIMIT 1")
+        .await
+        .ok()?
+        .take(0)
+        .ok()?;
+    records
+        .first()
+        .and_then(|record| record.get("id"))
+        .and_then(|id| id.as_str())
+        .map(crate::coding::db_clean_id)
+}
 
-    // 1. 先设置全局配置的明确字段（优先级最低）
-    if let Some(sisyphus) = global_config.sisyphus_agent {
-        final_json.insert("sisyphus_agent".to_string(), sisyphus);
-    }
-    if let Some(disabled_agents) = global_config.disabled_agents {
-        final_json.insert("disabled_agents".to_string(), serde_json::json!(disabled_agents));
-    }
-    if let Some(disabled_mcps) = global_config.disabled_mcps {
-        final_json.insert("disabled_mcps".to_string(), serde_json::json!(disabled_mcps));
-    }
-    if let Some(disabled_hooks) = global_config.disabled_hooks {
-        final_json.insert("disabled_hooks".to_string(), serde_json::json!(disabled_hooks));
-    }
-    if let Some(lsp) = global_config.lsp {
-        final_json.insert("lsp".to_string(), lsp);
-    }
-    if let Some(experimental) = global_config.experimental {
-        final_json.insert("experimental".to_string(), experimental);
-    }
+/// Backup directory of the app, see `backups::backup_dir_in`
+fn backup_dir<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<std::path::PathBuf, String> {
+    use tauri::Manager;
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    Ok(super::backups::backup_dir_in(&app_data_dir))
+}
 
-    // 2. 然后平铺全局配置的 other_fields（会覆盖上面的明确字段）
-    if let Some(global_others) = global_config.other_fields {
-        if let Some(others_obj) = global_others.as_object() {
-            for (key, value) in others_obj {
-                final_json.insert(key.clone(), value.clone());
-            }
+/// Write the config file through `backups::apply_with_backup`, so the write can be reverted
+/// Backup problems never block the write, they are only logged
+fn write_with_backup<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    config_path: &std::path::Path,
+    previous_config_id: Option<&str>,
+    config_id: &str,
+    write: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    match backup_dir(app) {
+        Ok(backup_dir) => {
+            super::backups::apply_with_backup(config_path, &backup_dir, previous_config_id, config_id, write)?;
+            Ok(())
         }
-    }
-
-    // 3. 设置 Agents Profile 的 agents（会覆盖前面的 agents）
-    if let Some(agents) = agents_profile.agents {
-        final_json.insert("agents".to_string(), agents);
-    }
-
-    // 4. 最后平铺 Agents Profile 的 other_fields（最高优先级，可以覆盖所有字段）
-    if let Some(profile_others) = agents_profile.other_fields {
-        if let Some(others_obj) = profile_others.as_object() {
-            for (key, value) in others_obj {
-                final_json.insert(key.clone(), value.clone());
-            }
+        Err(e) => {
+            eprintln!("Failed to back up config before writing: {}", e);
+            write()
         }
     }
+}
+/// Load the global config, falling back to an empty default if missing or unreadable
+pub(crate) async fn load_global_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<OhMyOpenCodeGlobalConfig, String> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query("SELECT *, type::string(id) as id FROM oh_my_opencode_global_config:`global` LIMIT 1")
+        .await
+        .map_err(|e| format!("Failed to query global config: {}", e))?
+        .take(0);
 
-    let mut final_json = Value::Object(final_json);
-
-    // 清理空值：删除空对象和空数组
-    adapter::clean_empty_values(&mut final_json);
-
-    // Write to file with pretty formatting
-    let json_content = serde_json::to_string_pretty(&final_json)
-        .map_err(|e| format!("Failed to serialize final config: {}", e))?;
-
-    fs::write(&config_path, json_content)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
-
-    Ok(())
+    Ok(match records_result {
+        Ok(records) => records
+            .first()
+            .map(|record| adapter::global_config_from_db_value(record.clone()))
+            .unwrap_or_default(),
+        Err(_) => OhMyOpenCodeGlobalConfig::default(),
+    })
 }
+
 /// Apply an oh-my-opencode config to the JSON file
 #[tauri::command]
 pub async fn apply_oh_my_opencode_config(
@@ -572,6 +583,110 @@ pub async fn apply_oh_my_opencode_config(
     Ok(())
 }
 
+/// Applies recorded with a backup of the file they replaced, oldest first
+#[tauri::command]
+pub async fn list_oh_my_opencode_apply_log(
+    app: tauri::AppHandle,
+) -> Result<Vec<super::backups::ApplyLogEntry>, String> {
+    let backup_dir = backup_dir(&app)?;
+    super::backups::read_apply_log(&backup_dir).map_err(|e| e.to_string())
+}
+
+/// Prune old backups: per-config retention first, then oldest first down to `max_total_bytes`
+#[tauri::command]
+pub async fn compact_oh_my_opencode_backups(
+    app: tauri::AppHandle,
+    retention_per_config: usize,
+    max_total_bytes: u64,
+) -> Result<super::backups::CompactReport, String> {
+    let backup_dir = backup_dir(&app)?;
+    super::backups::compact_backups(&backup_dir, retention_per_config, max_total_bytes).map_err(|e| e.to_string())
+}
+
+/// Restore the config file exactly as it was before the last apply
+/// The config applied before it is marked applied again
+#[tauri::command]
+pub async fn revert_oh_my_opencode_last_apply(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<super::backups::ApplyLogEntry, String> {
+    let db = state.0.lock().await;
+    let backup_dir = backup_dir(&app)?;
+    let entry = super::backups::revert_last_apply(&backup_dir).map_err(|e| e.to_string())?;
+
+    let now = Local::now().to_rfc3339();
+    db.query("UPDATE oh_my_opencode_config SET is_applied = false, updated_at = $now WHERE is_applied = true")
+        .bind(("now", now.clone()))
+        .await
+        .map_err(|e| format!("Failed to clear applied flags: {}", e))?;
+    if let Some(previous_id) = &entry.previous_config_id {
+        db.query("UPDATE oh_my_opencode_config SET is_applied = true, updated_at = $now WHERE id = type::thing('oh_my_opencode_config', $id)")
+            .bind(("id", previous_id.clone()))
+            .bind(("now", now))
+            .await
+            .map_err(|e| format!("Failed to update applied flag: {}", e))?;
+    }
+
+    let _ = app.emit("config-changed", "window");
+
+    #[cfg(target_os = "windows")]
+    let _ = app.emit("wsl-sync-request-opencode", ());
+
+    Ok(entry)
+}
+
+/// Write a config to several oh-my-opencode files (e.g. of other project directories)
+/// Every target is backed up and logged, a failing target does not stop the others
+/// The applied flag is not changed
+#[tauri::command]
+pub async fn apply_oh_my_opencode_config_to_targets(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+    targets: Vec<String>,
+) -> Result<Vec<super::targets::TargetOutcome>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    let backup_dir = backup_dir(&app)?;
+    Ok(super::targets::apply_to_targets(&global_config, &config, &targets, &backup_dir)
+        .into_iter()
+        .map(|(target, result)| super::targets::TargetOutcome {
+            target,
+            error: result.err().map(|e| e.to_string()),
+        })
+        .collect())
+}
+
+/// Write only the global portions of the config file, keeping the applied profile's sections
+/// Returns the changed top-level keys
+#[tauri::command]
+pub async fn apply_oh_my_opencode_global_only(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<super::global_apply::ConfigDiff>, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    // The applied profile stays applied, its keys keep precedence over the global config
+    let applied = applied_config_id(&db).await;
+    let profile = match applied.as_deref() {
+        Some(id) => Some(load_config_by_id(&db, id).await?),
+        None => None,
+    };
+    let config_path = get_oh_my_opencode_config_path()?;
+    let (merged, diffs) = super::global_apply::merge_global_into_file(&config_path, &global_config, profile.as_ref())
+        .map_err(|e| e.to_string())?;
+    if diffs.is_empty() {
+        return Ok(diffs);
+    }
+
+    // The log entry records the applied profile on both sides
+    write_with_backup(&app, &config_path, applied.as_deref(), applied.as_deref().unwrap_or_default(), || {
+        super::global_apply::write_merged(&config_path, &merged).map_err(|e| e.to_string())
+    })?;
+    Ok(diffs)
+}
+
 /// Internal function to apply config: writes to file and updates database
 /// This is the single source of truth for applying an Oh My OpenCode config
 pub async fn apply_config_internal<R: tauri::Runtime>(
@@ -580,10 +695,28 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
     config_id: &str,
     from_tray: bool,
 ) -> Result<(), String> {
-    // 应用配置到文件
-    apply_config_to_file(db, config_id).await?;
+    // 应用配置到文件, the live file is backed up so this apply can be reverted exactly
+    apply_config_to_file(db, app, config_id).await?;
+
+    mark_applied(db, config_id).await?;
+
+    // Notify based on source
+    let payload = if from_tray { "tray" } else { "window" };
+    let _ = app.emit("config-changed", payload);
+
+    // Trigger WSL sync via event (Windows only)
+    #[cfg(target_os = "windows")]
+    let _ = app.emit("wsl-sync-request-opencode", ());
+
+    Ok(())
+}
 
-    // Update database - set all configs to not applied, then set this one to applied
+/// Set `config_id` as the only applied config in the database
+pub(crate) async fn mark_applied(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config_id: &str,
+) -> Result<(), String> {
+    // Set all configs to not applied, then set this one to applied
     let now = Local::now().to_rfc3339();
 
     // Clear applied flag (only update the currently applied one)
@@ -599,15 +732,77 @@ pub async fn apply_config_internal<R: tauri::Runtime>(
         .await
         .map_err(|e| format!("Failed to update applied flag: {}", e))?;
 
-    // Notify based on source
-    let payload = if from_tray { "tray" } else { "window" };
-    let _ = app.emit("config-changed", payload);
+    Ok(())
+}
 
-    // Trigger WSL sync via event (Windows only)
-    #[cfg(target_os = "windows")]
-    let _ = app.emit("wsl-sync-request-opencode", ());
+/// Merge content-identical configs, keeping the oldest of each group
+#[tauri::command]
+pub async fn merge_oh_my_opencode_duplicate_configs(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<super::duplicates::MergeDupReport, String> {
+    let db = state.0.lock().await;
+    let mut configs = load_all_configs(&db).await?;
+    let report = super::duplicates::merge_duplicate_configs(&mut configs);
+    if report.merges.is_empty() {
+        return Ok(report);
+    }
 
-    Ok(())
+    for id in report.removed_ids() {
+        db.query(format!("DELETE oh_my_opencode_config:`{}`", id))
+            .await
+            .map_err(|e| format!("Failed to delete config: {}", e))?;
+    }
+
+    // The applied duplicate was removed, apply the kept one instead
+    if let Some(merge) = report.merges.iter().find(|m| m.applied_moved) {
+        apply_config_internal(&db, &app, &merge.kept, false).await?;
+    } else {
+        let _ = app.emit("config-changed", "window");
+    }
+
+    Ok(report)
+}
+
+/// Rewrite the timestamps of every config to UTC RFC 3339
+/// Returns the changes per config id, unparseable timestamps are reported and left as-is
+#[tauri::command]
+pub async fn normalize_oh_my_opencode_timestamps(
+    state: tauri::State<'_, DbState>,
+) -> Result<std::collections::HashMap<String, Vec<super::timestamps::TimestampChange>>, String> {
+    let db = state.0.lock().await;
+    let configs = load_all_configs(&db).await?;
+
+    let mut result = std::collections::HashMap::new();
+    for config in configs {
+        let mut content = OhMyOpenCodeConfigContent {
+            name: config.name,
+            is_applied: config.is_applied,
+            agents: config.agents_value(),
+            other_fields: config.other_fields,
+            created_at: config.created_at.unwrap_or_default(),
+            updated_at: config.updated_at.unwrap_or_default(),
+        };
+        let changes = super::timestamps::normalize_timestamps(&mut content);
+        if changes.is_empty() {
+            continue;
+        }
+
+        let modified = changes
+            .iter()
+            .any(|c| c.kind == super::timestamps::TimestampChangeKind::Normalized);
+        if modified {
+            // Inline JSON like update_oh_my_opencode_config to avoid enum<bool> binding issues
+            let json_str = serde_json::to_string(&adapter::to_db_value(&content))
+                .map_err(|e| format!("Failed to serialize json_data: {}", e))?;
+            db.query(format!("UPDATE oh_my_opencode_config:`{}` CONTENT {}", config.id, json_str))
+                .await
+                .map_err(|e| format!("Failed to update config: {}", e))?;
+        }
+        result.insert(config.id, changes);
+    }
+
+    Ok(result)
 }
 
 /// Reorder oh-my-opencode configs (by name for now)
@@ -657,6 +852,13 @@ pub async fn check_oh_my_opencode_config_exists() -> Result<bool, String> {
     Ok(jsonc_path.exists() || json_path.exists())
 }
 
+/// Migrate a stored config record to a specific schema version, for debugging migrations
+/// Downgrades are rejected
+#[tauri::command]
+pub fn migrate_oh_my_opencode_record(value: Value, target_version: u32) -> Result<Value, String> {
+    super::migration::migrate_to_version(value, target_version).map_err(|e| e.to_string())
+}
+
 // ============================================================================
 // Oh My OpenCode Global Config Commands
 // ============================================================================
@@ -665,6 +867,7 @@ pub async fn check_oh_my_opencode_config_exists() -> Result<bool, String> {
 #[tauri::command]
 pub async fn get_oh_my_opencode_global_config(
     state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
 ) -> Result<OhMyOpenCodeGlobalConfig, String> {
     let db = state.0.lock().await;
 
@@ -680,7 +883,7 @@ pub async fn get_oh_my_opencode_global_config(
                 Ok(adapter::global_config_from_db_value(record.clone()))
             } else {
                 // 数据库为空，尝试从本地文件导入全局配置
-                if let Ok(imported_config) = import_local_global_config_if_exists(&db).await {
+                if let Ok(imported_config) = import_local_global_config_if_exists(&db, super::binary_cache::cache_dir(&app).as_deref()).await {
                     return Ok(imported_config);
                 }
 
@@ -743,90 +946,16 @@ pub async fn get_oh_my_opencode_global_config(
 /// 从本地配置文件导入全局配置（如果存在）
 async fn import_local_global_config_if_exists(
     db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    cache_dir: Option<&std::path::Path>,
 ) -> Result<OhMyOpenCodeGlobalConfig, String> {
     let config_path = get_oh_my_opencode_config_path()
         .map_err(|_| "Local config file not found".to_string())?;
 
-    // 读取文件内容
-    let file_content = fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read local config file: {}", e))?;
-
-    // 解析 JSON（使用 json5 支持带注释的 JSONC 格式）
-    let json_value: Value = json5::from_str(&file_content)
-        .map_err(|e| format!("Failed to parse local config file: {}", e))?;
-    
-    // 提取全局配置字段
-    let sisyphus_agent = json_value
-        .get("sisyphus_agent")
-        .or_else(|| json_value.get("sisyphusAgent"))
-        .and_then(|v| serde_json::from_value(v.clone()).ok());
-    
-    let disabled_agents = json_value
-        .get("disabled_agents")
-        .or_else(|| json_value.get("disabledAgents"))
-        .and_then(|v| serde_json::from_value(v.clone()).ok());
-    
-    let disabled_mcps = json_value
-        .get("disabled_mcps")
-        .or_else(|| json_value.get("disabledMcps"))
-        .and_then(|v| serde_json::from_value(v.clone()).ok());
-    
-    let disabled_hooks = json_value
-        .get("disabled_hooks")
-        .or_else(|| json_value.get("disabledHooks"))
-        .and_then(|v| serde_json::from_value(v.clone()).ok());
-    
-    let lsp = json_value
-        .get("lsp")
-        .and_then(|v| serde_json::from_value(v.clone()).ok());
-    
-    let experimental = json_value
-        .get("experimental")
-        .and_then(|v| serde_json::from_value(v.clone()).ok());
-
-    // 提取 schema
-    let schema = json_value
-        .get("$schema")
-        .and_then(|v| v.as_str())
-        .map(String::from);
-
-    // 提取 other_fields（除了已知字段之外的所有字段）
-    let mut other_fields = json_value.clone();
-    if let Some(obj) = other_fields.as_object_mut() {
-        obj.remove("agents");
-        obj.remove("$schema");
-        obj.remove("sisyphus_agent");
-        obj.remove("sisyphusAgent");
-        obj.remove("disabled_agents");
-        obj.remove("disabledAgents");
-        obj.remove("disabled_mcps");
-        obj.remove("disabledMcps");
-        obj.remove("disabled_hooks");
-        obj.remove("disabledHooks");
-        obj.remove("lsp");
-        obj.remove("experimental");
-    }
-    
-    let other_fields_value = if other_fields.as_object().map(|o| o.is_empty()).unwrap_or(true) {
-        None
-    } else {
-        Some(other_fields)
-    };
-
-    let now = Local::now().to_rfc3339();
+    // 解析 JSON（使用 json5 支持带注释的 JSONC 格式），文件未变化时直接读取二进制缓存
+    let local = load_local_config(cache_dir, &config_path)?;
 
-    // 创建全局配置内容（不包含 config_id）
-    let content = OhMyOpenCodeGlobalConfigContent {
-        schema,
-        sisyphus_agent,
-        disabled_agents,
-        disabled_mcps,
-        disabled_hooks,
-        lsp,
-        experimental,
-        other_fields: other_fields_value,
-        updated_at: now,
-    };
+    // 创建全局配置内容（不包含 config_id），除已知字段之外的所有字段放入 other_fields
+    let content = local.global_content(local.other_fields.clone(), Local::now().to_rfc3339());
 
     let json_data = adapter::global_config_to_db_value(&content);
 
@@ -896,7 +1025,7 @@ pub async fn save_oh_my_opencode_global_config(
         if let Some(record) = records.first() {
             let applied_config = adapter::from_db_value(record.clone());
             // 重新应用配置到文件（不改变数据库中的 is_applied 状态）
-            if apply_config_to_file(&db, &applied_config.id).await.is_ok() {
+            if apply_config_to_file(&db, &app, &applied_config.id).await.is_ok() {
                 // Trigger WSL sync via event (Windows only)
                 #[cfg(target_os = "windows")]
                 let _ = app.emit("wsl-sync-request-opencode", ());
@@ -922,3 +1051,1114 @@ pub async fn save_oh_my_opencode_global_config(
         Err(e) => Err(format!("Failed to save global config: {}", e)),
     }
 }
+
+/// Empty the global disabled lists (agents, MCPs, hooks), locked agents stay disabled
+/// Returns how many entries were cleared from each list
+#[tauri::command]
+pub async fn clear_oh_my_opencode_disabled(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<super::disabled::ClearReport, String> {
+    let db = state.0.lock().await;
+    let mut global_config = load_global_config(&db).await?;
+    let configs = load_all_configs(&db).await?;
+    let locked = super::disabled::locked_agents(&configs);
+
+    let report = super::disabled::clear_all_disabled(&mut global_config, &locked);
+    if report.total() == 0 {
+        return Ok(report);
+    }
+    save_global_config_record(&db, &mut global_config).await?;
+
+    if let Some(applied) = configs.iter().find(|c| c.is_applied) {
+        if apply_config_to_file(&db, &app, &applied.id).await.is_ok() {
+            // Trigger WSL sync via event (Windows only)
+            #[cfg(target_os = "windows")]
+            let _ = app.emit("wsl-sync-request-opencode", ());
+        }
+    }
+    let _ = app.emit("config-changed", "window");
+    Ok(report)
+}
+
+// ============================================================================
+// Oh My OpenCode Agent Commands
+// ============================================================================
+
+/// Load a single oh-my-opencode config by ID
+async fn load_config_by_id(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config_id: &str,
+) -> Result<OhMyOpenCodeConfig, String> {
+    let records_result: Result<Vec<Value>, _> = db
+        .query(format!(
+            "SELECT *, type::string(id) as id FROM oh_my_opencode_config:`{}` LIMIT 1",
+            config_id
+        ))
+        .await
+        .map_err(|e| format!("Failed to query config: {}", e))?
+        .take(0);
+
+    match records_result {
+        Ok(records) => records
+            .first()
+            .map(|record| adapter::from_db_value(record.clone()))
+            .ok_or_else(|| format!("Config '{}' not found", config_id)),
+        Err(e) => Err(format!("Failed to get config: {}", e)),
+    }
+}
+
+/// Load all oh-my-opencode configs, without importing from the local file
+pub(crate) async fn load_all_configs(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+) -> Result<Vec<OhMyOpenCodeConfig>, String> {
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM oh_my_opencode_config")
+        .await
+        .map_err(|e| format!("Failed to query configs: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to deserialize configs: {}", e))?;
+
+    Ok(records.into_iter().map(adapter::from_db_value).collect())
+}
+
+/// Persist an already loaded global config (does not re-apply)
+async fn save_global_config_record(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    global_config: &mut OhMyOpenCodeGlobalConfig,
+) -> Result<(), String> {
+    let now = Local::now().to_rfc3339();
+    let content = OhMyOpenCodeGlobalConfigContent {
+        schema: global_config.schema.clone(),
+        sisyphus_agent: global_config.sisyphus_agent.clone(),
+        disabled_agents: global_config.disabled_agents.clone(),
+        disabled_mcps: global_config.disabled_mcps.clone(),
+        disabled_hooks: global_config.disabled_hooks.clone(),
+        lsp: global_config.lsp.clone(),
+        experimental: global_config.experimental.clone(),
+        other_fields: global_config.other_fields.clone(),
+        updated_at: now.clone(),
+    };
+
+    let json_data = adapter::global_config_to_db_value(&content);
+    db.query("UPSERT oh_my_opencode_global_config:`global` CONTENT $data")
+        .bind(("data", json_data))
+        .await
+        .map_err(|e| format!("Failed to save global config: {}", e))?;
+
+    global_config.updated_at = Some(now);
+    Ok(())
+}
+
+/// Persist an already loaded config and re-apply it to file if it is the applied one
+async fn save_config_record<R: tauri::Runtime>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    app: &tauri::AppHandle<R>,
+    config: &mut OhMyOpenCodeConfig,
+) -> Result<(), String> {
+    let now = Local::now().to_rfc3339();
+    let content = OhMyOpenCodeConfigContent {
+        name: config.name.clone(),
+        is_applied: config.is_applied,
+        agents: config.agents_value(),
+        other_fields: config.other_fields.clone(),
+        created_at: config.created_at.clone().unwrap_or_else(|| now.clone()),
+        updated_at: now.clone(),
+    };
+
+    let json_data = adapter::to_db_value(&content);
+
+    // Inline JSON like update_oh_my_opencode_config to avoid enum<bool> binding issues
+    let json_str = serde_json::to_string(&json_data)
+        .map_err(|e| format!("Failed to serialize json_data: {}", e))?;
+
+    db.query(format!("UPDATE oh_my_opencode_config:`{}` CONTENT {}", config.id, json_str))
+        .await
+        .map_err(|e| format!("Failed to update config: {}", e))?;
+
+    config.updated_at = Some(now);
+
+    if config.is_applied {
+        if let Err(e) = apply_config_to_file(db, app, &config.id).await {
+            eprintln!("Failed to auto-apply updated config: {}", e);
+        } else {
+            // Trigger WSL sync via event (Windows only)
+            #[cfg(target_os = "windows")]
+            let _ = app.emit("wsl-sync-request-opencode", ());
+        }
+    }
+
+    let _ = app.emit("config-changed", "window");
+
+    Ok(())
+}
+
+/// Get agent names of a config grouped by their UI group
+#[tauri::command]
+pub async fn get_oh_my_opencode_agent_groups(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<std::collections::BTreeMap<String, Vec<String>>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    Ok(super::agent::group_agents(&config))
+}
+
+/// Set or clear the UI group of an agent
+#[tauri::command]
+pub async fn set_oh_my_opencode_agent_group(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+    agent_name: String,
+    group: Option<String>,
+) -> Result<OhMyOpenCodeConfig, String> {
+    let db = state.0.lock().await;
+    let mut config = load_config_by_id(&db, &config_id).await?;
+    super::agent::set_agent_group(&mut config, &agent_name, group)?;
+    save_config_record(&db, &app, &mut config).await?;
+    Ok(config)
+}
+
+/// Set the same model on several agents of a config
+/// Returns the number of agents changed
+#[tauri::command]
+pub async fn set_oh_my_opencode_agents_model(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+    agent_names: Vec<String>,
+    model: String,
+) -> Result<usize, String> {
+    let db = state.0.lock().await;
+    let mut config = load_config_by_id(&db, &config_id).await?;
+    let changed = super::agent::set_model_for_agents(&mut config, &agent_names, &model)
+        .map_err(|e| e.to_string())?;
+    if changed > 0 {
+        save_config_record(&db, &app, &mut config).await?;
+    }
+    Ok(changed)
+}
+
+/// Apply the same field changes to every unlocked agent of a config matching the filter
+/// Returns the number of agents changed
+#[tauri::command]
+pub async fn bulk_edit_oh_my_opencode_agents(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+    filter: super::bulk_edit::AgentFilter,
+    edit: super::bulk_edit::AgentEdit,
+) -> Result<usize, String> {
+    let db = state.0.lock().await;
+    let mut config = load_config_by_id(&db, &config_id).await?;
+    let changed = super::bulk_edit::bulk_edit_agents(&mut config, &filter, &edit).map_err(|e| e.to_string())?;
+    if changed > 0 {
+        save_config_record(&db, &app, &mut config).await?;
+    }
+    Ok(changed)
+}
+
+/// Normalize suspicious characters in agent prompts (all agents when `agent_names` is not set)
+/// Smart quotes are only replaced with `smart_quotes`
+/// Returns the agents whose prompt changed
+#[tauri::command]
+pub async fn sanitize_oh_my_opencode_agent_prompts(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+    agent_names: Option<Vec<String>>,
+    smart_quotes: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let db = state.0.lock().await;
+    let mut config = load_config_by_id(&db, &config_id).await?;
+    let changed = super::prompt_encoding::sanitize_agent_prompts(
+        &mut config,
+        agent_names.as_deref(),
+        smart_quotes.unwrap_or(false),
+    );
+    if !changed.is_empty() {
+        save_config_record(&db, &app, &mut config).await?;
+    }
+    Ok(changed)
+}
+
+/// Groups of agent names of a config that only differ in case, whitespace or unicode form
+#[tauri::command]
+pub async fn find_oh_my_opencode_agent_name_collisions(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<Vec<Vec<String>>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    Ok(super::agent::find_agent_name_collisions(&config))
+}
+
+/// Get the content-based id of each agent in a config (agent name -> content id)
+/// Agents with equal ids are the same agent under different names
+#[tauri::command]
+pub async fn get_oh_my_opencode_agent_content_ids(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<std::collections::HashMap<String, String>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    Ok(config.typed_agents()
+        .iter()
+        .map(|(name, agent)| (name.clone(), super::agent::agent_content_id(agent)))
+        .collect())
+}
+
+/// Import agents from a directory with one file per agent (.json/.jsonc/.toml/.yaml)
+/// Returns the parsed agents for preview, files that failed are listed in `errors`
+#[tauri::command]
+pub async fn import_oh_my_opencode_agents_from_dir(
+    dir: String,
+) -> Result<super::import::AgentDirImport, String> {
+    super::import::import_agents_from_dir(std::path::Path::new(&dir)).map_err(|e| e.to_string())
+}
+
+/// Export only the global config, secrets redacted
+#[tauri::command]
+pub async fn export_oh_my_opencode_global_config(state: tauri::State<'_, DbState>) -> Result<Value, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    Ok(super::bundle::export_global_config(&global_config))
+}
+
+/// Lint raw config text before parsing: syntax errors with positions, duplicate and unquoted keys
+#[tauri::command]
+pub fn lint_oh_my_opencode_raw_json(raw: String) -> Vec<super::raw_json::RawJsonIssue> {
+    super::raw_json::lint_raw_json(&raw)
+}
+
+/// Read a global config export for preview, nothing is saved
+#[tauri::command]
+pub fn import_oh_my_opencode_global_config(value: Value) -> Result<OhMyOpenCodeGlobalConfigContent, String> {
+    super::bundle::import_global_config(&value).map_err(|e| e.to_string())
+}
+
+/// Export the global config and configs as a passphrase-protected bundle
+/// All configs are exported when `config_ids` is not set, record keys are snake_case unless `key_case` says otherwise
+#[tauri::command]
+pub async fn export_oh_my_opencode_encrypted_bundle(
+    state: tauri::State<'_, DbState>,
+    path: String,
+    passphrase: String,
+    config_ids: Option<Vec<String>>,
+    key_case: Option<super::key_case::KeyCase>,
+) -> Result<(), String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let mut configs = load_all_configs(&db).await?;
+    if let Some(ids) = config_ids {
+        configs.retain(|c| ids.contains(&c.id));
+    }
+    super::bundle::export_encrypted_bundle(
+        std::path::Path::new(&path),
+        &passphrase,
+        &global_config,
+        &configs,
+        key_case.unwrap_or_default(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Decrypt a bundle for preview, nothing is saved
+#[tauri::command]
+pub async fn import_oh_my_opencode_encrypted_bundle(
+    path: String,
+    passphrase: String,
+) -> Result<(OhMyOpenCodeGlobalConfig, Vec<OhMyOpenCodeConfig>), String> {
+    super::bundle::import_encrypted_bundle(std::path::Path::new(&path), &passphrase)
+        .map_err(|e| e.to_string())
+}
+
+/// Changelog between two versions of a bundle, nothing is saved
+#[tauri::command]
+pub async fn get_oh_my_opencode_bundle_changelog(
+    old_bundle: Value,
+    new_bundle: Value,
+) -> Result<super::changelog::BundleChangelog, String> {
+    super::changelog::bundle_changelog(&old_bundle, &new_bundle).map_err(|e| e.to_string())
+}
+
+/// Import the configs of a bundle into the store, id collisions are handled by `resolution`
+/// Overwritten configs that are applied are re-applied to file
+#[tauri::command]
+pub async fn import_oh_my_opencode_bundle_resolved(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    bundle: Value,
+    resolution: super::bundle::ConflictResolution,
+) -> Result<super::bundle::ImportResult, String> {
+    let db = state.0.lock().await;
+    let existing = load_all_configs(&db).await?;
+    let mut result = super::bundle::import_bundle_resolved(&bundle, &existing, resolution)
+        .map_err(|e| e.to_string())?;
+
+    for config in result.configs.iter_mut() {
+        if existing.iter().any(|c| c.id == config.id) {
+            save_config_record(&db, &app, config).await?;
+        } else {
+            insert_imported_config(&db, config).await?;
+        }
+    }
+
+    let _ = app.emit("config-changed", "window");
+    Ok(result)
+}
+
+/// Store an imported config under its own id, never as the applied one
+async fn insert_imported_config(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config: &mut OhMyOpenCodeConfig,
+) -> Result<(), String> {
+    let now = Local::now().to_rfc3339();
+    let content = OhMyOpenCodeConfigContent {
+        name: config.name.clone(),
+        is_applied: false,
+        agents: config.agents_value(),
+        other_fields: config.other_fields.clone(),
+        created_at: config.created_at.clone().unwrap_or_else(|| now.clone()),
+        updated_at: now.clone(),
+    };
+    let json_str = serde_json::to_string(&adapter::to_db_value(&content))
+        .map_err(|e| format!("Failed to serialize json_data: {}", e))?;
+    db.query(format!("UPSERT type::thing('oh_my_opencode_config', $id) CONTENT {}", json_str))
+        .bind(("id", config.id.clone()))
+        .await
+        .map_err(|e| format!("Failed to import config '{}': {}", config.name, e))?;
+    config.is_applied = false;
+    config.updated_at = Some(now);
+    Ok(())
+}
+
+/// Import a bundle file of any size, configs are parsed and stored one at a time
+/// Configs whose id already exists, repeats an earlier one or is not a valid id are stored under a new id
+/// Progress is sent as `oh-my-opencode-import-progress`, returns the number of imported configs
+/// and the bundle's global config, which is not saved: applying it is left to the caller
+#[tauri::command]
+pub async fn import_oh_my_opencode_bundle_streaming(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<super::bundle_stream::StreamImportSummary, String> {
+    use super::bundle_stream::{import_bundle_streaming, CountingReader, ImportProgress, StreamImportSummary};
+    use std::sync::atomic::Ordering;
+
+    let file = fs::File::open(&path).map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let reader = CountingReader::new(file);
+    let bytes_read = reader.counter();
+
+    // The parser runs on a blocking thread and waits for each config to be stored
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<OhMyOpenCodeConfig>(1);
+    let parser = tauri::async_runtime::spawn_blocking(move || {
+        import_bundle_streaming(reader, |_, config| {
+            tx.blocking_send(config)
+                .map_err(|_| super::import::ImportError::InvalidBundle("Import cancelled".to_string()))
+        })
+    });
+
+    let db = state.0.lock().await;
+    let mut existing_ids: Vec<String> = load_all_configs(&db).await?.into_iter().map(|c| c.id).collect();
+    let mut imported = 0;
+    let mut store_error = None;
+    while let Some(mut config) = rx.recv().await {
+        if !super::bundle::is_valid_config_id(&config.id) || existing_ids.contains(&config.id) {
+            config.id = uuid::Uuid::new_v4().simple().to_string();
+        }
+        if let Err(e) = insert_imported_config(&db, &mut config).await {
+            // Dropping the receiver stops the parser
+            store_error = Some(e);
+            break;
+        }
+        existing_ids.push(config.id);
+        imported += 1;
+        let _ = app.emit(
+            "oh-my-opencode-import-progress",
+            ImportProgress {
+                imported,
+                bytes_read: bytes_read.load(Ordering::Relaxed),
+                total_bytes,
+            },
+        );
+    }
+    drop(rx);
+
+    let parsed = parser.await.map_err(|e| format!("Import task failed: {}", e))?;
+    if imported > 0 {
+        let _ = app.emit("config-changed", "window");
+    }
+    if let Some(e) = store_error {
+        return Err(e);
+    }
+    let summary = parsed.map_err(|e| e.to_string())?;
+    Ok(StreamImportSummary { imported, ..summary })
+}
+
+/// Import an opencode markdown agent file for preview
+/// The name comes from the frontmatter `name`, or the file name when missing
+#[tauri::command]
+pub async fn import_oh_my_opencode_agent_markdown(
+    path: String,
+) -> Result<(String, super::agent::AgentProfile), String> {
+    let path = std::path::Path::new(&path);
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let (name, agent) =
+        super::markdown::parse_agent_markdown(&content).map_err(|e| e.to_string())?;
+
+    let name = name
+        .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(String::from))
+        .ok_or_else(|| super::import::ImportError::MissingName.to_string())?;
+    Ok((name, agent))
+}
+
+/// Write the agents of a config as opencode markdown agent files (`<name>.md`) into a directory
+/// Returns the written file paths
+#[tauri::command]
+pub async fn export_oh_my_opencode_agents_markdown(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    dir: String,
+) -> Result<Vec<String>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let files = super::markdown::export_agents_as_markdown(&config).map_err(|e| e.to_string())?;
+
+    let dir = std::path::Path::new(&dir);
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+
+    let mut names: Vec<&String> = files.keys().collect();
+    names.sort();
+
+    let mut written = Vec::new();
+    for name in names {
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(format!("Agent name '{}' is not a valid file name", name));
+        }
+        let path = dir.join(format!("{}.md", name));
+        fs::write(&path, &files[name])
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+
+    Ok(written)
+}
+
+/// Export a config with inheritance, prompt refs, config env and referenced global sections inlined
+/// Secrets are redacted, the result can be shared and imported on its own
+#[tauri::command]
+pub async fn export_oh_my_opencode_self_contained(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    key_case: Option<super::key_case::KeyCase>,
+) -> Result<Value, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    let exported = super::self_contained::export_self_contained(&global_config, &config);
+    let mut value = serde_json::to_value(exported).map_err(|e| e.to_string())?;
+    super::key_case::convert_record_keys(&mut value, key_case.unwrap_or_default());
+    Ok(value)
+}
+
+/// Export a config in its canonical form for version control
+/// Secrets are returned separately and referenced as `{env:NAME}` in the content
+#[tauri::command]
+pub async fn export_oh_my_opencode_vcs_form(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<super::vcs::VcsExport, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let record = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    super::vcs::to_vcs_export(&record).map_err(|e| e.to_string())
+}
+
+/// Rebuild a config from its VCS form and secrets for preview, nothing is saved
+#[tauri::command]
+pub async fn import_oh_my_opencode_vcs_form(
+    content: String,
+    secrets: indexmap::IndexMap<String, String>,
+) -> Result<OhMyOpenCodeConfig, String> {
+    let record = super::vcs::from_vcs_form(&content, &secrets).map_err(|e| e.to_string())?;
+    Ok(adapter::from_db_value(record))
+}
+
+/// Estimate the token footprint of a config, warning when it exceeds the threshold
+#[tauri::command]
+pub async fn get_oh_my_opencode_config_footprint(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    threshold: Option<usize>,
+) -> Result<super::footprint::FootprintReport, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    Ok(super::footprint::config_footprint(&global_config, &config, threshold))
+}
+
+/// Preview the final config oh-my-opencode loads, optionally for a project directory
+#[tauri::command]
+pub async fn get_oh_my_opencode_effective_config(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    project_dir: Option<String>,
+) -> Result<Value, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    let cycles = super::cycles::detect_resolution_cycles(&config, &global_config);
+    if !cycles.is_empty() {
+        let messages: Vec<String> = cycles.into_iter().map(|c| c.message).collect();
+        return Err(messages.join("\n"));
+    }
+    Ok(super::effective::effective_config(&global_config, &config, project_dir))
+}
+
+/// Agents of a config that inherit the global `field` and would change with it
+#[tauri::command]
+pub async fn get_oh_my_opencode_agents_affected_by_global(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    field: String,
+) -> Result<Vec<String>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    Ok(super::global_impact::agents_affected_by_global(&global_config, &config, &field))
+}
+
+/// Smallest overlay that turns `base` into `desired` when deep-merged, with the removals it cannot express
+#[tauri::command]
+pub async fn compute_oh_my_opencode_overlay(
+    base: Value,
+    desired: Value,
+) -> Result<super::overlay::OverlayForChange, String> {
+    Ok(super::overlay::overlay_for_change_report(&base, &desired))
+}
+
+/// Paths where `base` and `overlay` set different values, for the conflict-resolution dialog
+#[tauri::command]
+pub async fn find_oh_my_opencode_merge_conflicts(
+    base: Value,
+    overlay: Value,
+) -> Result<Vec<super::overlay::MergeConflict>, String> {
+    Ok(super::overlay::find_merge_conflicts(&base, &overlay))
+}
+
+/// Merge `overlay` into `base` with each conflict resolved as chosen
+/// Unresolved conflicts take the overlay value unless `strict`
+#[tauri::command]
+pub async fn merge_oh_my_opencode_with_resolutions(
+    base: Value,
+    overlay: Value,
+    resolutions: std::collections::HashMap<String, super::overlay::MergeChoice>,
+    strict: Option<bool>,
+) -> Result<Value, String> {
+    super::overlay::merge_with_resolutions(&base, &overlay, &resolutions, strict.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+/// Cycles across `extends` and `prompt_ref` of a config, with their full path
+#[tauri::command]
+pub async fn detect_oh_my_opencode_resolution_cycles(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<Vec<super::cycles::CycleReport>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    Ok(super::cycles::detect_resolution_cycles(&config, &global_config))
+}
+
+/// Build a minimal, redacted config containing a single agent, for bug reports
+#[tauri::command]
+pub async fn minimize_oh_my_opencode_config(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    keep_agent: String,
+) -> Result<OhMyOpenCodeConfigContent, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let content = OhMyOpenCodeConfigContent {
+        name: config.name,
+        is_applied: config.is_applied,
+        agents: config.agents_value(),
+        other_fields: config.other_fields,
+        created_at: config.created_at.unwrap_or_default(),
+        updated_at: config.updated_at.unwrap_or_default(),
+    };
+    super::minimize::minimize_config(&content, &keep_agent)
+}
+
+/// Apply the safe fixes of `repair::auto_repair` to oh-my-opencode.json content, nothing is saved
+/// Unknown disabled names are only pruned when the schema (`$schema` or the plugin's default) loads
+#[tauri::command]
+pub async fn auto_repair_oh_my_opencode_content(
+    state: tauri::State<'_, DbState>,
+    content: Value,
+) -> Result<(Value, Vec<super::repair::RepairAction>), String> {
+    let schema_url = content
+        .get("$schema")
+        .and_then(|v| v.as_str())
+        .unwrap_or(super::render::DEFAULT_SCHEMA_URL)
+        .to_string();
+    let client = crate::http_client::client_with_timeout(&state, 30).await?;
+    let schema = match super::experimental::fetch_schema(&client, &schema_url).await {
+        Ok(schema) => Some(schema),
+        Err(e) => {
+            eprintln!("Failed to load oh-my-opencode schema, unknown disabled names are kept: {}", e);
+            None
+        }
+    };
+    Ok(super::repair::auto_repair(content, schema.as_ref()))
+}
+
+/// Merge snake_case and camelCase disabled lists of oh-my-opencode.json content, nothing is saved
+#[tauri::command]
+pub fn merge_oh_my_opencode_disabled_casings(mut content: Value) -> (Value, Vec<super::disabled::DisabledMerge>) {
+    let merges = super::disabled::merge_disabled_casings(&mut content);
+    (content, merges)
+}
+
+/// Advisory shortlist of models for an agent, e.g. from a fetched provider model list
+#[tauri::command]
+pub fn recommend_oh_my_opencode_models(
+    agent_name: String,
+    agent: Value,
+    available: Vec<crate::coding::open_code::models_api::FetchedModel>,
+    role_hint: Option<String>,
+) -> Vec<super::recommend::ModelRecommendation> {
+    let agent = super::agent::AgentProfile::from_value(&agent).unwrap_or_default();
+    super::recommend::recommend_models(&agent, &agent_name, &available, role_hint)
+}
+
+/// Which agents of a config would work on which provider
+/// `models` holds the fetched model list of each provider id
+#[tauri::command]
+pub async fn get_oh_my_opencode_agent_provider_matrix(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    providers: Vec<super::matrix::MatrixProvider>,
+    models: indexmap::IndexMap<String, Vec<crate::coding::open_code::models_api::FetchedModel>>,
+) -> Result<super::matrix::AgentProviderMatrix, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    Ok(super::matrix::agent_provider_matrix(&config, &providers, &models))
+}
+
+/// Classify the experimental flags of the global config against the oh-my-opencode schema
+/// The schema is the one the global config points to, or the plugin's default
+#[tauri::command]
+pub async fn classify_oh_my_opencode_experimental_flags(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<super::experimental::FlagClassification>, String> {
+    let global = {
+        let db = state.0.lock().await;
+        load_global_config(&db).await?
+    };
+    let Some(experimental) = global.experimental else {
+        return Ok(Vec::new());
+    };
+    let schema_url = global
+        .schema
+        .unwrap_or_else(|| super::render::DEFAULT_SCHEMA_URL.to_string());
+    let client = crate::http_client::client_with_timeout(&state, 30).await?;
+    let schema = super::experimental::fetch_schema(&client, &schema_url).await?;
+    Ok(super::experimental::classify_experimental_flags(&experimental, &schema))
+}
+
+/// Agent dependency graph (`depends_on`) of a config for the graph view, cycles are annotated
+/// Cached per config until its content changes
+#[tauri::command]
+pub async fn get_oh_my_opencode_agent_dependency_graph(
+    state: tauri::State<'_, DbState>,
+    cache: tauri::State<'_, super::dependencies::DependencyGraphCache>,
+    config_id: String,
+) -> Result<super::dependencies::DependencyGraph, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let fingerprint = super::duplicates::config_fingerprint(&config);
+
+    let mut cache = cache.0.lock().map_err(|e| format!("Failed to lock graph cache: {}", e))?;
+    if let Some((cached_fingerprint, graph)) = cache.get(&config_id) {
+        if *cached_fingerprint == fingerprint {
+            return Ok(graph.clone());
+        }
+    }
+
+    let graph = super::dependencies::agent_dependency_graph(&config);
+    cache.insert(config_id, (fingerprint, graph.clone()));
+    Ok(graph)
+}
+
+/// Rename misspelled tool keys in every agent of a config (`fixes`: wrong name -> correct name)
+/// Returns the number of renamed keys
+#[tauri::command]
+pub async fn fix_oh_my_opencode_tool_names(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    config_id: String,
+    fixes: std::collections::HashMap<String, String>,
+) -> Result<usize, String> {
+    let db = state.0.lock().await;
+    let mut config = load_config_by_id(&db, &config_id).await?;
+    let fixed = super::tools::fix_tool_names(&mut config, &fixes);
+    if fixed > 0 {
+        save_config_record(&db, &app, &mut config).await?;
+    }
+    Ok(fixed)
+}
+
+// ============================================================================
+// Oh My OpenCode MCP Commands
+// ============================================================================
+
+/// Rename an MCP server, updating `disabled_mcps` and the tool references of every config's agents
+/// Returns the number of updated entries
+#[tauri::command]
+pub async fn rename_oh_my_opencode_mcp(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    old_name: String,
+    new_name: String,
+) -> Result<usize, String> {
+    let db = state.0.lock().await;
+    let mut global_config = load_global_config(&db).await?;
+    let mut configs = load_all_configs(&db).await?;
+    let before: Vec<(Option<indexmap::IndexMap<String, super::agent::AgentProfile>>, Option<Value>)> = configs
+        .iter()
+        .map(|c| (c.agents.clone(), c.other_fields.clone()))
+        .collect();
+
+    let updated = super::mcp::rename_mcp(&mut global_config, &mut configs, &old_name, &new_name)
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Ok(0);
+    }
+
+    save_global_config_record(&db, &mut global_config).await?;
+
+    let mut applied_saved = false;
+    for (config, (agents, other_fields)) in configs.iter_mut().zip(before) {
+        if config.agents != agents || config.other_fields != other_fields {
+            applied_saved |= config.is_applied;
+            save_config_record(&db, &app, config).await?;
+        }
+    }
+
+    // The global config changed too, re-apply even if the applied config itself did not
+    if !applied_saved {
+        if let Some(applied) = configs.iter().find(|c| c.is_applied) {
+            if apply_config_to_file(&db, &app, &applied.id).await.is_ok() {
+                // Trigger WSL sync via event (Windows only)
+                #[cfg(target_os = "windows")]
+                let _ = app.emit("wsl-sync-request-opencode", ());
+            }
+        }
+        let _ = app.emit("config-changed", "window");
+    }
+
+    Ok(updated)
+}
+
+/// MCP servers of a config that no enabled agent uses
+#[tauri::command]
+pub async fn find_oh_my_opencode_unused_mcps(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<Vec<String>, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let config = load_config_by_id(&db, &config_id).await?;
+    Ok(super::mcp::find_unused_mcps(&global_config, &config))
+}
+
+/// Add MCP servers to the global `disabled_mcps` and re-apply the applied config
+/// Returns the number of newly disabled servers
+#[tauri::command]
+pub async fn disable_oh_my_opencode_mcps(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+    names: Vec<String>,
+) -> Result<usize, String> {
+    let db = state.0.lock().await;
+    let mut global_config = load_global_config(&db).await?;
+    let added = super::mcp::disable_mcps(&mut global_config, &names);
+    if added == 0 {
+        return Ok(0);
+    }
+    save_global_config_record(&db, &mut global_config).await?;
+
+    if let Some(applied) = load_all_configs(&db).await?.iter().find(|c| c.is_applied) {
+        if apply_config_to_file(&db, &app, &applied.id).await.is_ok() {
+            // Trigger WSL sync via event (Windows only)
+            #[cfg(target_os = "windows")]
+            let _ = app.emit("wsl-sync-request-opencode", ());
+        }
+    }
+    let _ = app.emit("config-changed", "window");
+    Ok(added)
+}
+
+/// Simulate failover: the provider that would serve a model given the current health
+/// `health` maps provider id -> up, e.g. from the `provider-health` event
+#[tauri::command]
+pub async fn select_oh_my_opencode_provider(
+    state: tauri::State<'_, DbState>,
+    model_id: String,
+    health: std::collections::HashMap<String, bool>,
+) -> Result<Option<String>, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    Ok(super::failover::select_provider(&model_id, &global_config, &health))
+}
+
+/// OpenCode providers no oh-my-opencode config, global default or OpenCode default model uses
+/// A cleanup suggestion only, nothing is removed
+#[tauri::command]
+pub async fn find_oh_my_opencode_unused_providers(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    use crate::coding::open_code::ReadConfigResult;
+
+    let opencode_config = match crate::coding::open_code::read_opencode_config(state.clone(), app).await? {
+        ReadConfigResult::Success { config } => config,
+        ReadConfigResult::NotFound { .. } => return Ok(Vec::new()),
+        ReadConfigResult::ParseError { error, .. } | ReadConfigResult::Error { error } => return Err(error),
+    };
+    let providers: Vec<String> = opencode_config
+        .provider
+        .as_ref()
+        .map(|providers| providers.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let configs = load_all_configs(&db).await?;
+
+    let defaults: Vec<String> = [opencode_config.model.as_deref(), opencode_config.small_model.as_deref()]
+        .into_iter()
+        .flatten()
+        .filter_map(super::failover::model_provider)
+        .collect();
+    Ok(super::providers::find_unused_providers(&configs, &global_config, &providers)
+        .into_iter()
+        .filter(|provider| {
+            !defaults.contains(&crate::coding::open_code::provider_id::canonical_provider_id(provider))
+        })
+        .collect())
+}
+
+// ============================================================================
+// Oh My OpenCode Validation Commands
+// ============================================================================
+
+/// Run all validation rules on a config, errors first
+#[tauri::command]
+pub async fn validate_oh_my_opencode_config(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<Vec<super::validation::ValidationIssue>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    let issues = super::validation::validate_config(&global_config, &config);
+
+    let summary = super::validation::ValidationSummary::from_issues(
+        &issues,
+        super::validation::validation_fingerprint(&global_config, &config),
+        Local::now().to_rfc3339(),
+    );
+    if let Err(e) = store_validation_summary(&db, &config.id, &summary).await {
+        eprintln!("Failed to store validation summary: {}", e);
+    }
+    Ok(issues)
+}
+
+/// Validate every config and return the issues as a SARIF 2.1.0 log, for CI code scanning
+#[tauri::command]
+pub async fn export_oh_my_opencode_validation_sarif(state: tauri::State<'_, DbState>) -> Result<Value, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let results = load_all_configs(&db)
+        .await?
+        .into_iter()
+        .map(|config| {
+            let issues = super::validation::validate_config(&global_config, &config);
+            (config.id, issues)
+        })
+        .collect();
+    Ok(super::sarif::validation_to_sarif(&results))
+}
+
+/// Save the last validation result on a config record, next to its content
+async fn store_validation_summary(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    config_id: &str,
+    summary: &super::validation::ValidationSummary,
+) -> Result<(), String> {
+    let mut patch = serde_json::Map::new();
+    patch.insert(
+        super::validation::LAST_VALIDATION_KEY.to_string(),
+        serde_json::to_value(summary).map_err(|e| e.to_string())?,
+    );
+    let json_str = serde_json::to_string(&patch).map_err(|e| e.to_string())?;
+    db.query(format!("UPDATE oh_my_opencode_config:`{}` MERGE {}", config_id, json_str))
+        .await
+        .map_err(|e| format!("Failed to update config: {}", e))?;
+    Ok(())
+}
+
+/// List all configs with their validation status, ordered by name
+/// The stored result is reused while the config and global content are unchanged
+#[tauri::command]
+pub async fn list_oh_my_opencode_configs_with_status(
+    state: tauri::State<'_, DbState>,
+) -> Result<Vec<super::validation::ConfigWithStatus>, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let records: Vec<Value> = db
+        .query("SELECT *, type::string(id) as id FROM oh_my_opencode_config")
+        .await
+        .map_err(|e| format!("Failed to query configs: {}", e))?
+        .take(0)
+        .map_err(|e| format!("Failed to deserialize configs: {}", e))?;
+
+    let now = Local::now().to_rfc3339();
+    let mut result = Vec::with_capacity(records.len());
+    for record in records {
+        let cached = record
+            .get(super::validation::LAST_VALIDATION_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        let config = adapter::from_db_value(record);
+        let (summary, ran) = super::validation::validation_status(&global_config, &config, cached, &now);
+        if ran {
+            if let Err(e) = store_validation_summary(&db, &config.id, &summary).await {
+                eprintln!("Failed to store validation summary: {}", e);
+            }
+        }
+        result.push(super::validation::ConfigWithStatus {
+            config,
+            last_validation: summary,
+        });
+    }
+    result.sort_by_key(|c| c.config.name.clone());
+    Ok(result)
+}
+
+/// Checksums of the global config and every config, to verify them later
+#[tauri::command]
+pub async fn get_oh_my_opencode_integrity_manifest(
+    state: tauri::State<'_, DbState>,
+) -> Result<super::integrity::IntegrityManifest, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let configs = load_all_configs(&db).await?;
+    let now = Local::now().to_rfc3339();
+    Ok(super::integrity::integrity_manifest(&global_config, &configs, &now))
+}
+
+/// Configs modified, added or removed since the manifest was taken
+#[tauri::command]
+pub async fn verify_oh_my_opencode_integrity(
+    state: tauri::State<'_, DbState>,
+    manifest: super::integrity::IntegrityManifest,
+) -> Result<Vec<super::integrity::IntegrityViolation>, String> {
+    let db = state.0.lock().await;
+    let global_config = load_global_config(&db).await?;
+    let configs = load_all_configs(&db).await?;
+    let now = Local::now().to_rfc3339();
+    let current = super::integrity::integrity_manifest(&global_config, &configs, &now);
+    Ok(super::integrity::verify_integrity(&manifest, &current))
+}
+
+/// Check that the MCP commands and LSP binaries of a config are installed
+/// Binaries are resolved against the shell PATH (process env + shell config exports)
+#[tauri::command]
+pub async fn preflight_oh_my_opencode_config(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<super::preflight::PreflightReport, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    let env = crate::coding::open_code::shell_env::get_shell_env();
+    Ok(super::preflight::preflight_check(&global_config, &config, &env))
+}
+
+/// Start (or connect to) one MCP server of a config and list its tools
+#[tauri::command]
+pub async fn test_oh_my_opencode_mcp_tools(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    mcp_name: String,
+) -> Result<Vec<String>, String> {
+    let (global_config, config) = {
+        let db = state.0.lock().await;
+        (load_global_config(&db).await?, load_config_by_id(&db, &config_id).await?)
+    };
+    let server = super::mcp_probe::enabled_mcp_servers(&global_config, &config)
+        .into_iter()
+        .find(|(name, _)| *name == mcp_name)
+        .map(|(_, server)| server)
+        .ok_or_else(|| format!("MCP server '{}' not found or disabled", mcp_name))?;
+    let base_env = crate::coding::open_code::shell_env::get_shell_env();
+    let env = super::launch_env::resolved_launch_env(&global_config, &config, &base_env, Some(&mcp_name));
+    let client = crate::http_client::client(&state).await?;
+    super::mcp_probe::test_mcp_tools(&client, &server, &env).await
+}
+
+/// Tool names exposed by more than one enabled MCP server of a config
+/// Servers that cannot be probed are skipped
+#[tauri::command]
+pub async fn find_oh_my_opencode_mcp_tool_collisions(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+) -> Result<Vec<super::mcp_probe::ToolCollision>, String> {
+    let (global_config, config) = {
+        let db = state.0.lock().await;
+        (load_global_config(&db).await?, load_config_by_id(&db, &config_id).await?)
+    };
+    let base_env = crate::coding::open_code::shell_env::get_shell_env();
+    let client = crate::http_client::client(&state).await?;
+    Ok(super::mcp_probe::find_mcp_tool_collisions(&client, &global_config, &config, &base_env).await)
+}
+
+/// The environment opencode (or one of its MCP servers) gets for a config, secrets masked
+#[tauri::command]
+pub async fn get_oh_my_opencode_launch_env(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    mcp_server: Option<String>,
+) -> Result<std::collections::HashMap<String, String>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    let base_env = crate::coding::open_code::shell_env::get_shell_env();
+    let env = super::launch_env::resolved_launch_env(&global_config, &config, &base_env, mcp_server.as_deref());
+    Ok(super::launch_env::masked_env(&env))
+}
+
+// ============================================================================
+// Oh My OpenCode Policy Commands
+// ============================================================================
+
+/// Lint a config against org policy rules
+/// Intended as a pre-apply gate: the frontend applies only when no violations are returned
+#[tauri::command]
+pub async fn lint_oh_my_opencode_policy(
+    state: tauri::State<'_, DbState>,
+    config_id: String,
+    rules: super::policy::PolicyRuleSet,
+) -> Result<Vec<super::policy::PolicyViolation>, String> {
+    let db = state.0.lock().await;
+    let config = load_config_by_id(&db, &config_id).await?;
+    let global_config = load_global_config(&db).await?;
+    Ok(super::policy::lint_against_policy(&global_config, &config, &rules))
+}
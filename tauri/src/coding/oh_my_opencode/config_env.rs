@@ -0,0 +1,71 @@
+//! Oh My OpenCode Config Env Module
+//!
+//! Per-profile env variables (`other_fields.config_env`, name -> value) used to resolve
+//! `{env:NAME}` placeholders before the process environment.
+//! The section exists only in AI Toolbox and is never written to oh-my-opencode.json;
+//! it has its own key so a user's `env` section passes through untouched.
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use super::types::OhMyOpenCodeConfig;
+
+/// Key of the config-level env in the profile `other_fields`
+pub const CONFIG_ENV_KEY: &str = "config_env";
+
+const ENV_PLACEHOLDER_PREFIX: &str = "{env:";
+
+/// The config-level env of a profile, non-string values are ignored
+pub fn config_env(profile: &OhMyOpenCodeConfig) -> IndexMap<String, String> {
+    profile
+        .other_fields
+        .as_ref()
+        .and_then(|fields| fields.get(CONFIG_ENV_KEY))
+        .and_then(|env| env.as_object())
+        .map(|env| {
+            env.iter()
+                .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace `{env:NAME}` placeholders whose name is in `env`, recursively
+/// Unknown names are left as-is so the process environment can still resolve them
+pub fn fold_env(value: &mut Value, env: &IndexMap<String, String>) {
+    match value {
+        Value::String(s) if s.contains(ENV_PLACEHOLDER_PREFIX) => {
+            let mut resolved = String::with_capacity(s.len());
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find(ENV_PLACEHOLDER_PREFIX) {
+                let Some(len) = rest[start..].find('}') else {
+                    break;
+                };
+                let placeholder = &rest[start..start + len + 1];
+                let name = &placeholder[ENV_PLACEHOLDER_PREFIX.len()..placeholder.len() - 1];
+                resolved.push_str(&rest[..start]);
+                resolved.push_str(env.get(name).map(String::as_str).unwrap_or(placeholder));
+                rest = &rest[start + len + 1..];
+            }
+            resolved.push_str(rest);
+            *s = resolved;
+        }
+        Value::Object(map) => map.values_mut().for_each(|item| fold_env(item, env)),
+        Value::Array(items) => items.iter_mut().for_each(|item| fold_env(item, env)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_fold_env_keeps_unknown_placeholders() {
+        let env = IndexMap::from([("MODEL".to_string(), "openai/o3".to_string())]);
+        let mut value = json!({ "model": "{env:MODEL}", "args": ["--key={env:API_KEY}"] });
+        fold_env(&mut value, &env);
+        assert_eq!(value, json!({ "model": "openai/o3", "args": ["--key={env:API_KEY}"] }));
+    }
+}
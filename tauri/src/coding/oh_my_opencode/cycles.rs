@@ -0,0 +1,156 @@
+//! Oh My OpenCode Resolution Cycles Module
+//!
+//! One graph over everything resolved before rendering: `extends` between agents and
+//! `prompt_ref` from agents to library entries. Library entries are plain text, so they
+//! end a path. A cycle anywhere in the graph is reported with its full path.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+use super::agent;
+use super::prompt_library;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// A cycle of the resolution graph
+/// Path nodes are `agent:NAME` or `prompt:NAME`, the first node closes the cycle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CycleReport {
+    pub path: Vec<String>,
+    pub message: String,
+}
+
+fn agent_node(name: &str) -> String {
+    format!("agent:{}", name)
+}
+
+fn prompt_node(name: &str) -> String {
+    format!("prompt:{}", name)
+}
+
+struct ResolutionGraph<'a> {
+    agents: &'a indexmap::IndexMap<String, agent::AgentProfile>,
+    library: Option<&'a Map<String, Value>>,
+}
+
+impl ResolutionGraph<'_> {
+    /// Outgoing edges of a node, library entries (`prompt:` nodes) have none
+    fn edges(&self, node: &str) -> Vec<String> {
+        let Some(agent) = node.strip_prefix("agent:").and_then(|name| self.agents.get(name)) else {
+            return Vec::new();
+        };
+        agent
+            .extends
+            .iter()
+            .map(|parent| agent_node(parent))
+            .chain(
+                agent
+                    .prompt_ref
+                    .iter()
+                    .filter(|entry| self.library.is_some_and(|library| library.contains_key(entry.as_str())))
+                    .map(|entry| prompt_node(entry)),
+            )
+            .collect()
+    }
+
+    fn visit(&self, node: String, stack: &mut Vec<String>, done: &mut HashSet<String>, cycles: &mut Vec<Vec<String>>) {
+        if done.contains(&node) {
+            return;
+        }
+        if let Some(position) = stack.iter().position(|n| *n == node) {
+            let mut cycle = stack[position..].to_vec();
+            // Same cycle found from another start: compare rotated to the smallest node
+            let start = cycle.iter().enumerate().min_by(|a, b| a.1.cmp(b.1)).map(|(i, _)| i).unwrap_or(0);
+            cycle.rotate_left(start);
+            if !cycles.contains(&cycle) {
+                cycles.push(cycle);
+            }
+            return;
+        }
+
+        stack.push(node.clone());
+        for next in self.edges(&node) {
+            self.visit(next, stack, done, cycles);
+        }
+        stack.pop();
+        done.insert(node);
+    }
+}
+
+/// Every cycle across agent inheritance and prompt refs
+pub fn detect_resolution_cycles(
+    profile: &OhMyOpenCodeConfig,
+    global: &OhMyOpenCodeGlobalConfig,
+) -> Vec<CycleReport> {
+    let agents = profile.typed_agents();
+    let library = prompt_library::prompt_library(global);
+    let graph = ResolutionGraph {
+        agents: &agents,
+        library,
+    };
+
+    let starts = agents.keys().map(|name| agent_node(name));
+    let mut done = HashSet::new();
+    let mut cycles = Vec::new();
+    for start in starts {
+        graph.visit(start, &mut Vec::new(), &mut done, &mut cycles);
+    }
+
+    cycles
+        .into_iter()
+        .map(|mut path| {
+            path.push(path[0].clone());
+            CycleReport {
+                message: format!("Circular resolution: {}", path.join(" -> ")),
+                path,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_extends_cycle() {
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "a": { "extends": "b" },
+            "b": { "extends": "c", "prompt_ref": "base" },
+            "c": { "extends": "a" },
+            "d": { "extends": "a" }
+        }));
+        let reports = detect_resolution_cycles(&profile, &OhMyOpenCodeGlobalConfig::default());
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].path, vec!["agent:a", "agent:b", "agent:c", "agent:a"]);
+        assert_eq!(
+            reports[0].message,
+            "Circular resolution: agent:a -> agent:b -> agent:c -> agent:a"
+        );
+    }
+
+    #[test]
+    fn test_self_referential_prompt() {
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({
+                "prompt_library": { "self": "Mentions {prompt:self} literally" }
+            })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "self": { "extends": "self", "prompt_ref": "self" },
+            "oracle": { "extends": "explore", "prompt_ref": "self" },
+            "explore": { "prompt_ref": "self" }
+        }));
+        let paths: Vec<Vec<String>> = detect_resolution_cycles(&profile, &global)
+            .into_iter()
+            .map(|r| r.path)
+            .collect();
+
+        // Only the agent extending itself, a shared prompt entry closes no cycle
+        assert_eq!(paths, vec![vec!["agent:self".to_string(), "agent:self".to_string()]]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Oh My OpenCode Delete Module
+//!
+//! Deleting the applied config would leave oh-my-opencode.json rendered from a config
+//! that no longer exists. An applied config is only deleted when another config is
+//! applied in its place, or when the caller forces it.
+
+use std::future::Future;
+
+use thiserror::Error;
+
+use super::types::OhMyOpenCodeConfig;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DeleteError {
+    #[error("Config '{0}' not found")]
+    NotFound(String),
+    #[error("Config '{0}' is applied, choose a config to apply instead or force the deletion")]
+    Applied(String),
+    #[error("Cannot reassign to '{0}': {1}")]
+    InvalidReassignment(String, String),
+    #[error("{0}")]
+    Database(String),
+}
+
+/// What deleting a config takes
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeletePlan {
+    Delete,
+    /// Apply this config first, then delete
+    ApplyThenDelete(String),
+}
+
+/// Decide how to delete `id`, nothing is changed
+/// A reassignment is only used for an applied config; `force` deletes an applied config as-is
+pub fn plan_delete(
+    configs: &[OhMyOpenCodeConfig],
+    id: &str,
+    reassign_to: Option<&str>,
+    force: bool,
+) -> Result<DeletePlan, DeleteError> {
+    let config = configs
+        .iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| DeleteError::NotFound(id.to_string()))?;
+    if !config.is_applied {
+        return Ok(DeletePlan::Delete);
+    }
+
+    match reassign_to {
+        Some(target) if target == id => Err(DeleteError::InvalidReassignment(
+            target.to_string(),
+            "it is the config being deleted".to_string(),
+        )),
+        Some(target) if !configs.iter().any(|c| c.id == target) => Err(DeleteError::InvalidReassignment(
+            target.to_string(),
+            "config not found".to_string(),
+        )),
+        Some(target) => Ok(DeletePlan::ApplyThenDelete(target.to_string())),
+        None if force => Ok(DeletePlan::Delete),
+        None => Err(DeleteError::Applied(id.to_string())),
+    }
+}
+
+/// Delete `id` as `plan_delete` decides, calling `apply` on the reassignment target first
+/// Nothing is deleted when the apply fails
+pub async fn delete_config_with<F, Fut>(
+    db: &surrealdb::Surreal<surrealdb::engine::local::Db>,
+    id: &str,
+    reassign_to: Option<&str>,
+    force: bool,
+    apply: F,
+) -> Result<(), DeleteError>
+where
+    F: FnOnce(String) -> Fut,
+    Fut: Future<Output = Result<(), String>>,
+{
+    let configs = super::commands::load_all_configs(db).await.map_err(DeleteError::Database)?;
+    if let DeletePlan::ApplyThenDelete(target) = plan_delete(&configs, id, reassign_to, force)? {
+        apply(target).await.map_err(DeleteError::Database)?;
+    }
+
+    db.query(format!("DELETE oh_my_opencode_config:`{}`", id))
+        .await
+        .map_err(|e| DeleteError::Database(format!("Failed to delete config: {}", e)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(id: &str, is_applied: bool) -> OhMyOpenCodeConfig {
+        OhMyOpenCodeConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            is_applied,
+            agents: None,
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_delete_applied_config_without_reassignment() {
+        let configs = vec![config("work", true), config("home", false)];
+        assert_eq!(
+            plan_delete(&configs, "work", None, false),
+            Err(DeleteError::Applied("work".to_string()))
+        );
+        assert_eq!(plan_delete(&configs, "work", None, true), Ok(DeletePlan::Delete));
+        assert_eq!(plan_delete(&configs, "home", None, false), Ok(DeletePlan::Delete));
+    }
+
+    #[test]
+    fn test_delete_applied_config_with_reassignment() {
+        let configs = vec![config("work", true), config("home", false)];
+        assert_eq!(
+            plan_delete(&configs, "work", Some("home"), false),
+            Ok(DeletePlan::ApplyThenDelete("home".to_string()))
+        );
+        assert!(matches!(
+            plan_delete(&configs, "work", Some("work"), false),
+            Err(DeleteError::InvalidReassignment(..))
+        ));
+        assert!(matches!(
+            plan_delete(&configs, "work", Some("missing"), false),
+            Err(DeleteError::InvalidReassignment(..))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_applied_config_applies_reassignment_first() {
+        use super::super::adapter;
+        use super::super::commands::{load_all_configs, mark_applied};
+        use surrealdb::engine::local::SurrealKv;
+
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-delete-{}", uuid::Uuid::new_v4()));
+        let db = surrealdb::Surreal::new::<SurrealKv>(dir.join("database")).await.unwrap();
+        db.use_ns("test").use_db("test").await.unwrap();
+        for config in [config("work", true), config("home", false)] {
+            db.query(format!("CREATE oh_my_opencode_config:`{}` CONTENT $data", config.id))
+                .bind(("data", adapter::to_db_value(&super::super::types::OhMyOpenCodeConfigContent {
+                    name: config.name,
+                    is_applied: config.is_applied,
+                    agents: None,
+                    other_fields: None,
+                    created_at: String::new(),
+                    updated_at: String::new(),
+                })))
+                .await
+                .unwrap();
+        }
+        let apply = |target: String| {
+            let db = &db;
+            async move { mark_applied(db, &target).await }
+        };
+
+        // Refused without a reassignment, and nothing changes
+        assert_eq!(
+            delete_config_with(&db, "work", None, false, apply).await,
+            Err(DeleteError::Applied("work".to_string()))
+        );
+        assert_eq!(load_all_configs(&db).await.unwrap().len(), 2);
+
+        delete_config_with(&db, "work", Some("home"), false, apply).await.unwrap();
+        let remaining = load_all_configs(&db).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, "home");
+        assert!(remaining[0].is_applied);
+
+        drop(db);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
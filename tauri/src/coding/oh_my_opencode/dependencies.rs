@@ -0,0 +1,219 @@
+//! Oh My OpenCode Agent Dependencies Module
+//!
+//! `depends_on` edges between agents of a profile: validation and a graph for the UI.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use super::agent::AgentProfile;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const DEPENDENCY_RULE: &str = "agent-dependency";
+
+fn dependencies(agent: &AgentProfile) -> impl Iterator<Item = &str> {
+    agent.depends_on.iter().flatten().map(String::as_str)
+}
+
+/// Rotate a cycle so it starts at its smallest agent, making equal cycles compare equal
+fn canonical_cycle(mut cycle: Vec<String>) -> Vec<String> {
+    if let Some(start) = cycle
+        .iter()
+        .enumerate()
+        .min_by(|a, b| a.1.cmp(b.1))
+        .map(|(index, _)| index)
+    {
+        cycle.rotate_left(start);
+    }
+    cycle
+}
+
+fn visit<'a>(
+    name: &'a str,
+    agents: &'a IndexMap<String, AgentProfile>,
+    stack: &mut Vec<&'a str>,
+    done: &mut HashSet<&'a str>,
+    cycles: &mut Vec<Vec<String>>,
+) {
+    if done.contains(name) {
+        return;
+    }
+    if let Some(position) = stack.iter().position(|n| *n == name) {
+        let cycle = canonical_cycle(stack[position..].iter().map(|n| n.to_string()).collect());
+        if !cycles.contains(&cycle) {
+            cycles.push(cycle);
+        }
+        return;
+    }
+    let Some(agent) = agents.get(name) else {
+        return;
+    };
+
+    stack.push(name);
+    for dependency in dependencies(agent) {
+        visit(dependency, agents, stack, done, cycles);
+    }
+    stack.pop();
+    done.insert(name);
+}
+
+/// Every distinct `depends_on` cycle, each listed once starting at its smallest agent
+pub fn find_dependency_cycles(agents: &IndexMap<String, AgentProfile>) -> Vec<Vec<String>> {
+    let mut cycles = Vec::new();
+    let mut done = HashSet::new();
+    for name in agents.keys() {
+        visit(name, agents, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+}
+
+/// Validation rule: dependencies must exist and must not form a cycle
+pub fn check_agent_dependencies(
+    _global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    let agents = profile.typed_agents();
+    let mut issues = Vec::new();
+
+    for (name, agent) in &agents {
+        for dependency in dependencies(agent).filter(|d| !agents.contains_key(*d)) {
+            issues.push(ValidationIssue::new(
+                DEPENDENCY_RULE,
+                Severity::Error,
+                format!("agents.{}.depends_on", name),
+                format!("Agent '{}' depends on unknown agent '{}'", name, dependency),
+            ));
+        }
+    }
+
+    for cycle in find_dependency_cycles(&agents) {
+        issues.push(ValidationIssue::new(
+            DEPENDENCY_RULE,
+            Severity::Error,
+            format!("agents.{}.depends_on", cycle[0]),
+            format!("Circular dependency: {} -> {}", cycle.join(" -> "), cycle[0]),
+        ));
+    }
+
+    issues
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyNode {
+    pub name: String,
+    /// Referenced by `depends_on` but not defined in the profile
+    pub missing: bool,
+    pub in_cycle: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+    pub in_cycle: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyNode>,
+    pub edges: Vec<DependencyEdge>,
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Whether `from -> to` is an edge of one of the cycles
+fn edge_in_cycle(cycles: &[Vec<String>], from: &str, to: &str) -> bool {
+    cycles.iter().any(|cycle| {
+        (0..cycle.len()).any(|i| cycle[i] == from && cycle[(i + 1) % cycle.len()] == to)
+    })
+}
+
+/// Dependency graph of a profile's agents, in agent order
+pub fn agent_dependency_graph(config: &OhMyOpenCodeConfig) -> DependencyGraph {
+    let agents = config.typed_agents();
+    let cycles = find_dependency_cycles(&agents);
+    let in_cycle = |name: &str| cycles.iter().any(|cycle| cycle.iter().any(|n| n == name));
+
+    let mut graph = DependencyGraph::default();
+    for (name, agent) in &agents {
+        graph.nodes.push(DependencyNode {
+            name: name.clone(),
+            missing: false,
+            in_cycle: in_cycle(name),
+        });
+        for dependency in dependencies(agent) {
+            graph.edges.push(DependencyEdge {
+                from: name.clone(),
+                to: dependency.to_string(),
+                in_cycle: edge_in_cycle(&cycles, name, dependency),
+            });
+        }
+    }
+
+    for edge in &graph.edges {
+        if !agents.contains_key(&edge.to) && !graph.nodes.iter().any(|n| n.name == edge.to) {
+            graph.nodes.push(DependencyNode {
+                name: edge.to.clone(),
+                missing: true,
+                in_cycle: false,
+            });
+        }
+    }
+
+    graph.cycles = cycles;
+    graph
+}
+
+/// Graphs by config id with the content fingerprint they were computed from, managed as Tauri state
+#[derive(Default)]
+pub struct DependencyGraphCache(pub Mutex<HashMap<String, (String, DependencyGraph)>>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dependency_graph_dag() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "sisyphus": { "depends_on": ["oracle", "explore"] },
+            "oracle": { "depends_on": ["explore"] },
+            "explore": {}
+        }));
+
+        let graph = agent_dependency_graph(&config);
+        let nodes: Vec<&str> = graph.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(nodes, vec!["sisyphus", "oracle", "explore"]);
+        assert_eq!(graph.edges.len(), 3);
+        assert!(graph.cycles.is_empty());
+        assert!(graph.edges.iter().all(|e| !e.in_cycle));
+        assert!(check_agent_dependencies(&OhMyOpenCodeGlobalConfig::default(), &config).is_empty());
+    }
+
+    #[test]
+    fn test_dependency_graph_cycle() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "sisyphus": { "depends_on": ["oracle"] },
+            "oracle": { "depends_on": ["librarian"] },
+            "librarian": { "depends_on": ["oracle", "ghost"] }
+        }));
+
+        let graph = agent_dependency_graph(&config);
+        assert_eq!(graph.cycles, vec![vec!["librarian".to_string(), "oracle".to_string()]]);
+        assert!(!graph.nodes[0].in_cycle);
+        assert!(graph.nodes[1].in_cycle);
+        assert_eq!(graph.nodes[3].name, "ghost");
+        assert!(graph.nodes[3].missing);
+        assert!(!graph.edges[0].in_cycle);
+        assert!(graph.edges[1].in_cycle);
+
+        let issues = check_agent_dependencies(&OhMyOpenCodeGlobalConfig::default(), &config);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("'ghost'"));
+        assert_eq!(issues[1].message, "Circular dependency: librarian -> oracle -> librarian");
+    }
+}
@@ -0,0 +1,366 @@
+//! Oh My OpenCode Disabled Lists Module
+//!
+//! Bulk operations on the `disabled_agents`, `disabled_mcps` and `disabled_hooks`
+//! lists of the global config, the check that they leave something enabled, and the
+//! merge of lists written in both snake_case and camelCase.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::agent;
+use super::key_case::KeyCase;
+use super::mcp;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const NOTHING_ENABLED_RULE: &str = "nothing-enabled";
+
+/// The disabled lists, with the section holding the names they refer to
+const DISABLED_LISTS: &[(&str, Option<&str>)] = &[
+    ("disabled_agents", Some("agents")),
+    ("disabled_mcps", Some("mcp")),
+    ("disabled_hooks", None),
+];
+
+/// Number of entries removed from each disabled list
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearReport {
+    pub agents: usize,
+    pub mcps: usize,
+    pub hooks: usize,
+}
+
+impl ClearReport {
+    pub fn total(&self) -> usize {
+        self.agents + self.mcps + self.hooks
+    }
+}
+
+/// Names of the locked agents of any config
+pub fn locked_agents(configs: &[OhMyOpenCodeConfig]) -> Vec<String> {
+    let mut locked = Vec::new();
+    for config in configs {
+        for (name, agent) in config.typed_agents() {
+            if agent.locked && !locked.contains(&name) {
+                locked.push(name);
+            }
+        }
+    }
+    locked
+}
+
+/// Remove the entries not kept by `keep`, an emptied list is unset
+fn clear_list(list: &mut Option<Vec<String>>, keep: impl Fn(&String) -> bool) -> usize {
+    let Some(items) = list.as_mut() else {
+        return 0;
+    };
+    let before = items.len();
+    items.retain(|item| keep(item));
+    let cleared = before - items.len();
+    if items.is_empty() {
+        *list = None;
+    }
+    cleared
+}
+
+/// Re-enable everything: empty the three disabled lists
+/// Locked agents stay disabled, bulk edits never touch them
+pub fn clear_all_disabled(global: &mut OhMyOpenCodeGlobalConfig, locked_agents: &[String]) -> ClearReport {
+    ClearReport {
+        agents: clear_list(&mut global.disabled_agents, |name| locked_agents.contains(name)),
+        mcps: clear_list(&mut global.disabled_mcps, |_| false),
+        hooks: clear_list(&mut global.disabled_hooks, |_| false),
+    }
+}
+
+/// Entries of a disabled list merged into one, reported by `merge_disabled_casings`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisabledMerge {
+    /// snake_case key of the list
+    pub list: String,
+    /// Entries as written, from both keys
+    pub from: Vec<String>,
+    /// The entry kept
+    pub into: String,
+}
+
+/// Union a disabled list with its camelCase twin and dedup it case-insensitively
+/// Entries take the spelling of the key they refer to in `names`, otherwise their first spelling.
+/// Non-string entries are kept at the end as written.
+fn merge_list(list: &str, entries: Vec<Value>, names: &[&String], merges: &mut Vec<DisabledMerge>) -> Vec<Value> {
+    let mut groups: IndexMap<String, Vec<String>> = IndexMap::new();
+    let mut others = Vec::new();
+    for entry in entries {
+        match entry {
+            Value::String(name) => groups.entry(agent::normalize_agent_name(&name)).or_default().push(name),
+            other => others.push(other),
+        }
+    }
+
+    let mut merged: Vec<Value> = Vec::with_capacity(groups.len() + others.len());
+    for (normalized, from) in groups {
+        let into = names
+            .iter()
+            .find(|name| agent::normalize_agent_name(name) == normalized)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| from[0].clone());
+        if from.len() > 1 || from[0] != into {
+            merges.push(DisabledMerge {
+                list: list.to_string(),
+                from,
+                into: into.clone(),
+            });
+        }
+        merged.push(Value::String(into));
+    }
+    merged.extend(others);
+    merged
+}
+
+/// Merge the snake_case and camelCase variants of the disabled lists in oh-my-opencode.json
+/// content (e.g. `disabled_agents` and a leftover `disabledAgents`) into the snake_case key
+/// opencode reads, using the twin keys of `key_case`. Entries are deduped case-insensitively
+/// and resolved to the agent or MCP server keys of the content. Returns what was merged.
+pub fn merge_disabled_casings(content: &mut Value) -> Vec<DisabledMerge> {
+    let mut merges = Vec::new();
+    let Some(map) = content.as_object_mut() else {
+        return merges;
+    };
+
+    for (list, section) in DISABLED_LISTS {
+        let Some(camel) = KeyCase::CamelCase.convert(list) else {
+            continue;
+        };
+        let snake_entries = map.get(*list).and_then(|v| v.as_array()).cloned();
+        let camel_entries = map.get(camel).and_then(|v| v.as_array()).cloned();
+        if snake_entries.is_none() && camel_entries.is_none() {
+            continue;
+        }
+
+        let empty = Map::new();
+        let names: Vec<&String> = section
+            .and_then(|section| map.get(section))
+            .and_then(|v| v.as_object())
+            .unwrap_or(&empty)
+            .keys()
+            .collect();
+        let entries = snake_entries.into_iter().chain(camel_entries).flatten().collect();
+        let merged = merge_list(list, entries, &names, &mut merges);
+
+        map.shift_remove(camel);
+        map.insert(list.to_string(), Value::Array(merged));
+    }
+
+    merges
+}
+
+fn is_disabled(entry: &Value, flag: &str) -> bool {
+    entry.get(flag).and_then(|v| v.as_bool()) == Some(true)
+}
+
+/// Validation rule: the disabled lists must leave at least one configured agent active
+/// (error), and should not turn off every MCP or LSP server the config sets up (warning)
+/// Agents with a `when` condition only count as active inside matching projects. Agents are
+/// judged on the profile's typed agents, since rendering strips `when` along with the other
+/// UI-only fields; the disabled lists and tools come from the rendered file.
+pub fn validate_something_enabled(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    let config = render_opencode_json(global, profile);
+    let agents = profile.typed_agents();
+    let mut issues = Vec::new();
+
+    let disabled_agents = mcp::string_list(&config, "disabled_agents");
+    let active: Vec<(&String, &agent::AgentProfile)> = agents
+        .iter()
+        .filter(|(name, agent)| {
+            !disabled_agents.contains(&name.as_str()) && agent.extra.get("disable").and_then(|v| v.as_bool()) != Some(true)
+        })
+        .collect();
+    if !agents.is_empty() {
+        if active.is_empty() {
+            issues.push(ValidationIssue::new(
+                NOTHING_ENABLED_RULE,
+                Severity::Error,
+                "disabled_agents",
+                "Every agent is disabled, at least one agent must be active for opencode to do anything",
+            ));
+        } else if active.iter().all(|(_, agent)| agent.extra.contains_key("when")) {
+            issues.push(ValidationIssue::new(
+                NOTHING_ENABLED_RULE,
+                Severity::Warning,
+                "agents",
+                "Only conditional agents are active, no agent runs in projects that match none of their conditions",
+            ));
+        }
+    }
+
+    if let Some(servers) = config.get("mcp").and_then(|v| v.as_object()).filter(|s| !s.is_empty()) {
+        let disabled_mcps = mcp::string_list(&config, "disabled_mcps");
+        let all_disabled = servers
+            .iter()
+            .all(|(name, server)| disabled_mcps.contains(&name.as_str()) || server.get("enabled").and_then(|e| e.as_bool()) == Some(false));
+        let names: Vec<&String> = servers.keys().collect();
+        let relied_on = servers.keys().any(|name| {
+            mcp::tools_enable_mcp(config.get("tools"), name, &names)
+                || active.iter().any(|(agent_name, _)| {
+                    let rendered = config.get("agents").and_then(|agents| agents.get(agent_name.as_str()));
+                    mcp::tools_enable_mcp(rendered.and_then(|agent| agent.get("tools")), name, &names)
+                })
+        });
+        if all_disabled && relied_on {
+            issues.push(ValidationIssue::new(
+                NOTHING_ENABLED_RULE,
+                Severity::Warning,
+                "disabled_mcps",
+                "Every MCP server is disabled but agents enable MCP tools, those tools will be missing",
+            ));
+        }
+    }
+
+    if let Some(servers) = config.get("lsp").and_then(|v| v.as_object()).filter(|s| !s.is_empty()) {
+        if servers.values().all(|server| is_disabled(server, "disabled")) {
+            issues.push(ValidationIssue::new(
+                NOTHING_ENABLED_RULE,
+                Severity::Warning,
+                "lsp",
+                "Every configured LSP server is disabled, agents get no language server diagnostics",
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use serde_json::json;
+
+    #[test]
+    fn test_every_agent_disabled_is_an_error() {
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["oracle".to_string()]),
+            lsp: Some(json!({ "rust-analyzer": { "disabled": true } })),
+            ..Default::default()
+        };
+        let mut profile = OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "model": "openai/o3" }, "explore": { "disable": true } }));
+
+        let issues = validate_something_enabled(&global, &profile);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].path, "disabled_agents");
+        assert!(issues[0].message.contains("at least one agent must be active"));
+        assert_eq!((issues[1].severity, issues[1].path.as_str()), (Severity::Warning, "lsp"));
+
+        profile.agents = Some(parse_agents(Some(&json!({ "oracle": {}, "explore": {} }))));
+        let issues = validate_something_enabled(&global, &profile);
+        assert!(issues.iter().all(|issue| issue.severity != Severity::Error));
+    }
+
+    #[test]
+    fn test_only_conditional_agents_active_is_a_warning() {
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["explore".to_string()]),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "oracle": { "model": "openai/o3", "when": { "path_exists": "Cargo.toml" } },
+            "explore": { "model": "openai/o3" }
+        }));
+
+        let issues = validate_something_enabled(&global, &profile);
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].severity, issues[0].path.as_str()), (Severity::Warning, "agents"));
+        assert!(issues[0].message.contains("Only conditional agents are active"));
+    }
+
+    #[test]
+    fn test_clear_all_disabled() {
+        let mut global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["explore".to_string(), "oracle".to_string()]),
+            disabled_mcps: Some(vec!["websearch".to_string()]),
+            disabled_hooks: Some(vec!["comment-checker".to_string(), "auto-update-checker".to_string()]),
+            ..Default::default()
+        };
+        let report = clear_all_disabled(&mut global, &[]);
+
+        assert_eq!(report, ClearReport { agents: 2, mcps: 1, hooks: 2 });
+        assert_eq!(report.total(), 5);
+        assert_eq!(global.disabled_agents, None);
+        assert_eq!(global.disabled_mcps, None);
+        assert_eq!(global.disabled_hooks, None);
+    }
+
+    #[test]
+    fn test_clear_all_disabled_keeps_locked_agents() {
+        let configs = vec![OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "locked": true }, "explore": {} }))];
+        let mut global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["explore".to_string(), "oracle".to_string()]),
+            ..Default::default()
+        };
+        let report = clear_all_disabled(&mut global, &locked_agents(&configs));
+
+        assert_eq!(report.agents, 1);
+        assert_eq!(global.disabled_agents, Some(vec!["oracle".to_string()]));
+    }
+
+    #[test]
+    fn test_merge_disabled_casings() {
+        let mut content = json!({
+            "agents": { "builder": {}, "oracle": {} },
+            "mcp": { "websearch": {} },
+            "disabled_agents": ["Builder", "ORACLE"],
+            "disabledAgents": ["builder", "librarian"],
+            "disabledMcps": ["WebSearch"],
+            "disabled_hooks": ["comment-checker"]
+        });
+
+        let merges = merge_disabled_casings(&mut content);
+
+        assert_eq!(content["disabled_agents"], json!(["builder", "oracle", "librarian"]));
+        assert_eq!(content["disabled_mcps"], json!(["websearch"]));
+        assert_eq!(content["disabled_hooks"], json!(["comment-checker"]));
+        assert!(content.get("disabledAgents").is_none());
+        assert!(content.get("disabledMcps").is_none());
+        assert_eq!(
+            merges,
+            vec![
+                DisabledMerge {
+                    list: "disabled_agents".to_string(),
+                    from: vec!["Builder".to_string(), "builder".to_string()],
+                    into: "builder".to_string(),
+                },
+                DisabledMerge {
+                    list: "disabled_agents".to_string(),
+                    from: vec!["ORACLE".to_string()],
+                    into: "oracle".to_string(),
+                },
+                DisabledMerge {
+                    list: "disabled_mcps".to_string(),
+                    from: vec!["WebSearch".to_string()],
+                    into: "websearch".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_global_config_from_db_value_merges_casings() {
+        let global = crate::coding::oh_my_opencode::adapter::global_config_from_db_value(json!({
+            "disabled_agents": ["oracle"],
+            "disabledAgents": ["explore", "Oracle"],
+            "disabledMcps": ["websearch"]
+        }));
+
+        assert_eq!(global.disabled_agents, Some(vec!["oracle".to_string(), "explore".to_string()]));
+        assert_eq!(global.disabled_mcps, Some(vec!["websearch".to_string()]));
+        assert_eq!(global.disabled_hooks, None);
+    }
+}
@@ -0,0 +1,145 @@
+//! Oh My OpenCode Duplicates Module
+//!
+//! Detect configs with identical content and merge them.
+
+use chrono::DateTime;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+use super::fingerprint;
+use super::types::OhMyOpenCodeConfig;
+
+/// Content fingerprint of a config: agents and other fields, ignoring name, id and timestamps
+pub fn config_fingerprint(config: &OhMyOpenCodeConfig) -> String {
+    fingerprint::fingerprint(&json!({
+        "agents": config.agents,
+        "other_fields": config.other_fields,
+    }))
+}
+
+/// One group of duplicates after merging
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateMerge {
+    pub kept: String,
+    pub removed: Vec<String>,
+    /// The kept config took over the applied flag of a removed duplicate
+    pub applied_moved: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeDupReport {
+    pub merges: Vec<DuplicateMerge>,
+}
+
+impl MergeDupReport {
+    pub fn removed_ids(&self) -> Vec<String> {
+        self.merges.iter().flat_map(|m| m.removed.clone()).collect()
+    }
+}
+
+/// Sort key for "oldest first", configs without a valid `created_at` count as newest
+fn created_key(config: &OhMyOpenCodeConfig) -> (bool, i64) {
+    match config
+        .created_at
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    {
+        Some(time) => (false, time.timestamp_millis()),
+        None => (true, 0),
+    }
+}
+
+/// Merge content-identical configs: the oldest of each group is kept, the others removed
+/// If any removed duplicate was applied, the kept config becomes the applied one
+pub fn merge_duplicate_configs(configs: &mut Vec<OhMyOpenCodeConfig>) -> MergeDupReport {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut by_fingerprint: HashMap<String, usize> = HashMap::new();
+    for (index, config) in configs.iter().enumerate() {
+        let group = *by_fingerprint
+            .entry(config_fingerprint(config))
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+        groups[group].push(index);
+    }
+
+    let mut report = MergeDupReport::default();
+    let mut remove: Vec<usize> = Vec::new();
+
+    for mut group in groups.into_iter().filter(|g| g.len() > 1) {
+        // Stable sort keeps the list order for equal timestamps
+        group.sort_by_key(|&i| created_key(&configs[i]));
+        let keep = group[0];
+        let duplicates = &group[1..];
+
+        let applied_moved =
+            !configs[keep].is_applied && duplicates.iter().any(|&i| configs[i].is_applied);
+        if applied_moved {
+            configs[keep].is_applied = true;
+        }
+
+        report.merges.push(DuplicateMerge {
+            kept: configs[keep].id.clone(),
+            removed: duplicates.iter().map(|&i| configs[i].id.clone()).collect(),
+            applied_moved,
+        });
+        remove.extend_from_slice(duplicates);
+    }
+
+    let mut index = 0;
+    configs.retain(|_| {
+        let keep = !remove.contains(&index);
+        index += 1;
+        keep
+    });
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+
+    fn config(id: &str, created_at: &str, is_applied: bool) -> OhMyOpenCodeConfig {
+        OhMyOpenCodeConfig {
+            id: id.to_string(),
+            name: format!("Config {}", id),
+            is_applied,
+            agents: Some(parse_agents(Some(&json!({ "oracle": { "model": "openai/o3" } })))),
+            other_fields: None,
+            created_at: Some(created_at.to_string()),
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_duplicate_configs() {
+        let mut other = config("c", "2026-01-01T00:00:00+00:00", false);
+        other.agents = Some(parse_agents(Some(&json!({ "explore": { "model": "qwen/qwen3" } }))));
+
+        let mut configs = vec![
+            config("newer", "2026-03-01T00:00:00+08:00", true),
+            config("older", "2026-02-01T00:00:00+08:00", false),
+            other,
+        ];
+
+        let report = merge_duplicate_configs(&mut configs);
+        assert_eq!(
+            report.merges,
+            vec![DuplicateMerge {
+                kept: "older".to_string(),
+                removed: vec!["newer".to_string()],
+                applied_moved: true,
+            }]
+        );
+
+        let ids: Vec<&str> = configs.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["older", "c"]);
+        assert!(configs[0].is_applied);
+    }
+}
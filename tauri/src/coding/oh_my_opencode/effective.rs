@@ -0,0 +1,219 @@
+//! Oh My OpenCode Effective Config Module
+//!
+//! The config oh-my-opencode actually loads for a project, as a read-only preview.
+//!
+//! Pipeline:
+//! 1. expand `extends` and drop conditional agents (`when`) that do not match the project
+//! 2. render global + profile (prompt refs inlined, AI Toolbox-only fields stripped)
+//! 3. merge the project config (`<project>/.opencode/oh-my-opencode.json[c]`)
+//! 4. remove disabled agents and MCP servers
+//! 5. substitute `{env:NAME}` (config-level env first) and expand `~/` in string values
+
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::adapter;
+use super::config_env;
+use super::inheritance;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Project config file names, checked in order
+const PROJECT_CONFIG_FILES: &[&str] = &["oh-my-opencode.jsonc", "oh-my-opencode.json"];
+
+/// Evaluate the `when` condition of an agent against the project directory
+/// Supported: `{ "path_exists": "Cargo.toml" }` or a list of paths (all must exist)
+/// Agents without a condition, or with an unknown one, are always kept
+fn matches_condition(condition: Option<&Value>, project_dir: Option<&Path>) -> bool {
+    let Some(paths) = condition.and_then(|c| c.get("path_exists")) else {
+        return true;
+    };
+    let paths: Vec<&str> = match paths {
+        Value::String(path) => vec![path.as_str()],
+        Value::Array(items) => items.iter().filter_map(|v| v.as_str()).collect(),
+        _ => return true,
+    };
+    match project_dir {
+        Some(dir) => paths.iter().all(|path| dir.join(path).exists()),
+        None => false,
+    }
+}
+
+/// Read the project-level oh-my-opencode config, if present
+fn read_project_config(project_dir: &Path) -> Option<Value> {
+    let opencode_dir = project_dir.join(".opencode");
+    PROJECT_CONFIG_FILES.iter().find_map(|file| {
+        let content = std::fs::read_to_string(opencode_dir.join(file)).ok()?;
+        match json5::from_str::<Value>(&content) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                eprintln!("Failed to parse project config {}: {}", file, e);
+                None
+            }
+        }
+    })
+}
+
+/// Remove entries listed in `disabled_key` from the `section_key` map
+fn remove_disabled(config: &mut Value, section_key: &str, disabled_key: &str) {
+    let disabled: Vec<String> = config
+        .get(disabled_key)
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default();
+    if let Some(section) = config.get_mut(section_key).and_then(|v| v.as_object_mut()) {
+        section.retain(|name, _| !disabled.contains(name));
+    }
+}
+
+/// Substitute `{env:NAME}` and expand a leading `~/` in every string value
+fn resolve_strings(value: &mut Value, env: &dyn Fn(&str) -> Option<String>, home: Option<&Path>) {
+    match value {
+        Value::String(s) => {
+            let mut resolved = String::with_capacity(s.len());
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{env:") {
+                let Some(len) = rest[start..].find('}') else {
+                    break;
+                };
+                resolved.push_str(&rest[..start]);
+                let name = &rest[start + "{env:".len()..start + len];
+                resolved.push_str(&env(name).unwrap_or_default());
+                rest = &rest[start + len + 1..];
+            }
+            resolved.push_str(rest);
+
+            if let (Some(home), Some(tail)) = (home, resolved.strip_prefix("~/")) {
+                resolved = home.join(tail).to_string_lossy().to_string();
+            }
+            *s = resolved;
+        }
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|item| resolve_strings(item, env, home)),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| resolve_strings(item, env, home)),
+        _ => {}
+    }
+}
+
+/// Run the full pipeline with an explicit environment, used by `effective_config` and tests
+pub fn effective_config_with_env(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    project_dir: Option<&Path>,
+    env: &dyn Fn(&str) -> Option<String>,
+    home: Option<&Path>,
+) -> Value {
+    // Config-level env is not part of the rendered file, read it first
+    let config_env = config_env::config_env(profile);
+    let env = |name: &str| config_env.get(name).cloned().or_else(|| env(name));
+
+    // 1. Inheritance first, so conditional parents can be dropped without breaking children
+    let mut profile = profile.clone();
+    if profile.agents.is_some() {
+        let parsed = profile.typed_agents();
+        let mut agents = inheritance::resolve_agents(&parsed).unwrap_or_else(|e| {
+            eprintln!("Failed to resolve agent inheritance: {}", e);
+            parsed
+        });
+        agents.retain(|_, agent| matches_condition(agent.extra.get("when"), project_dir));
+        profile.agents = Some(agents);
+    }
+
+    // 2. Global + profile
+    let mut config = render_opencode_json(global, &profile);
+
+    // 3. Project config has the highest priority
+    if let Some(project_config) = project_dir.and_then(read_project_config) {
+        adapter::deep_merge_json(&mut config, &project_config);
+    }
+
+    // 4. Disabled entries are never loaded
+    remove_disabled(&mut config, "agents", "disabled_agents");
+    remove_disabled(&mut config, "mcp", "disabled_mcps");
+
+    // 5. Env and paths
+    resolve_strings(&mut config, &env, home);
+
+    adapter::clean_empty_values(&mut config);
+    config
+}
+
+/// The final config oh-my-opencode would load, optionally for a project directory
+pub fn effective_config(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    project_dir: Option<String>,
+) -> Value {
+    let project_dir = project_dir.map(PathBuf::from);
+    let home = dirs::home_dir();
+    effective_config_with_env(
+        global,
+        profile,
+        project_dir.as_deref(),
+        &|name| std::env::var(name).ok(),
+        home.as_deref(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_effective_config_pipeline() {
+        let project_dir =
+            std::env::temp_dir().join(format!("ai-toolbox-effective-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(project_dir.join(".opencode")).unwrap();
+        fs::write(project_dir.join("Cargo.toml"), "").unwrap();
+        fs::write(
+            project_dir.join(".opencode").join("oh-my-opencode.json"),
+            r#"{ "agents": { "oracle": { "temperature": 0.1 } } }"#,
+        )
+        .unwrap();
+
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["explore".to_string()]),
+            other_fields: Some(json!({ "prompt_library": { "rust": "You know Rust." } })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "base": { "model": "openai/gpt-4o", "instructions": "~/notes.md" },
+            "oracle": { "extends": "base", "prompt_ref": "rust", "when": { "path_exists": "Cargo.toml" } },
+            "frontend": { "extends": "base", "when": { "path_exists": "package.json" } },
+            "explore": { "model": "qwen/qwen3" },
+            "keyed": { "model": "{env:MODEL_OVERRIDE}" }
+        }));
+
+        let env = |name: &str| (name == "MODEL_OVERRIDE").then(|| "openai/o3".to_string());
+        let config = effective_config_with_env(
+            &global,
+            &profile,
+            Some(&project_dir),
+            &env,
+            Some(Path::new("/home/dev")),
+        );
+        fs::remove_dir_all(&project_dir).unwrap();
+
+        let agents = config["agents"].as_object().unwrap();
+        let names: Vec<&String> = agents.keys().collect();
+        assert_eq!(names, vec!["base", "oracle", "keyed"]);
+
+        let oracle = &agents["oracle"];
+        assert_eq!(oracle["model"], json!("openai/gpt-4o"));
+        assert_eq!(oracle["prompt"], json!("You know Rust."));
+        assert_eq!(oracle["temperature"], json!(0.1));
+        assert!(oracle.get("when").is_none());
+        assert!(oracle.get("prompt_ref").is_none());
+        assert_eq!(
+            PathBuf::from(oracle["instructions"].as_str().unwrap()),
+            Path::new("/home/dev").join("notes.md")
+        );
+        assert_eq!(agents["keyed"]["model"], json!("openai/o3"));
+        assert!(config.get("prompt_library").is_none());
+    }
+}
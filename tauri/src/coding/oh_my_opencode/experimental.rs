@@ -0,0 +1,108 @@
+//! Oh My OpenCode Experimental Module
+//!
+//! Which `experimental` flags the oh-my-opencode plugin actually reads, so dead flags
+//! can be pruned. The flags belong to the plugin, not to opencode, so they are checked
+//! against the `experimental` section of the plugin's published JSON schema.
+
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum FlagStatus {
+    /// Listed in the schema
+    Active,
+    /// Not in the schema, the plugin ignores it
+    Unknown,
+    /// Listed in the schema with `"deprecated": true`
+    Deprecated,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlagClassification {
+    pub flag: String,
+    #[serde(flatten)]
+    pub status: FlagStatus,
+}
+
+/// Label every top-level key of the `experimental` section against the plugin schema, in order
+pub fn classify_experimental_flags(experimental: &Value, schema: &Value) -> Vec<FlagClassification> {
+    let Some(flags) = experimental.as_object() else {
+        return Vec::new();
+    };
+    let known = schema.pointer("/properties/experimental/properties").and_then(|v| v.as_object());
+
+    flags
+        .keys()
+        .map(|flag| {
+            let status = match known.and_then(|known| known.get(flag)) {
+                None => FlagStatus::Unknown,
+                Some(definition) if definition.get("deprecated").and_then(|v| v.as_bool()) == Some(true) => {
+                    FlagStatus::Deprecated
+                }
+                Some(_) => FlagStatus::Active,
+            };
+            FlagClassification {
+                flag: flag.clone(),
+                status,
+            }
+        })
+        .collect()
+}
+
+/// Download the oh-my-opencode schema at `url`
+pub async fn fetch_schema(client: &reqwest::Client, url: &str) -> Result<Value, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch oh-my-opencode schema: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Schema request failed: {}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse oh-my-opencode schema: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_classify_experimental_flags() {
+        let schema = json!({
+            "properties": {
+                "experimental": {
+                    "type": "object",
+                    "properties": {
+                        "auto_resume": { "type": "boolean" },
+                        "enable_preemptive_compaction": { "type": "boolean", "deprecated": true }
+                    }
+                }
+            }
+        });
+        let experimental = json!({
+            "auto_resume": true,
+            "enable_preemptive_compaction": true,
+            "turbo_mode": true
+        });
+
+        let flags = classify_experimental_flags(&experimental, &schema);
+
+        let statuses: Vec<(&str, &FlagStatus)> = flags.iter().map(|f| (f.flag.as_str(), &f.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("auto_resume", &FlagStatus::Active),
+                ("enable_preemptive_compaction", &FlagStatus::Deprecated),
+                ("turbo_mode", &FlagStatus::Unknown),
+            ]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Oh My OpenCode Failover Module
+//!
+//! Fallback provider chains stored in the global config
+//! (`other_fields.provider_fallbacks`, provider -> ordered list of fallback providers).
+
+use std::collections::HashMap;
+
+use super::types::OhMyOpenCodeGlobalConfig;
+use crate::coding::open_code::provider_id::canonical_provider_id;
+
+/// Key of the fallback chains in the global `other_fields`
+pub const PROVIDER_FALLBACKS_KEY: &str = "provider_fallbacks";
+
+/// Canonical provider of a `provider/model` id
+pub fn model_provider(model_id: &str) -> Option<String> {
+    model_id
+        .split_once('/')
+        .map(|(provider, _)| canonical_provider_id(provider))
+        .filter(|provider| !provider.is_empty())
+}
+
+/// Canonical providers to try for a model, primary first
+/// Chain keys and entries match by canonical id, so `OpenAI` and `openai` are one provider.
+/// Duplicates in the chain are skipped so a misconfigured chain cannot loop
+pub fn provider_chain(model_id: &str, global: &OhMyOpenCodeGlobalConfig) -> Vec<String> {
+    let Some(primary) = model_provider(model_id) else {
+        return Vec::new();
+    };
+
+    let mut chain = vec![primary.clone()];
+    let chains = global
+        .other_fields
+        .as_ref()
+        .and_then(|fields| fields.get(PROVIDER_FALLBACKS_KEY))
+        .and_then(|chains| chains.as_object());
+    let fallbacks = chains
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| canonical_provider_id(key) == primary)
+        .filter_map(|(_, list)| list.as_array())
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .map(canonical_provider_id);
+
+    for provider in fallbacks {
+        if !provider.is_empty() && !chain.contains(&provider) {
+            chain.push(provider);
+        }
+    }
+    chain
+}
+
+/// The first healthy provider of the chain, `None` when all are down
+/// Providers missing from `health` have not been checked yet and count as healthy, its keys
+/// match by canonical id too
+pub fn select_provider(
+    model_id: &str,
+    global: &OhMyOpenCodeGlobalConfig,
+    health: &HashMap<String, bool>,
+) -> Option<String> {
+    let health: HashMap<String, bool> = health
+        .iter()
+        .map(|(provider, healthy)| (canonical_provider_id(provider), *healthy))
+        .collect();
+    provider_chain(model_id, global)
+        .into_iter()
+        .find(|provider| health.get(provider).copied().unwrap_or(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn global() -> OhMyOpenCodeGlobalConfig {
+        OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({
+                "provider_fallbacks": { "anthropic": ["openrouter", "anthropic", "bedrock"] }
+            })),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_select_fallback_when_primary_down() {
+        let global = global();
+        assert_eq!(
+            provider_chain("anthropic/claude-opus-4", &global),
+            vec!["anthropic", "openrouter", "amazon-bedrock"]
+        );
+
+        let health = HashMap::from([
+            ("anthropic".to_string(), false),
+            ("openrouter".to_string(), false),
+            ("bedrock".to_string(), true),
+        ]);
+        assert_eq!(
+            select_provider("anthropic/claude-opus-4", &global, &health).as_deref(),
+            Some("amazon-bedrock")
+        );
+
+        let all_down = HashMap::from([
+            ("anthropic".to_string(), false),
+            ("openrouter".to_string(), false),
+            ("bedrock".to_string(), false),
+        ]);
+        assert_eq!(select_provider("anthropic/claude-opus-4", &global, &all_down), None);
+
+        // No chain configured: only the primary
+        assert_eq!(
+            select_provider("openai/o3", &global, &HashMap::new()).as_deref(),
+            Some("openai")
+        );
+    }
+
+    #[test]
+    fn test_chain_matches_providers_by_canonical_id() {
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({
+                "provider_fallbacks": { "Anthropic": ["OpenRouter", "claude", "open-router", "AWS_Bedrock"] }
+            })),
+            ..Default::default()
+        };
+        assert_eq!(
+            provider_chain("anthropic/claude-opus-4", &global),
+            vec!["anthropic", "openrouter", "amazon-bedrock"]
+        );
+
+        let health = HashMap::from([("Anthropic".to_string(), false), ("OPENROUTER".to_string(), false)]);
+        assert_eq!(
+            select_provider("ANTHROPIC/claude-opus-4", &global, &health).as_deref(),
+            Some("amazon-bedrock")
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Oh My OpenCode Fingerprint Module
+//!
+//! Canonical JSON serialization and content hashing.
+//! Key order, whitespace and null fields never change a fingerprint.
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Serialize a JSON value in canonical form: object keys sorted, null fields dropped, no whitespace
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map
+                .iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, _)| k)
+                .collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String((*key).clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key.as_str()], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+/// SHA-256 hex digest of the canonical form of a JSON value
+pub fn fingerprint(value: &Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_json(value).as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_json_ignores_order_and_nulls() {
+        let a = json!({ "b": 1, "a": { "y": [1, 2], "x": null } });
+        let b = json!({ "a": { "y": [1, 2] }, "b": 1 });
+        assert_eq!(canonical_json(&a), r#"{"a":{"y":[1,2]},"b":1}"#);
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_ne!(fingerprint(&a), fingerprint(&json!({ "b": 2 })));
+    }
+}
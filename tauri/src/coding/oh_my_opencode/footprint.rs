@@ -0,0 +1,173 @@
+//! Oh My OpenCode Footprint Module
+//!
+//! Rough token footprint of a config: agent prompts plus MCP server overhead.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::inheritance;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Default warning threshold for the combined footprint
+pub const DEFAULT_FOOTPRINT_THRESHOLD: usize = 32_000;
+/// Prompts above this size are reported individually
+const LARGE_PROMPT_TOKENS: usize = 4_000;
+/// Estimated tokens added by the tool definitions of one MCP server
+const MCP_SERVER_OVERHEAD_TOKENS: usize = 1_500;
+
+/// Estimate the token count of a prompt
+/// About 4 ASCII characters per token, other characters (e.g. CJK) count as one token each
+pub fn estimate_prompt_tokens(text: &str) -> usize {
+    let (ascii, other) = text.chars().fold((0usize, 0usize), |(ascii, other), c| {
+        if c.is_ascii() {
+            (ascii + 1, other)
+        } else {
+            (ascii, other + 1)
+        }
+    });
+    ascii.div_ceil(4) + other
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentFootprint {
+    pub name: String,
+    pub prompt_tokens: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FootprintReport {
+    /// Active agents, largest prompt first
+    pub agents: Vec<AgentFootprint>,
+    pub prompt_tokens: usize,
+    pub mcp_servers: usize,
+    pub lsp_servers: usize,
+    pub overhead_tokens: usize,
+    pub total_tokens: usize,
+    pub threshold: usize,
+    pub warnings: Vec<String>,
+}
+
+/// Count enabled servers of a `mcp` / `lsp` section
+fn count_servers(section: Option<&Value>, disabled: &[String]) -> usize {
+    section
+        .and_then(|v| v.as_object())
+        .map(|servers| {
+            servers
+                .iter()
+                .filter(|(name, server)| {
+                    !disabled.contains(name)
+                        && server.get("disabled").and_then(|d| d.as_bool()) != Some(true)
+                        && server.get("enabled").and_then(|e| e.as_bool()) != Some(false)
+                })
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Compute the footprint of the config that would be written for this profile
+pub fn config_footprint(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    threshold: Option<usize>,
+) -> FootprintReport {
+    let threshold = threshold.unwrap_or(DEFAULT_FOOTPRINT_THRESHOLD);
+    let disabled_agents = global.disabled_agents.clone().unwrap_or_default();
+    let disabled_mcps = global.disabled_mcps.clone().unwrap_or_default();
+
+    let parsed = profile.typed_agents();
+    let agents = inheritance::resolve_agents(&parsed).unwrap_or(parsed);
+
+    let mut footprints: Vec<AgentFootprint> = agents
+        .iter()
+        .filter(|(name, _)| !disabled_agents.contains(name))
+        .map(|(name, agent)| AgentFootprint {
+            name: name.clone(),
+            prompt_tokens: agent.prompt.as_deref().map(estimate_prompt_tokens).unwrap_or(0),
+        })
+        .collect();
+    footprints.sort_by(|a, b| b.prompt_tokens.cmp(&a.prompt_tokens).then(a.name.cmp(&b.name)));
+
+    let rendered = render_opencode_json(global, profile);
+    let mcp_servers = count_servers(rendered.get("mcp"), &disabled_mcps);
+    let lsp_servers = count_servers(rendered.get("lsp"), &[]);
+
+    let prompt_tokens: usize = footprints.iter().map(|a| a.prompt_tokens).sum();
+    let overhead_tokens = mcp_servers * MCP_SERVER_OVERHEAD_TOKENS;
+    let total_tokens = prompt_tokens + overhead_tokens;
+
+    let mut warnings = Vec::new();
+    if total_tokens > threshold {
+        warnings.push(format!(
+            "Estimated footprint of {} tokens exceeds the threshold of {}",
+            total_tokens, threshold
+        ));
+    }
+    for agent in footprints
+        .iter()
+        .filter(|a| a.prompt_tokens > LARGE_PROMPT_TOKENS)
+    {
+        warnings.push(format!(
+            "Agent '{}' has a large prompt (~{} tokens)",
+            agent.name, agent.prompt_tokens
+        ));
+    }
+
+    FootprintReport {
+        agents: footprints,
+        prompt_tokens,
+        mcp_servers,
+        lsp_servers,
+        overhead_tokens,
+        total_tokens,
+        threshold,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_estimate_prompt_tokens() {
+        assert_eq!(estimate_prompt_tokens(""), 0);
+        assert_eq!(estimate_prompt_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_prompt_tokens("你好"), 2);
+    }
+
+    #[test]
+    fn test_config_footprint_exceeds_threshold() {
+        let large_prompt = "x".repeat(20_000); // ~5000 tokens
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["explore".to_string()]),
+            disabled_mcps: Some(vec!["grep_app".to_string()]),
+            other_fields: Some(json!({
+                "mcp": {
+                    "context7": { "type": "remote" },
+                    "grep_app": { "type": "remote" },
+                    "local": { "type": "local", "enabled": false }
+                }
+            })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "Sisyphus": { "prompt": large_prompt },
+            "oracle": { "prompt": large_prompt },
+            "librarian": { "extends": "oracle" },
+            "explore": { "prompt": large_prompt }
+        }));
+
+        let report = config_footprint(&global, &profile, Some(12_000));
+        assert_eq!(report.agents.len(), 3);
+        assert_eq!(report.prompt_tokens, 15_000);
+        assert_eq!(report.mcp_servers, 1);
+        assert_eq!(report.total_tokens, 15_000 + MCP_SERVER_OVERHEAD_TOKENS);
+        // One threshold warning and one per large prompt
+        assert_eq!(report.warnings.len(), 4);
+        assert!(report.warnings[0].contains("exceeds the threshold"));
+    }
+}
@@ -0,0 +1,251 @@
+//! Oh My OpenCode Global Apply Module
+//!
+//! Write only the global portions of oh-my-opencode.json, keeping the
+//! profile-derived sections (agents, profile fields) of the live file.
+//! A key the applied profile sets wins over the global config, as in `render_opencode_json`.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+use super::render;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Top-level keys always owned by the global config
+/// They are removed from the live file when the global config no longer sets them
+const GLOBAL_FIELDS: &[&str] = &[
+    "$schema",
+    "sisyphus_agent",
+    "disabled_agents",
+    "disabled_mcps",
+    "disabled_hooks",
+    "lsp",
+    "experimental",
+];
+
+/// Sections only a profile writes, never touched by a global-only apply
+const PROFILE_FIELDS: &[&str] = &["agents"];
+
+/// Top-level keys the profile owns in the rendered file: its sections and `other_fields` keys
+fn profile_keys(profile: Option<&OhMyOpenCodeConfig>) -> Vec<&str> {
+    let other_fields = profile
+        .and_then(|profile| profile.other_fields.as_ref())
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flat_map(|fields| fields.keys().map(String::as_str));
+    PROFILE_FIELDS.iter().copied().chain(other_fields).collect()
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ApplyError {
+    #[error("Failed to locate config file: {0}")]
+    ConfigPath(String),
+    #[error("Failed to read {path}: {message}")]
+    Read { path: String, message: String },
+    #[error("Failed to parse {path}: {message}")]
+    Parse { path: String, message: String },
+    #[error("Failed to write {path}: {message}")]
+    Write { path: String, message: String },
+    #[error("Refusing to replace {0} with an empty config")]
+    EmptyWrite(String),
+}
+
+/// A changed top-level key, `None` means absent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiff {
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Render the global config alone, without any profile
+pub(crate) fn render_global(global: &OhMyOpenCodeGlobalConfig) -> Map<String, Value> {
+    let empty_profile = OhMyOpenCodeConfig {
+        id: String::new(),
+        name: String::new(),
+        is_applied: false,
+        agents: None,
+        other_fields: None,
+        created_at: None,
+        updated_at: None,
+    };
+    match render::render_opencode_json(global, &empty_profile) {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+/// Merge the rendered global portions into the live file content, leaving the keys of the
+/// applied `profile` alone. Returns the merged content and the changed keys
+pub fn merge_global_into(
+    live: &Map<String, Value>,
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: Option<&OhMyOpenCodeConfig>,
+) -> (Map<String, Value>, Vec<ConfigDiff>) {
+    let rendered = render_global(global);
+    let profile_keys = profile_keys(profile);
+    let mut merged = live.clone();
+    let mut diffs = Vec::new();
+
+    for (key, value) in &rendered {
+        if profile_keys.contains(&key.as_str()) || live.get(key) == Some(value) {
+            continue;
+        }
+        diffs.push(ConfigDiff {
+            key: key.clone(),
+            before: live.get(key).cloned(),
+            after: Some(value.clone()),
+        });
+        merged.insert(key.clone(), value.clone());
+    }
+
+    // Global fields cleared in the global config disappear from the file
+    let cleared: Vec<String> = merged
+        .keys()
+        .filter(|key| {
+            GLOBAL_FIELDS.contains(&key.as_str())
+                && !rendered.contains_key(key.as_str())
+                && !profile_keys.contains(&key.as_str())
+        })
+        .cloned()
+        .collect();
+    for key in cleared {
+        diffs.push(ConfigDiff {
+            before: merged.shift_remove(&key),
+            key,
+            after: None,
+        });
+    }
+
+    (merged, diffs)
+}
+
+/// The live file merged with the global config and the changed keys, nothing is written
+pub fn merge_global_into_file(
+    path: &Path,
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: Option<&OhMyOpenCodeConfig>,
+) -> Result<(Map<String, Value>, Vec<ConfigDiff>), ApplyError> {
+    let path_str = path.to_string_lossy().to_string();
+
+    let live = if path.exists() {
+        let content = fs::read_to_string(path).map_err(|e| ApplyError::Read {
+            path: path_str.clone(),
+            message: e.to_string(),
+        })?;
+        match json5::from_str::<Value>(&content) {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => Map::new(),
+            Err(e) => {
+                return Err(ApplyError::Parse {
+                    path: path_str,
+                    message: e.to_string(),
+                })
+            }
+        }
+    } else {
+        Map::new()
+    };
+
+    Ok(merge_global_into(&live, global, profile))
+}
+
+/// Write merged content produced by `merge_global_into_file`
+pub fn write_merged(path: &Path, merged: &Map<String, Value>) -> Result<(), ApplyError> {
+    let write_error = |e: String| ApplyError::Write {
+        path: path.to_string_lossy().to_string(),
+        message: e,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| write_error(e.to_string()))?;
+    }
+    let json_content =
+        serde_json::to_string_pretty(&Value::Object(merged.clone())).map_err(|e| write_error(e.to_string()))?;
+    fs::write(path, json_content).map_err(|e| write_error(e.to_string()))
+}
+
+/// Global-only apply against a specific file, the file is only written when something changed
+pub fn apply_global_only_to_path(
+    path: &Path,
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: Option<&OhMyOpenCodeConfig>,
+) -> Result<Vec<ConfigDiff>, ApplyError> {
+    let (merged, diffs) = merge_global_into_file(path, global, profile)?;
+    if !diffs.is_empty() {
+        write_merged(path, &merged)?;
+    }
+    Ok(diffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_global_only_apply_keeps_agents() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-global-apply-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("oh-my-opencode.json");
+        fs::create_dir_all(&dir).unwrap();
+        let agents = json!({ "oracle": { "model": "openai/o3", "prompt": "Think hard." } });
+        let live = json!({
+            "$schema": render::DEFAULT_SCHEMA_URL,
+            "agents": agents,
+            "disabled_mcps": ["websearch"],
+            "disabled_hooks": ["comment-checker"],
+            "google_auth": false
+        });
+        fs::write(&path, serde_json::to_string_pretty(&live).unwrap()).unwrap();
+
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_mcps: Some(vec!["context7".to_string()]),
+            lsp: Some(json!({ "rust": { "command": ["rust-analyzer"] } })),
+            ..Default::default()
+        };
+        let diffs = apply_global_only_to_path(&path, &global, None).unwrap();
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(written["agents"], agents);
+        assert_eq!(written["google_auth"], json!(false));
+        assert_eq!(written["disabled_mcps"], json!(["context7"]));
+        assert_eq!(written["lsp"]["rust"]["command"], json!(["rust-analyzer"]));
+        assert!(written.get("disabled_hooks").is_none());
+
+        let keys: Vec<&str> = diffs.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(keys, vec!["disabled_mcps", "lsp", "disabled_hooks"]);
+    }
+
+    #[test]
+    fn test_global_only_apply_keeps_profile_fields() {
+        let live = json!({
+            "$schema": render::DEFAULT_SCHEMA_URL,
+            "agents": { "oracle": { "model": "openai/o3" } },
+            "google_auth": true,
+            "lsp": { "rust": { "disabled": true } },
+            "disabled_hooks": ["comment-checker"]
+        });
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_hooks: Some(vec!["auto-update-checker".to_string()]),
+            other_fields: Some(json!({ "google_auth": false })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig {
+            is_applied: true,
+            other_fields: Some(json!({ "google_auth": true, "lsp": { "rust": { "disabled": true } } })),
+            ..OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "model": "openai/o3" } }))
+        };
+
+        let (merged, diffs) = merge_global_into(live.as_object().unwrap(), &global, Some(&profile));
+
+        // The profile's keys win over the global config and are not cleared
+        assert_eq!(merged["google_auth"], json!(true));
+        assert_eq!(merged["lsp"], live["lsp"]);
+        assert_eq!(merged["disabled_hooks"], json!(["auto-update-checker"]));
+        let keys: Vec<&str> = diffs.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(keys, vec!["disabled_hooks"]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Oh My OpenCode Global Impact Module
+//!
+//! Which agents follow a global default and which override it. An agent field is taken,
+//! highest first, from the agent itself (with `extends` expanded), the profile's
+//! `other_fields`, then the global `other_fields`.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::agent::AgentProfile;
+use super::inheritance;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Where the effective value of an agent field comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ValueSource {
+    Agent,
+    Profile,
+    Global,
+}
+
+fn agent_value(agent: &AgentProfile, field: &str) -> Option<Value> {
+    agent.to_value().get(field).filter(|v| !v.is_null()).cloned()
+}
+
+fn level_value(other_fields: Option<&Value>, field: &str) -> Option<Value> {
+    other_fields?.get(field).filter(|v| !v.is_null()).cloned()
+}
+
+fn resolved_agents(profile: &OhMyOpenCodeConfig) -> indexmap::IndexMap<String, AgentProfile> {
+    let parsed = profile.typed_agents();
+    inheritance::resolve_agents(&parsed).unwrap_or(parsed)
+}
+
+fn effective_value(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    agent: &AgentProfile,
+    field: &str,
+) -> Option<(Value, ValueSource)> {
+    agent_value(agent, field)
+        .map(|v| (v, ValueSource::Agent))
+        .or_else(|| level_value(profile.other_fields.as_ref(), field).map(|v| (v, ValueSource::Profile)))
+        .or_else(|| level_value(global.other_fields.as_ref(), field).map(|v| (v, ValueSource::Global)))
+}
+
+/// Effective value of `field` for one agent and where it comes from, `None` when nothing sets it
+pub fn resolve_effective_value(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    agent_name: &str,
+    field: &str,
+) -> Option<(Value, ValueSource)> {
+    let agents = resolved_agents(profile);
+    effective_value(global, profile, agents.get(agent_name)?, field)
+}
+
+/// Agents of a profile that would pick up a change of the global `field`, in profile order
+/// Agents setting the field themselves (or through `extends`), agents of a profile that
+/// sets it, and globally disabled agents are not affected
+pub fn agents_affected_by_global(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    field: &str,
+) -> Vec<String> {
+    if level_value(profile.other_fields.as_ref(), field).is_some() {
+        return Vec::new();
+    }
+    let disabled = global.disabled_agents.as_deref().unwrap_or_default();
+    resolved_agents(profile)
+        .into_iter()
+        .filter(|(name, agent)| !disabled.contains(name) && agent_value(agent, field).is_none())
+        .map(|(name, _)| name)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_agents_affected_by_default_model() {
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["frontend".to_string()]),
+            other_fields: Some(json!({ "model": "anthropic/claude-sonnet-4" })),
+            ..Default::default()
+        };
+        let mut profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "oracle": { "model": "openai/o3" },
+            "reviewer": { "extends": "oracle" },
+            "explore": { "prompt": "Find files" },
+            "librarian": {},
+            "frontend": {}
+        }));
+
+        assert_eq!(agents_affected_by_global(&global, &profile, "model"), vec!["explore", "librarian"]);
+        assert_eq!(
+            resolve_effective_value(&global, &profile, "reviewer", "model"),
+            Some((json!("openai/o3"), ValueSource::Agent))
+        );
+        assert_eq!(
+            resolve_effective_value(&global, &profile, "explore", "model"),
+            Some((json!("anthropic/claude-sonnet-4"), ValueSource::Global))
+        );
+
+        // A profile-level default shields every agent from the global one
+        profile.other_fields = Some(json!({ "model": "deepseek/deepseek-chat" }));
+        assert!(agents_affected_by_global(&global, &profile, "model").is_empty());
+        assert_eq!(
+            resolve_effective_value(&global, &profile, "librarian", "model"),
+            Some((json!("deepseek/deepseek-chat"), ValueSource::Profile))
+        );
+    }
+}
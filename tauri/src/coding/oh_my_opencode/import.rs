@@ -0,0 +1,193 @@
+//! Oh My OpenCode Import Module
+//!
+//! Import agents and configs from external files.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::agent::AgentProfile;
+use super::markdown;
+use super::raw_json;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ImportError {
+    #[error("Failed to read '{path}': {message}")]
+    Io { path: String, message: String },
+    #[error("Agent '{name}' is defined in several files: {}", .files.join(", "))]
+    NameCollision { name: String, files: Vec<String> },
+    #[error("Invalid frontmatter at line {line}: {message}")]
+    Frontmatter { line: usize, message: String },
+    #[error("Agent markdown has no name")]
+    MissingName,
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(String),
+    #[error("Failed to decrypt bundle: wrong passphrase or corrupted file")]
+    DecryptionFailed,
+}
+
+/// A file that could not be imported
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileImportError {
+    pub file: String,
+    pub message: String,
+}
+
+/// Result of importing a directory of agent files
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentDirImport {
+    pub agents: HashMap<String, AgentProfile>,
+    /// Files that failed to parse, the rest of the import still succeeds
+    pub errors: Vec<FileImportError>,
+}
+
+/// Supported agent file extensions
+const AGENT_FILE_EXTENSIONS: &[&str] = &["json", "jsonc", "toml", "yaml", "yml", "md"];
+
+/// Parse the content of an agent file according to its extension
+fn parse_agent_file(path: &Path, content: &str) -> Result<AgentProfile, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    if extension == "md" {
+        return markdown::parse_agent_markdown(content)
+            .map(|(_, agent)| agent)
+            .map_err(|e| e.to_string());
+    }
+
+    let value: Value = match extension.as_str() {
+        "toml" => {
+            let toml_value: toml::Value = toml::from_str(content).map_err(|e| e.to_string())?;
+            serde_json::to_value(toml_value).map_err(|e| e.to_string())?
+        }
+        "yaml" | "yml" => serde_norway::from_str(content).map_err(|e| e.to_string())?,
+        // 使用 json5 支持带注释的 JSONC 格式，先 lint 以报告错误位置
+        _ => raw_json::parse_linted(content)?,
+    };
+
+    AgentProfile::from_value(&value).ok_or_else(|| "Agent file must contain an object".to_string())
+}
+
+/// Import every agent file of a directory, keyed by file stem
+/// Markdown files are parsed as opencode agent markdown
+/// Unparseable files are reported in `errors`, two files with the same stem abort the import
+pub fn import_agents_from_dir(dir: &Path) -> Result<AgentDirImport, ImportError> {
+    let entries = fs::read_dir(dir).map_err(|e| ImportError::Io {
+        path: dir.to_string_lossy().to_string(),
+        message: e.to_string(),
+    })?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .map(|e| AGENT_FILE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+
+    // Check name collisions before parsing anything
+    let mut by_name: HashMap<String, Vec<String>> = HashMap::new();
+    for path in &files {
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            by_name
+                .entry(stem.to_string())
+                .or_default()
+                .push(path.file_name().unwrap_or_default().to_string_lossy().to_string());
+        }
+    }
+    let mut collisions: Vec<(&String, &Vec<String>)> =
+        by_name.iter().filter(|(_, files)| files.len() > 1).collect();
+    collisions.sort();
+    if let Some((name, files)) = collisions.first() {
+        return Err(ImportError::NameCollision {
+            name: (*name).clone(),
+            files: (*files).clone(),
+        });
+    }
+
+    let mut result = AgentDirImport::default();
+    for path in files {
+        let file = path.to_string_lossy().to_string();
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()).map(String::from) else {
+            continue;
+        };
+
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| parse_agent_file(&path, &content));
+
+        match parsed {
+            Ok(agent) => {
+                result.agents.insert(name, agent);
+            }
+            Err(message) => result.errors.push(FileImportError { file, message }),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-import-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_import_agents_from_dir() {
+        let dir = temp_dir();
+        fs::write(
+            dir.join("oracle.json"),
+            r#"{ "model": "openai/o3", "temperature": 0.1 }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("explore.yaml"), "model: qwen/qwen3\nprompt: Explore fast\n").unwrap();
+        fs::write(dir.join("broken.toml"), "model = ").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let imported = import_agents_from_dir(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(imported.agents.len(), 2);
+        assert_eq!(imported.agents["oracle"].model.as_deref(), Some("openai/o3"));
+        assert_eq!(imported.agents["oracle"].temperature, Some(0.1));
+        assert_eq!(imported.agents["explore"].prompt.as_deref(), Some("Explore fast"));
+        assert_eq!(imported.errors.len(), 1);
+        assert!(imported.errors[0].file.ends_with("broken.toml"));
+    }
+
+    #[test]
+    fn test_import_agents_from_dir_collision() {
+        let dir = temp_dir();
+        fs::write(dir.join("oracle.json"), r#"{ "model": "openai/o3" }"#).unwrap();
+        fs::write(dir.join("oracle.toml"), "model = \"openai/o3\"\n").unwrap();
+
+        let result = import_agents_from_dir(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            result.unwrap_err(),
+            ImportError::NameCollision {
+                name: "oracle".to_string(),
+                files: vec!["oracle.json".to_string(), "oracle.toml".to_string()],
+            }
+        );
+    }
+}
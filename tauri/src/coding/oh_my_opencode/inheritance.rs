@@ -0,0 +1,167 @@
+//! Oh My OpenCode Agent Inheritance Module
+//!
+//! Resolves `extends` chains between agents of the same profile.
+//! Fields set on the child win, unset fields are taken from the parent.
+//! `tools` maps are merged per tool, so a child can turn single tools on or off.
+
+use indexmap::IndexMap;
+use thiserror::Error;
+
+use super::agent::AgentProfile;
+use super::tools;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    #[error("Agent '{0}' not found")]
+    NotFound(String),
+    #[error("Agent '{agent}' extends unknown agent '{parent}'")]
+    UnknownParent { agent: String, parent: String },
+    #[error("Circular extends: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Merge a child agent over its resolved parent
+/// `group` and `locked` belong to the child only and are never inherited
+fn merge_agent(parent: &AgentProfile, child: &AgentProfile) -> AgentProfile {
+    let mut extra = parent.extra.clone();
+    for (key, value) in &child.extra {
+        extra.insert(key.clone(), value.clone());
+    }
+
+    // `prompt` and `prompt_ref` are one setting: a child setting either replaces both
+    let prompt_source = if child.prompt.is_some() || child.prompt_ref.is_some() {
+        child
+    } else {
+        parent
+    };
+
+    AgentProfile {
+        model: child.model.clone().or_else(|| parent.model.clone()),
+        prompt: prompt_source.prompt.clone(),
+        prompt_ref: prompt_source.prompt_ref.clone(),
+        tools: tools::merge_tools(parent.tools.as_ref(), child.tools.as_ref()),
+        env: child.env.clone().or_else(|| parent.env.clone()),
+        temperature: child.temperature.or(parent.temperature),
+        top_p: child.top_p.or(parent.top_p),
+        reasoning_effort: child.reasoning_effort.or(parent.reasoning_effort),
+        thinking_budget: child.thinking_budget.or(parent.thinking_budget),
+        extends: None,
+        depends_on: child.depends_on.clone().or_else(|| parent.depends_on.clone()),
+        group: child.group.clone(),
+        locked: child.locked,
+        extra,
+        raw: child.raw.clone(),
+    }
+}
+
+/// Resolve the `extends` chain of one agent
+pub fn resolve_agent(
+    agents: &IndexMap<String, AgentProfile>,
+    name: &str,
+) -> Result<AgentProfile, ResolveError> {
+    let mut chain: Vec<&str> = vec![name];
+    let mut current = agents
+        .get(name)
+        .ok_or_else(|| ResolveError::NotFound(name.to_string()))?;
+
+    while let Some(parent_name) = current.extends.as_deref() {
+        if chain.contains(&parent_name) {
+            let mut cycle: Vec<String> = chain.iter().map(|n| n.to_string()).collect();
+            cycle.push(parent_name.to_string());
+            return Err(ResolveError::Cycle(cycle));
+        }
+        current = agents
+            .get(parent_name)
+            .ok_or_else(|| ResolveError::UnknownParent {
+                agent: chain.last().unwrap_or(&name).to_string(),
+                parent: parent_name.to_string(),
+            })?;
+        chain.push(parent_name);
+    }
+
+    // Merge from the root ancestor down to the requested agent
+    let mut resolved = AgentProfile::default();
+    for agent_name in chain.iter().rev() {
+        resolved = merge_agent(&resolved, &agents[*agent_name]);
+    }
+    Ok(resolved)
+}
+
+/// Resolve every agent of a profile, keeping the original order
+pub fn resolve_agents(
+    agents: &IndexMap<String, AgentProfile>,
+) -> Result<IndexMap<String, AgentProfile>, ResolveError> {
+    agents
+        .keys()
+        .map(|name| Ok((name.clone(), resolve_agent(agents, name)?)))
+        .collect()
+}
+
+/// Whether any agent uses `extends`
+pub fn has_inheritance(agents: &IndexMap<String, AgentProfile>) -> bool {
+    agents.values().any(|agent| agent.extends.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use serde_json::json;
+
+    #[test]
+    fn test_resolve_agent_chain() {
+        let agents = parse_agents(Some(&json!({
+            "base": { "model": "openai/gpt-4o", "prompt": "Be careful", "temperature": 0.2 },
+            "coder": { "extends": "base", "temperature": 0.5, "group": "coding" },
+            "reviewer": { "extends": "coder", "model": "openai/o3" }
+        })));
+
+        let reviewer = resolve_agent(&agents, "reviewer").unwrap();
+        assert_eq!(reviewer.model.as_deref(), Some("openai/o3"));
+        assert_eq!(reviewer.prompt.as_deref(), Some("Be careful"));
+        assert_eq!(reviewer.temperature, Some(0.5));
+        assert_eq!(reviewer.extends, None);
+        assert_eq!(reviewer.group, None);
+    }
+
+    #[test]
+    fn test_resolve_agent_errors() {
+        let agents = parse_agents(Some(&json!({
+            "a": { "extends": "b" },
+            "b": { "extends": "a" },
+            "c": { "extends": "ghost" }
+        })));
+
+        assert_eq!(
+            resolve_agent(&agents, "a"),
+            Err(ResolveError::Cycle(vec![
+                "a".to_string(),
+                "b".to_string(),
+                "a".to_string()
+            ]))
+        );
+        assert_eq!(
+            resolve_agent(&agents, "c"),
+            Err(ResolveError::UnknownParent {
+                agent: "c".to_string(),
+                parent: "ghost".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_child_disables_one_parent_tool() {
+        let agents = parse_agents(Some(&json!({
+            "base": { "tools": { "bash": true, "edit": true, "webfetch": true } },
+            "reader": { "extends": "base", "tools": { "edit": false, "grep": true } }
+        })));
+
+        let reader = resolve_agent(&agents, "reader").unwrap();
+        let tools = reader.tools.unwrap();
+        let entries: Vec<(&str, bool)> = tools.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+        assert_eq!(
+            entries,
+            vec![("bash", true), ("edit", false), ("webfetch", true), ("grep", true)]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Oh My OpenCode Integrity Module
+//!
+//! A checksum manifest of the global config and every config, for audited setups.
+//! Checksums are canonical fingerprints, so re-saving or reformatting a config does
+//! not change them; applying a config or touching timestamps does not either.
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::fingerprint;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Manifest key of the global config
+pub const GLOBAL_ENTRY: &str = "global";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityManifest {
+    pub created_at: String,
+    pub global: String,
+    /// Config id -> checksum, in config order
+    pub configs: IndexMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ViolationKind {
+    Modified,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityViolation {
+    /// Config id, or `global`
+    pub id: String,
+    pub kind: ViolationKind,
+}
+
+/// Checksum of a config: name and content, not the applied flag or timestamps
+pub fn config_checksum(config: &OhMyOpenCodeConfig) -> String {
+    fingerprint::fingerprint(&json!({
+        "name": config.name,
+        "agents": config.agents,
+        "other_fields": config.other_fields,
+    }))
+}
+
+/// Checksum of the global config, without its update timestamp
+pub fn global_checksum(global: &OhMyOpenCodeGlobalConfig) -> String {
+    let mut value = serde_json::to_value(global).unwrap_or_default();
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("updatedAt");
+    }
+    fingerprint::fingerprint(&value)
+}
+
+pub fn integrity_manifest(
+    global: &OhMyOpenCodeGlobalConfig,
+    configs: &[OhMyOpenCodeConfig],
+    created_at: &str,
+) -> IntegrityManifest {
+    IntegrityManifest {
+        created_at: created_at.to_string(),
+        global: global_checksum(global),
+        configs: configs
+            .iter()
+            .map(|config| (config.id.clone(), config_checksum(config)))
+            .collect(),
+    }
+}
+
+/// Differences between a trusted manifest and one taken now:
+/// changed checksums, then configs added, then configs removed
+pub fn verify_integrity(manifest: &IntegrityManifest, current: &IntegrityManifest) -> Vec<IntegrityViolation> {
+    let violation = |id: &str, kind| IntegrityViolation {
+        id: id.to_string(),
+        kind,
+    };
+    let mut violations = Vec::new();
+    if manifest.global != current.global {
+        violations.push(violation(GLOBAL_ENTRY, ViolationKind::Modified));
+    }
+    for (id, checksum) in &current.configs {
+        if manifest.configs.get(id).is_some_and(|trusted| trusted != checksum) {
+            violations.push(violation(id, ViolationKind::Modified));
+        }
+    }
+    for id in current.configs.keys().filter(|id| !manifest.configs.contains_key(*id)) {
+        violations.push(violation(id, ViolationKind::Added));
+    }
+    for id in manifest.configs.keys().filter(|id| !current.configs.contains_key(*id)) {
+        violations.push(violation(id, ViolationKind::Removed));
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+
+    fn config(id: &str, agents: serde_json::Value) -> OhMyOpenCodeConfig {
+        OhMyOpenCodeConfig {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            is_applied: false,
+            agents: Some(parse_agents(Some(&agents))),
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_modified_config_after_manifest() {
+        let global = OhMyOpenCodeGlobalConfig::default();
+        let mut configs = vec![
+            config("team", json!({ "oracle": { "model": "openai/o3", "temperature": 0.2 } })),
+            config("solo", json!({})),
+        ];
+        let manifest = integrity_manifest(&global, &configs, "2026-01-01T00:00:00+00:00");
+
+        // Reordered keys, a new timestamp and the applied flag are not tampering
+        configs[0].agents = Some(parse_agents(Some(&json!({ "oracle": { "temperature": 0.2, "model": "openai/o3" } }))));
+        configs[0].is_applied = true;
+        configs[0].updated_at = Some("2026-02-01T00:00:00+00:00".to_string());
+        let current = integrity_manifest(&global, &configs, "now");
+        assert!(verify_integrity(&manifest, &current).is_empty());
+
+        configs[0].agents = Some(parse_agents(Some(&json!({ "oracle": { "model": "evil/model", "temperature": 0.2 } }))));
+        configs.remove(1);
+        configs.push(config("new", json!({})));
+        let current = integrity_manifest(&global, &configs, "now");
+        assert_eq!(
+            verify_integrity(&manifest, &current),
+            vec![
+                IntegrityViolation { id: "team".to_string(), kind: ViolationKind::Modified },
+                IntegrityViolation { id: "new".to_string(), kind: ViolationKind::Added },
+                IntegrityViolation { id: "solo".to_string(), kind: ViolationKind::Removed },
+            ]
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Oh My OpenCode Key Case Module
+//!
+//! Output casing of exported records. Only the keys this app defines on config and
+//! global records are converted; `other_fields`, agent entries and any unknown key
+//! are written as stored.
+//!
+//! Bundles used to be written with camelCase record keys (`isApplied`, `disabledMcps`),
+//! exports are snake_case by default now. Imports read both casings, so old bundles keep
+//! importing; tooling that reads bundles itself has to ask for `KeyCase::CamelCase`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Known record keys whose snake_case and camelCase forms differ
+const RECORD_KEYS: &[(&str, &str)] = &[
+    ("is_applied", "isApplied"),
+    ("other_fields", "otherFields"),
+    ("created_at", "createdAt"),
+    ("updated_at", "updatedAt"),
+    ("schema_version", "schemaVersion"),
+    ("sisyphus_agent", "sisyphusAgent"),
+    ("disabled_agents", "disabledAgents"),
+    ("disabled_mcps", "disabledMcps"),
+    ("disabled_hooks", "disabledHooks"),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyCase {
+    /// What opencode reads
+    #[default]
+    SnakeCase,
+    /// For legacy tooling
+    CamelCase,
+}
+
+impl KeyCase {
+    /// `key` in this casing, `None` for keys outside the known record keys
+    pub(crate) fn convert(self, key: &str) -> Option<&'static str> {
+        RECORD_KEYS.iter().find_map(|(snake, camel)| {
+            if key != *snake && key != *camel {
+                return None;
+            }
+            Some(match self {
+                KeyCase::SnakeCase => *snake,
+                KeyCase::CamelCase => *camel,
+            })
+        })
+    }
+}
+
+/// Rename the known keys of a config or global record, keeping key order
+/// A key already present in the target casing wins over its converted twin
+pub fn convert_record_keys(record: &mut Value, case: KeyCase) {
+    let Some(obj) = record.as_object_mut() else {
+        return;
+    };
+    let mut converted = Map::new();
+    for (key, value) in std::mem::take(obj) {
+        match case.convert(&key) {
+            Some(target) if target != key => {
+                if !converted.contains_key(target) {
+                    converted.insert(target.to_string(), value);
+                }
+            }
+            _ => {
+                converted.insert(key, value);
+            }
+        }
+    }
+    *obj = converted;
+}
+
+/// Convert the global record and every config of a bundle
+pub fn convert_bundle_keys(bundle: &mut Value, case: KeyCase) {
+    if let Some(global) = bundle.get_mut("global") {
+        convert_record_keys(global, case);
+    }
+    if let Some(configs) = bundle.get_mut("configs").and_then(|v| v.as_array_mut()) {
+        configs.iter_mut().for_each(|config| convert_record_keys(config, case));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_convert_record_keys_known_only() {
+        let record = json!({
+            "id": "p1",
+            "is_applied": true,
+            "agents": { "oracle": { "prompt_ref": "base" } },
+            "other_fields": { "small_model": "openai/gpt-4o-mini", "customKey": 1 },
+            "updatedAt": "2026-01-01T00:00:00+00:00",
+            "team_note": "kept"
+        });
+
+        let mut camel = record.clone();
+        convert_record_keys(&mut camel, KeyCase::CamelCase);
+        assert_eq!(
+            camel,
+            json!({
+                "id": "p1",
+                "isApplied": true,
+                "agents": { "oracle": { "prompt_ref": "base" } },
+                "otherFields": { "small_model": "openai/gpt-4o-mini", "customKey": 1 },
+                "updatedAt": "2026-01-01T00:00:00+00:00",
+                "team_note": "kept"
+            })
+        );
+
+        let mut snake = camel;
+        convert_record_keys(&mut snake, KeyCase::SnakeCase);
+        assert_eq!(snake["other_fields"], record["other_fields"]);
+        assert_eq!(snake["updated_at"], json!("2026-01-01T00:00:00+00:00"));
+        assert_eq!(snake["team_note"], json!("kept"));
+        assert!(snake.get("isApplied").is_none());
+    }
+}
@@ -0,0 +1,119 @@
+//! Oh My OpenCode Launch Env Module
+//!
+//! The environment a process started for a profile runs with, lowest precedence first:
+//! 1. the base environment (process env + shell config exports, see `shell_env`)
+//! 2. the config-level env of the profile (`other_fields.config_env`)
+//! 3. the `env` of the MCP server, for a server process only
+//!
+//! `{env:NAME}` placeholders in server env values resolve against layers 1 and 2, unknown
+//! names are kept as written. Agents run inside opencode and have no env of their own.
+
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::config_env;
+use super::mcp;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use crate::coding::redact::{is_secret_key, REDACTED};
+
+/// Merged environment of opencode (`mcp_server` is `None`) or of one of its MCP servers
+/// An unknown server gets the opencode environment
+pub fn resolved_launch_env(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    base_env: &HashMap<String, String>,
+    mcp_server: Option<&str>,
+) -> HashMap<String, String> {
+    let mut env = base_env.clone();
+    env.extend(config_env::config_env(profile));
+
+    let Some(server) = mcp_server else {
+        return env;
+    };
+    let rendered = render_opencode_json(global, profile);
+    let server_env = mcp::mcp_servers(Some(&rendered))
+        .and_then(|servers| servers.get(server))
+        .and_then(|server| server.get("env"))
+        .and_then(|env| env.as_object());
+    let Some(server_env) = server_env else {
+        return env;
+    };
+
+    let layered: IndexMap<String, String> = env.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    for (name, value) in server_env {
+        let mut value = match value {
+            Value::String(_) => value.clone(),
+            Value::Number(_) | Value::Bool(_) => Value::String(value.to_string()),
+            _ => continue,
+        };
+        config_env::fold_env(&mut value, &layered);
+        if let Value::String(value) = value {
+            env.insert(name.clone(), value);
+        }
+    }
+    env
+}
+
+/// Copy of an environment for display, values of secret names replaced
+pub fn masked_env(env: &HashMap<String, String>) -> HashMap<String, String> {
+    env.iter()
+        .map(|(name, value)| {
+            let value = if is_secret_key(name) && !value.is_empty() {
+                REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (name.clone(), value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_launch_env_precedence() {
+        let global = OhMyOpenCodeGlobalConfig::default();
+        let profile = OhMyOpenCodeConfig {
+            id: "p1".to_string(),
+            name: "Profile".to_string(),
+            is_applied: false,
+            agents: None,
+            other_fields: Some(json!({
+                "config_env": { "REGION": "eu", "LOG_LEVEL": "info" },
+                "mcp": {
+                    "github": {
+                        "type": "local",
+                        "env": { "LOG_LEVEL": "debug", "GITHUB_TOKEN": "{env:GH_TOKEN}", "ENDPOINT": "{env:REGION}.api" }
+                    }
+                }
+            })),
+            created_at: None,
+            updated_at: None,
+        };
+        let base = HashMap::from([
+            ("REGION".to_string(), "us".to_string()),
+            ("LOG_LEVEL".to_string(), "warn".to_string()),
+            ("GH_TOKEN".to_string(), "ghp_x".to_string()),
+        ]);
+
+        let opencode = resolved_launch_env(&global, &profile, &base, None);
+        assert_eq!(opencode["REGION"], "eu");
+        assert_eq!(opencode["LOG_LEVEL"], "info");
+        assert!(!opencode.contains_key("GITHUB_TOKEN"));
+
+        let server = resolved_launch_env(&global, &profile, &base, Some("github"));
+        assert_eq!(server["LOG_LEVEL"], "debug");
+        assert_eq!(server["ENDPOINT"], "eu.api");
+        assert_eq!(server["GITHUB_TOKEN"], "ghp_x");
+
+        let masked = masked_env(&server);
+        assert_eq!(masked["GITHUB_TOKEN"], REDACTED);
+        assert_eq!(masked["GH_TOKEN"], REDACTED);
+        assert_eq!(masked["REGION"], "eu");
+    }
+}
@@ -0,0 +1,252 @@
+//! Oh My OpenCode Markdown Agent Module
+//!
+//! Convert agents to and from opencode agent markdown files:
+//! YAML frontmatter for settings, the markdown body for the prompt.
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::agent::AgentProfile;
+use super::import::ImportError;
+use super::inheritance::{self, ResolveError};
+use super::types::OhMyOpenCodeConfig;
+
+const FRONTMATTER_DELIMITER: &str = "---";
+
+/// Agent fields that never go into the frontmatter: the prompt is the body, the rest is UI only
+const NON_FRONTMATTER_FIELDS: &[&str] = &["prompt", "group", "locked"];
+/// Written first, in this order, like opencode lists them
+const LEADING_FIELDS: &[&str] = &["description", "model", "tools"];
+
+/// Build the markdown file content of a single (already resolved) agent
+/// Every setting of the agent goes into the frontmatter, so nothing is lost on export
+pub fn agent_to_markdown(agent: &AgentProfile) -> String {
+    let fields = match agent.to_value() {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    };
+    let mut frontmatter = Map::new();
+    for key in LEADING_FIELDS {
+        if let Some(value) = fields.get(*key).filter(|v| !v.is_null()) {
+            frontmatter.insert(key.to_string(), value.clone());
+        }
+    }
+    for (key, value) in fields {
+        let skipped = NON_FRONTMATTER_FIELDS.contains(&key.as_str()) || LEADING_FIELDS.contains(&key.as_str());
+        if !skipped && !value.is_null() {
+            frontmatter.insert(key, value);
+        }
+    }
+
+    let yaml = if frontmatter.is_empty() {
+        String::new()
+    } else {
+        serde_norway::to_string(&Value::Object(frontmatter)).unwrap_or_default()
+    };
+
+    format!(
+        "{delim}\n{yaml}{delim}\n\n{body}\n",
+        delim = FRONTMATTER_DELIMITER,
+        yaml = yaml,
+        body = agent.prompt.as_deref().unwrap_or("").trim_end()
+    )
+}
+
+/// Export every agent of a profile as markdown, keyed by agent name
+/// `extends` is resolved first so each file is complete on its own
+pub fn export_agents_as_markdown(
+    config: &OhMyOpenCodeConfig,
+) -> Result<HashMap<String, String>, ResolveError> {
+    let agents = config.typed_agents();
+    let resolved = inheritance::resolve_agents(&agents)?;
+    Ok(resolved
+        .iter()
+        .map(|(name, agent)| (name.clone(), agent_to_markdown(agent)))
+        .collect())
+}
+
+/// Parse agent markdown into its optional frontmatter `name` and the agent
+/// Content without a leading `---` line is treated as a prompt-only agent
+pub fn parse_agent_markdown(content: &str) -> Result<(Option<String>, AgentProfile), ImportError> {
+    let content = content.trim_start_matches('\u{feff}');
+    let lines: Vec<&str> = content.lines().collect();
+
+    let (mut frontmatter, body) = if lines.first().map(|l| l.trim_end()) == Some(FRONTMATTER_DELIMITER)
+    {
+        let end = lines
+            .iter()
+            .skip(1)
+            .position(|l| l.trim_end() == FRONTMATTER_DELIMITER)
+            .map(|i| i + 1)
+            .ok_or_else(|| ImportError::Frontmatter {
+                line: 1,
+                message: "Frontmatter is not closed with '---'".to_string(),
+            })?;
+
+        let yaml = lines[1..end].join("\n");
+        // Frontmatter starts on the second line of the file
+        let to_file_line = |e: &serde_norway::Error| e.location().map(|l| l.line() + 1).unwrap_or(2);
+        let parsed: Value = if yaml.trim().is_empty() {
+            Value::Object(Map::new())
+        } else {
+            serde_norway::from_str(&yaml).map_err(|e| ImportError::Frontmatter {
+                line: to_file_line(&e),
+                message: e.to_string(),
+            })?
+        };
+        let Value::Object(map) = parsed else {
+            return Err(ImportError::Frontmatter {
+                line: 2,
+                message: "Frontmatter must be a mapping".to_string(),
+            });
+        };
+        (map, lines[end + 1..].join("\n"))
+    } else {
+        (Map::new(), content.to_string())
+    };
+
+    let name = match frontmatter.remove("name") {
+        Some(Value::String(name)) if !name.trim().is_empty() => Some(name.trim().to_string()),
+        _ => None,
+    };
+
+    let body = body.trim();
+    if !body.is_empty() {
+        frontmatter.insert("prompt".to_string(), Value::String(body.to_string()));
+    }
+
+    let agent = AgentProfile::from_value(&Value::Object(frontmatter)).unwrap_or_default();
+    Ok((name, agent))
+}
+
+/// Import a single opencode agent markdown, the agent name comes from the frontmatter `name`
+pub fn import_agent_markdown(content: &str) -> Result<(String, AgentProfile), ImportError> {
+    match parse_agent_markdown(content)? {
+        (Some(name), agent) => Ok((name, agent)),
+        (None, _) => Err(ImportError::MissingName),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use serde_json::json;
+
+    #[test]
+    fn test_export_agents_as_markdown() {
+        let config = OhMyOpenCodeConfig {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            is_applied: false,
+            agents: Some(parse_agents(Some(&json!({
+                "base": { "model": "openai/gpt-4o", "tools": { "write": false, "bash": true } },
+                "reviewer": {
+                    "extends": "base",
+                    "description": "Reviews code",
+                    "temperature": 0.1,
+                    "group": "review",
+                    "prompt": "You are a strict code reviewer.\n"
+                }
+            })))),
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        };
+
+        let files = export_agents_as_markdown(&config).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            files["reviewer"],
+            "---\n\
+             description: Reviews code\n\
+             model: openai/gpt-4o\n\
+             tools:\n  write: false\n  bash: true\n\
+             temperature: 0.1\n\
+             ---\n\
+             \n\
+             You are a strict code reviewer.\n"
+        );
+    }
+
+    #[test]
+    fn test_agent_to_markdown_keeps_typed_fields() {
+        let agent = AgentProfile::from_value(&json!({
+            "model": "openai/o3",
+            "top_p": 0.9,
+            "env": { "REGION": "eu" },
+            "reasoning_effort": "high",
+            "thinking_budget": 4096,
+            "depends_on": ["explore"],
+            "prompt_ref": "reviewer",
+            "group": "review",
+            "locked": true,
+            "prompt": "Think."
+        }))
+        .unwrap();
+
+        let markdown = agent_to_markdown(&agent);
+        assert!(!markdown.contains("group:") && !markdown.contains("locked:"), "{markdown}");
+        let reparsed = parse_agent_markdown(&markdown).unwrap().1;
+        assert_eq!(
+            reparsed,
+            AgentProfile {
+                group: None,
+                locked: false,
+                ..agent
+            }
+        );
+    }
+
+    #[test]
+    fn test_import_agent_markdown() {
+        let content = "---\n\
+            name: review\n\
+            description: Reviews code for quality and best practices\n\
+            mode: subagent\n\
+            model: anthropic/claude-sonnet-4-20250514\n\
+            temperature: 0.1\n\
+            tools:\n  write: false\n  edit: false\n  bash: false\n\
+            ---\n\
+            \n\
+            You are in code review mode. Focus on:\n\
+            \n\
+            - Code quality and best practices\n\
+            - Potential bugs and edge cases\n";
+
+        let (name, agent) = import_agent_markdown(content).unwrap();
+        assert_eq!(name, "review");
+        assert_eq!(agent.model.as_deref(), Some("anthropic/claude-sonnet-4-20250514"));
+        assert_eq!(agent.tools.as_ref().unwrap().get("write"), Some(&false));
+        assert!(agent.prompt.as_deref().unwrap().starts_with("You are in code review mode."));
+        assert!(agent.prompt.as_deref().unwrap().ends_with("edge cases"));
+        assert_eq!(agent.extra["mode"], json!("subagent"));
+        assert_eq!(agent.temperature, Some(0.1));
+        assert!(!agent.extra.contains_key("name"));
+
+        // Round-trip through the exporter
+        let reparsed = parse_agent_markdown(&agent_to_markdown(&agent)).unwrap().1;
+        assert_eq!(reparsed, agent);
+    }
+
+    #[test]
+    fn test_import_agent_markdown_errors() {
+        let malformed = "---\nname: review\nmodel: [unclosed\n---\nBody\n";
+        match import_agent_markdown(malformed) {
+            Err(ImportError::Frontmatter { line, .. }) => assert!(line >= 3),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert_eq!(
+            import_agent_markdown("---\nmodel: openai/o3\n"),
+            Err(ImportError::Frontmatter {
+                line: 1,
+                message: "Frontmatter is not closed with '---'".to_string()
+            })
+        );
+        assert_eq!(
+            import_agent_markdown("---\nmodel: openai/o3\n---\nBody\n"),
+            Err(ImportError::MissingName)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Oh My OpenCode Agent/Provider Matrix Module
+//!
+//! Which agents of a config would work on which provider, for teams choosing one.
+//! An agent needs its model (by model id, the `provider/` prefix is ignored) to be
+//! listed by the provider and allowed by its allow/deny lists, and the model to have
+//! the capabilities the agent uses. Agents without a model need any such model.
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::coding::open_code::model_metadata::ModelCapability;
+use crate::coding::open_code::models_api::{filter_models, FetchedModel};
+
+use super::capabilities::{enables_tools, uses_vision};
+use super::inheritance;
+use super::types::OhMyOpenCodeConfig;
+
+/// A provider column with its allow/deny lists
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixProvider {
+    pub id: String,
+    #[serde(default)]
+    pub allowed_models: Vec<String>,
+    #[serde(default)]
+    pub denied_models: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum MatrixCell {
+    Supported,
+    Unsupported { reason: String },
+    /// The provider's model list or the model's capabilities are not known
+    Unknown,
+}
+
+/// Rows are agents, columns providers, both in input order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentProviderMatrix {
+    pub agents: Vec<String>,
+    pub providers: Vec<String>,
+    pub cells: Vec<Vec<MatrixCell>>,
+}
+
+/// Whether a model has every required capability, `None` when its capabilities are unknown
+fn has_capabilities(model: &FetchedModel, required: &[(ModelCapability, &str)]) -> Option<Result<(), String>> {
+    let capabilities = model.metadata.as_ref()?.capabilities.as_ref()?;
+    Some(
+        match required.iter().find(|(capability, _)| !capabilities.contains(capability)) {
+            Some((_, label)) => Err(format!("model '{}' does not support {}", model.id, label)),
+            None => Ok(()),
+        },
+    )
+}
+
+fn cell(
+    model_id: Option<&str>,
+    required: &[(ModelCapability, &str)],
+    provider: &MatrixProvider,
+    listed: Option<&Vec<FetchedModel>>,
+) -> MatrixCell {
+    let Some(listed) = listed else {
+        return MatrixCell::Unknown;
+    };
+    let available = filter_models(listed.clone(), &provider.allowed_models, &provider.denied_models);
+
+    let Some(model_id) = model_id else {
+        let results: Vec<Option<Result<(), String>>> =
+            available.iter().map(|model| has_capabilities(model, required)).collect();
+        return if results.iter().any(|r| matches!(r, Some(Ok(())))) {
+            MatrixCell::Supported
+        } else if results.iter().any(|r| r.is_none()) {
+            MatrixCell::Unknown
+        } else {
+            MatrixCell::Unsupported {
+                reason: "no allowed model has the required capabilities".to_string(),
+            }
+        };
+    };
+
+    if provider.denied_models.iter().any(|id| id == model_id) {
+        return MatrixCell::Unsupported {
+            reason: format!("model '{}' is denied", model_id),
+        };
+    }
+    let Some(model) = available.iter().find(|model| model.id == model_id) else {
+        let reason = if listed.iter().any(|model| model.id == model_id) {
+            format!("model '{}' is not in the allow list", model_id)
+        } else {
+            format!("model '{}' is not offered", model_id)
+        };
+        return MatrixCell::Unsupported { reason };
+    };
+    match has_capabilities(model, required) {
+        Some(Ok(())) => MatrixCell::Supported,
+        Some(Err(reason)) => MatrixCell::Unsupported { reason },
+        None if required.is_empty() => MatrixCell::Supported,
+        None => MatrixCell::Unknown,
+    }
+}
+
+/// Matrix of every agent of `config` (with `extends` resolved) against every provider
+/// `models` holds the fetched model list of each provider id; a provider without one is `Unknown`
+pub fn agent_provider_matrix(
+    config: &OhMyOpenCodeConfig,
+    providers: &[MatrixProvider],
+    models: &IndexMap<String, Vec<FetchedModel>>,
+) -> AgentProviderMatrix {
+    let parsed = config.typed_agents();
+    let agents = inheritance::resolve_agents(&parsed).unwrap_or(parsed);
+
+    let cells = agents
+        .iter()
+        .map(|(name, agent)| {
+            let mut required = Vec::new();
+            if enables_tools(agent) {
+                required.push((ModelCapability::Tool, "tools"));
+            }
+            if uses_vision(name, agent) {
+                required.push((ModelCapability::Vision, "images"));
+            }
+            let model_id = agent
+                .model
+                .as_deref()
+                .map(|model| model.split_once('/').map(|(_, id)| id).unwrap_or(model));
+            providers
+                .iter()
+                .map(|provider| cell(model_id, &required, provider, models.get(&provider.id)))
+                .collect()
+        })
+        .collect();
+
+    AgentProviderMatrix {
+        agents: agents.keys().cloned().collect(),
+        providers: providers.iter().map(|p| p.id.clone()).collect(),
+        cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use crate::coding::open_code::model_metadata::ModelMetadata;
+    use serde_json::json;
+
+    fn model(id: &str, capabilities: Option<Vec<ModelCapability>>) -> FetchedModel {
+        FetchedModel {
+            id: id.to_string(),
+            name: None,
+            owned_by: None,
+            created: None,
+            metadata: Some(ModelMetadata {
+                capabilities,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_agent_provider_matrix() {
+        let config = OhMyOpenCodeConfig {
+            id: "work".to_string(),
+            name: "Work".to_string(),
+            is_applied: false,
+            agents: Some(parse_agents(Some(&json!({
+                "coder": { "model": "openai/gpt-4o", "tools": { "bash": true } },
+                "writer": { "model": "openai/o3" }
+            })))),
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let providers = vec![
+            MatrixProvider {
+                id: "openai".to_string(),
+                ..Default::default()
+            },
+            MatrixProvider {
+                id: "proxy".to_string(),
+                denied_models: vec!["o3".to_string()],
+                ..Default::default()
+            },
+        ];
+        let models = IndexMap::from([
+            (
+                "openai".to_string(),
+                vec![model("gpt-4o", Some(vec![ModelCapability::Tool])), model("o3", None)],
+            ),
+            ("proxy".to_string(), vec![model("gpt-4o", Some(Vec::new())), model("o3", None)]),
+        ]);
+
+        let matrix = agent_provider_matrix(&config, &providers, &models);
+        assert_eq!(matrix.agents, vec!["coder", "writer"]);
+        assert_eq!(matrix.providers, vec!["openai", "proxy"]);
+        assert_eq!(
+            matrix.cells,
+            vec![
+                vec![
+                    MatrixCell::Supported,
+                    MatrixCell::Unsupported {
+                        reason: "model 'gpt-4o' does not support tools".to_string()
+                    },
+                ],
+                vec![
+                    MatrixCell::Supported,
+                    MatrixCell::Unsupported {
+                        reason: "model 'o3' is denied".to_string()
+                    },
+                ],
+            ]
+        );
+
+        let unknown = agent_provider_matrix(&config, &providers[..1], &IndexMap::new());
+        assert_eq!(unknown.cells, vec![vec![MatrixCell::Unknown], vec![MatrixCell::Unknown]]);
+    }
+}
@@ -0,0 +1,357 @@
+//! Oh My OpenCode MCP Module
+//!
+//! MCP servers are defined in the `mcp` map of `other_fields` (global or profile level).
+//! Agents reference an MCP through their `tools` keys: `{mcp}_{tool}` or the `{mcp}*` wildcard.
+//! An MCP is only scoped to agents when the top-level `tools` map turns it off (`{mcp}*: false`),
+//! otherwise every agent can use it.
+
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::agent;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const UNUSED_MCP_RULE: &str = "unused-mcp";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RenameError {
+    #[error("Name must not be empty")]
+    EmptyName,
+    #[error("'{0}' not found")]
+    NotFound(String),
+    #[error("'{0}' already exists")]
+    AlreadyExists(String),
+}
+
+/// The `mcp` map inside an `other_fields` value
+pub fn mcp_servers(other_fields: Option<&Value>) -> Option<&Map<String, Value>> {
+    other_fields?.get("mcp")?.as_object()
+}
+
+fn mcp_servers_mut(other_fields: Option<&mut Value>) -> Option<&mut Map<String, Value>> {
+    other_fields?.get_mut("mcp")?.as_object_mut()
+}
+
+/// Whether `key` is `{mcp}`, `{mcp}_...` or `{mcp}*`
+fn has_mcp_prefix(key: &str, mcp: &str) -> bool {
+    match key.strip_prefix(mcp) {
+        Some(rest) => rest.is_empty() || rest.starts_with('_') || rest.starts_with('*'),
+        None => false,
+    }
+}
+
+/// Whether a `tools` key of an agent refers to the given MCP server
+/// `mcps` are all known server names, a key belongs to the longest one it matches:
+/// `grep_app_search` is a tool of `grep_app`, not of `grep`
+pub fn tool_key_references_mcp<S: AsRef<str>>(key: &str, mcp: &str, mcps: &[S]) -> bool {
+    has_mcp_prefix(key, mcp)
+        && !mcps
+            .iter()
+            .map(AsRef::<str>::as_ref)
+            .any(|other| other.len() > mcp.len() && has_mcp_prefix(key, other))
+}
+
+/// Rename a key of a map in place, keeping its position
+fn rename_map_key(map: &mut Map<String, Value>, old: &str, new: &str) -> bool {
+    if !map.contains_key(old) {
+        return false;
+    }
+    let entries = std::mem::take(map);
+    for (key, value) in entries {
+        if key == old {
+            map.insert(new.to_string(), value);
+        } else {
+            map.insert(key, value);
+        }
+    }
+    true
+}
+
+/// Rename the MCP prefix of an agent's tools keys
+fn rename_tool_refs(tools: &mut IndexMap<String, bool>, old: &str, new: &str, mcps: &[String]) -> usize {
+    let mut changed = 0;
+    let entries = std::mem::take(tools);
+    for (key, enabled) in entries {
+        if tool_key_references_mcp(&key, old, mcps) {
+            tools.insert(format!("{}{}", new, &key[old.len()..]), enabled);
+            changed += 1;
+        } else {
+            tools.insert(key, enabled);
+        }
+    }
+    changed
+}
+
+/// Rename an MCP server everywhere: the `mcp` map keys, `disabled_mcps` and agent tool references
+/// Returns the number of updated entries, nothing is changed on error
+pub fn rename_mcp(
+    global: &mut OhMyOpenCodeGlobalConfig,
+    profiles: &mut [OhMyOpenCodeConfig],
+    old: &str,
+    new: &str,
+) -> Result<usize, RenameError> {
+    let new = new.trim();
+    if new.is_empty() {
+        return Err(RenameError::EmptyName);
+    }
+    if old == new {
+        return Ok(0);
+    }
+
+    let mcp_maps = std::iter::once(mcp_servers(global.other_fields.as_ref()))
+        .chain(profiles.iter().map(|p| mcp_servers(p.other_fields.as_ref())))
+        .flatten();
+    let mut exists = false;
+    let mut known: Vec<String> = Vec::new();
+    for servers in mcp_maps {
+        if servers.contains_key(new) {
+            return Err(RenameError::AlreadyExists(new.to_string()));
+        }
+        exists |= servers.contains_key(old);
+        known.extend(servers.keys().cloned());
+    }
+    let disabled = global.disabled_mcps.as_deref().unwrap_or_default();
+    known.extend(disabled.iter().cloned());
+    if disabled.iter().any(|d| d == new) {
+        return Err(RenameError::AlreadyExists(new.to_string()));
+    }
+    if !exists && !disabled.iter().any(|d| d == old) {
+        return Err(RenameError::NotFound(old.to_string()));
+    }
+
+    let mut updated = 0;
+
+    if let Some(servers) = mcp_servers_mut(global.other_fields.as_mut()) {
+        updated += rename_map_key(servers, old, new) as usize;
+    }
+    if let Some(disabled) = global.disabled_mcps.as_mut() {
+        for name in disabled.iter_mut().filter(|name| name.as_str() == old) {
+            *name = new.to_string();
+            updated += 1;
+        }
+    }
+
+    for profile in profiles.iter_mut() {
+        if let Some(servers) = mcp_servers_mut(profile.other_fields.as_mut()) {
+            updated += rename_map_key(servers, old, new) as usize;
+        }
+        if profile.agents.is_none() {
+            continue;
+        }
+        updated += agent::with_agents_mut(profile, |agents| {
+            agents
+                .values_mut()
+                .filter_map(|agent| agent.tools.as_mut())
+                .map(|tools| rename_tool_refs(tools, old, new, &known))
+                .sum::<usize>()
+        });
+    }
+
+    Ok(updated)
+}
+
+/// String entries of a list field of the rendered config
+pub fn string_list<'a>(config: &'a Value, key: &str) -> Vec<&'a str> {
+    config
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Whether a `tools` map turns on at least one tool of the MCP, `mcps` are all known server names
+pub fn tools_enable_mcp<S: AsRef<str>>(tools: Option<&Value>, mcp: &str, mcps: &[S]) -> bool {
+    tools
+        .and_then(|t| t.as_object())
+        .map(|tools| {
+            tools
+                .iter()
+                .any(|(key, enabled)| tool_key_references_mcp(key, mcp, mcps) && enabled.as_bool() == Some(true))
+        })
+        .unwrap_or(false)
+}
+
+/// Whether the top-level `tools` map turns the MCP off for agents that do not enable it
+fn scoped_to_agents<S: AsRef<str>>(tools: Option<&Value>, mcp: &str, mcps: &[S]) -> bool {
+    tools
+        .and_then(|t| t.as_object())
+        .map(|tools| {
+            tools
+                .iter()
+                .any(|(key, enabled)| tool_key_references_mcp(key, mcp, mcps) && enabled.as_bool() == Some(false))
+        })
+        .unwrap_or(false)
+}
+
+/// Enabled MCP servers of the rendered config that no active agent uses
+/// MCPs available to all agents (not turned off in the top-level `tools`) are never reported
+pub fn find_unused_mcps(global: &OhMyOpenCodeGlobalConfig, profile: &OhMyOpenCodeConfig) -> Vec<String> {
+    let config = render_opencode_json(global, profile);
+    let Some(servers) = config.get("mcp").and_then(|v| v.as_object()) else {
+        return Vec::new();
+    };
+    let names: Vec<&String> = servers.keys().collect();
+    let disabled_mcps = string_list(&config, "disabled_mcps");
+    let disabled_agents = string_list(&config, "disabled_agents");
+    let top_level_tools = config.get("tools");
+
+    let active_agents: Vec<&Value> = config
+        .get("agents")
+        .and_then(|v| v.as_object())
+        .into_iter()
+        .flatten()
+        .filter(|(name, agent)| {
+            !disabled_agents.contains(&name.as_str())
+                && agent.get("disable").and_then(|d| d.as_bool()) != Some(true)
+        })
+        .map(|(_, agent)| agent)
+        .collect();
+
+    servers
+        .iter()
+        .filter(|(name, server)| {
+            !disabled_mcps.contains(&name.as_str())
+                && server.get("enabled").and_then(|e| e.as_bool()) != Some(false)
+        })
+        .map(|(name, _)| name)
+        .filter(|name| scoped_to_agents(top_level_tools, name, &names))
+        .filter(|name| !active_agents.iter().any(|agent| tools_enable_mcp(agent.get("tools"), name, &names)))
+        .cloned()
+        .collect()
+}
+
+/// Validation rule: hint at enabled MCP servers no agent uses, they can be disabled
+pub fn validate_unused_mcps(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    find_unused_mcps(global, profile)
+        .into_iter()
+        .map(|name| {
+            ValidationIssue::new(
+                UNUSED_MCP_RULE,
+                Severity::Info,
+                format!("mcp.{}", name),
+                format!(
+                    "MCP server '{}' is not used by any enabled agent, disable it to save startup time",
+                    name
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Add MCP servers to the global `disabled_mcps`, returns the number newly disabled
+pub fn disable_mcps(global: &mut OhMyOpenCodeGlobalConfig, names: &[String]) -> usize {
+    let disabled = global.disabled_mcps.get_or_insert_with(Vec::new);
+    let mut added = 0;
+    for name in names {
+        if !disabled.contains(name) {
+            disabled.push(name.clone());
+            added += 1;
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rename_mcp() {
+        let mut global = OhMyOpenCodeGlobalConfig {
+            disabled_mcps: Some(vec!["context7".to_string(), "websearch".to_string()]),
+            other_fields: Some(json!({
+                "mcp": { "websearch": { "type": "remote" }, "github": { "type": "local" } }
+            })),
+            ..Default::default()
+        };
+        let mut profiles = vec![OhMyOpenCodeConfig::test_with_agents(json!({
+            "librarian": { "tools": { "websearch_search": true, "websearch*": false, "github_pr": true } }
+        }))];
+
+        let updated = rename_mcp(&mut global, &mut profiles, "websearch", "exa").unwrap();
+        assert_eq!(updated, 4);
+
+        let servers = mcp_servers(global.other_fields.as_ref()).unwrap();
+        let keys: Vec<&String> = servers.keys().collect();
+        assert_eq!(keys, vec!["exa", "github"]);
+        assert_eq!(
+            global.disabled_mcps,
+            Some(vec!["context7".to_string(), "exa".to_string()])
+        );
+
+        let agents = profiles[0].typed_agents();
+        let tools = agents["librarian"].tools.as_ref().unwrap();
+        let tool_keys: Vec<&String> = tools.keys().collect();
+        assert_eq!(tool_keys, vec!["exa_search", "exa*", "github_pr"]);
+    }
+
+    #[test]
+    fn test_tool_keys_belong_to_longest_mcp() {
+        let mcps = ["grep", "grep_app"];
+        assert!(tool_key_references_mcp("grep_app_search", "grep_app", &mcps));
+        assert!(!tool_key_references_mcp("grep_app_search", "grep", &mcps));
+        assert!(tool_key_references_mcp("grep_search", "grep", &mcps));
+        assert!(!tool_key_references_mcp("grepper", "grep", &mcps));
+
+        let mut global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({ "mcp": { "grep": {}, "grep_app": {} } })),
+            ..Default::default()
+        };
+        let mut profiles = vec![OhMyOpenCodeConfig::test_with_agents(json!({
+            "explore": { "tools": { "grep_search": true, "grep_app_search": true } }
+        }))];
+        rename_mcp(&mut global, &mut profiles, "grep", "rg").unwrap();
+        let agents = profiles[0].typed_agents();
+        let tool_keys: Vec<&String> = agents["explore"].tools.as_ref().unwrap().keys().collect();
+        assert_eq!(tool_keys, vec!["rg_search", "grep_app_search"]);
+    }
+
+    #[test]
+    fn test_rename_mcp_errors() {
+        let mut global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({ "mcp": { "a": {}, "b": {} } })),
+            ..Default::default()
+        };
+        assert_eq!(
+            rename_mcp(&mut global, &mut [], "a", "b"),
+            Err(RenameError::AlreadyExists("b".to_string()))
+        );
+        assert_eq!(
+            rename_mcp(&mut global, &mut [], "ghost", "c"),
+            Err(RenameError::NotFound("ghost".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_find_unused_mcps() {
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({
+                "mcp": { "github": { "type": "local" }, "websearch": { "type": "remote" } },
+                "tools": { "github*": false, "websearch*": false }
+            })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "librarian": { "tools": { "github_search": true } },
+            "explore": { "tools": { "websearch*": false } }
+        }));
+        assert_eq!(find_unused_mcps(&global, &profile), vec!["websearch".to_string()]);
+        assert_eq!(validate_unused_mcps(&global, &profile)[0].severity, Severity::Info);
+
+        // Available to every agent when not turned off at the top level
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({
+                "mcp": { "github": { "type": "local" }, "websearch": { "type": "remote" } }
+            })),
+            ..Default::default()
+        };
+        assert!(find_unused_mcps(&global, &profile).is_empty());
+    }
+}
@@ -0,0 +1,403 @@
+//! Oh My OpenCode MCP Probe Module
+//!
+//! Ask MCP servers for their tools: local servers are started with their launch env and
+//! spoken to over stdio, remote servers over streamable HTTP. Only `initialize` and
+//! `tools/list` are sent, the server is stopped right after.
+//!
+//! Used to find tool names exposed by more than one enabled server, which opencode
+//! resolves ambiguously.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use super::launch_env;
+use super::mcp;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use crate::coding::concurrency::run_bounded;
+
+/// Servers probed at the same time
+const PROBE_CONCURRENCY: usize = 4;
+/// Time a server gets to answer both requests
+const PROBE_TIMEOUT_SECS: u64 = 20;
+/// Protocol version announced in `initialize`
+const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
+/// `tools/list` pages followed at most
+const MAX_TOOL_PAGES: usize = 20;
+
+/// A tool name exposed by more than one server
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCollision {
+    pub tool: String,
+    /// Servers exposing it, in config order
+    pub servers: Vec<String>,
+}
+
+fn rpc_request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn initialize_request() -> Value {
+    rpc_request(
+        1,
+        "initialize",
+        json!({
+            "protocolVersion": MCP_PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "ai-toolbox", "version": env!("CARGO_PKG_VERSION") }
+        }),
+    )
+}
+
+fn initialized_notification() -> Value {
+    json!({ "jsonrpc": "2.0", "method": "notifications/initialized" })
+}
+
+fn tools_list_request(id: u64, cursor: Option<&str>) -> Value {
+    let params = match cursor {
+        Some(cursor) => json!({ "cursor": cursor }),
+        None => json!({}),
+    };
+    rpc_request(id, "tools/list", params)
+}
+
+/// The `result` of a JSON-RPC response, its `error` as `Err`
+fn rpc_result(response: Value) -> Result<Value, String> {
+    if let Some(error) = response.get("error") {
+        let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error");
+        return Err(format!("MCP error: {}", message));
+    }
+    response.get("result").cloned().ok_or_else(|| "MCP response has no result".to_string())
+}
+
+/// Tool names of one `tools/list` result and the cursor of the next page
+fn parse_tools_page(result: &Value) -> (Vec<String>, Option<String>) {
+    let tools = result
+        .get("tools")
+        .and_then(|t| t.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| Some(tool.get("name")?.as_str()?.to_string()))
+        .collect();
+    let next = result.get("nextCursor").and_then(|c| c.as_str()).map(str::to_string);
+    (tools, next)
+}
+
+/// Connection to a started local server or a remote one
+enum Session {
+    Stdio {
+        // Kept so the server is killed when the session is dropped
+        _child: tokio::process::Child,
+        stdin: tokio::process::ChildStdin,
+        stdout: tokio::io::Lines<BufReader<tokio::process::ChildStdout>>,
+    },
+    Http {
+        client: reqwest::Client,
+        url: String,
+        headers: Vec<(String, String)>,
+        session_id: Option<String>,
+    },
+}
+
+impl Session {
+    /// Start a local server from its `command`
+    fn start_local(server: &Value, env: &HashMap<String, String>) -> Result<Self, String> {
+        let parts = command_parts(server).ok_or("Local MCP server has no command")?;
+        let mut child = tokio::process::Command::new(&parts[0])
+            .args(&parts[1..])
+            .env_clear()
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to start '{}': {}", parts[0], e))?;
+        let stdin = child.stdin.take().ok_or("Failed to open stdin")?;
+        let stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+        Ok(Session::Stdio {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+        })
+    }
+
+    fn remote(client: reqwest::Client, server: &Value) -> Result<Self, String> {
+        let url = server.get("url").and_then(|u| u.as_str()).ok_or("Remote MCP server has no url")?;
+        let headers = server
+            .get("headers")
+            .and_then(|h| h.as_object())
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, value)| Some((name.clone(), value.as_str()?.to_string())))
+            .collect();
+        Ok(Session::Http {
+            client,
+            url: url.to_string(),
+            headers,
+            session_id: None,
+        })
+    }
+
+    /// Send a message, wait for the response with the same id when it is a request
+    async fn send(&mut self, message: Value) -> Result<Option<Value>, String> {
+        let id = message.get("id").cloned();
+        match self {
+            Session::Stdio { stdin, stdout, .. } => {
+                // Messages are newline-delimited JSON, other output is skipped
+                let line = format!("{}\n", message);
+                stdin.write_all(line.as_bytes()).await.map_err(|e| e.to_string())?;
+                stdin.flush().await.map_err(|e| e.to_string())?;
+                let Some(id) = id else {
+                    return Ok(None);
+                };
+                while let Some(line) = stdout.next_line().await.map_err(|e| e.to_string())? {
+                    match serde_json::from_str::<Value>(&line) {
+                        Ok(response) if response.get("id") == Some(&id) => return Ok(Some(response)),
+                        _ => continue,
+                    }
+                }
+                Err("MCP server exited before answering".to_string())
+            }
+            Session::Http {
+                client,
+                url,
+                headers,
+                session_id,
+            } => {
+                let mut request = client
+                    .post(url.as_str())
+                    .header("Accept", "application/json, text/event-stream")
+                    .json(&message);
+                for (name, value) in headers.iter() {
+                    request = request.header(name.as_str(), value.as_str());
+                }
+                if let Some(session) = session_id.as_deref() {
+                    request = request.header("Mcp-Session-Id", session);
+                }
+                let response = request.send().await.map_err(|e| e.to_string())?;
+                if !response.status().is_success() {
+                    return Err(format!("HTTP {}", response.status()));
+                }
+                if let Some(session) = response.headers().get("Mcp-Session-Id").and_then(|v| v.to_str().ok()) {
+                    *session_id = Some(session.to_string());
+                }
+                let Some(id) = id else {
+                    return Ok(None);
+                };
+                let body = response.text().await.map_err(|e| e.to_string())?;
+                parse_http_response(&body, &id).map(Some)
+            }
+        }
+    }
+
+    async fn request(&mut self, message: Value) -> Result<Value, String> {
+        let response = self.send(message).await?.ok_or("No response")?;
+        rpc_result(response)
+    }
+}
+
+/// The response with `id` from a JSON body or a `text/event-stream` body
+fn parse_http_response(body: &str, id: &Value) -> Result<Value, String> {
+    if let Ok(response) = serde_json::from_str::<Value>(body) {
+        return Ok(response);
+    }
+    body.lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| serde_json::from_str::<Value>(data.trim()).ok())
+        .find(|message| message.get("id") == Some(id))
+        .ok_or_else(|| "No MCP response in HTTP body".to_string())
+}
+
+/// `command` of a local server as program and arguments
+fn command_parts(server: &Value) -> Option<Vec<String>> {
+    let parts: Vec<String> = match server.get("command")? {
+        Value::Array(parts) => parts.iter().filter_map(|p| p.as_str().map(str::to_string)).collect(),
+        Value::String(command) => command.split_whitespace().map(str::to_string).collect(),
+        _ => return None,
+    };
+    (!parts.is_empty()).then_some(parts)
+}
+
+async fn list_tools(mut session: Session) -> Result<Vec<String>, String> {
+    session.request(initialize_request()).await?;
+    session.send(initialized_notification()).await?;
+
+    let mut tools = Vec::new();
+    let mut cursor: Option<String> = None;
+    for page in 0..MAX_TOOL_PAGES {
+        let result = session.request(tools_list_request(2 + page as u64, cursor.as_deref())).await?;
+        let (page_tools, next) = parse_tools_page(&result);
+        tools.extend(page_tools);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(tools)
+}
+
+/// Tool names of an MCP server definition (`type` `local` or `remote`)
+/// `env` is the environment a local server is started with
+pub async fn test_mcp_tools(
+    client: &reqwest::Client,
+    server: &Value,
+    env: &HashMap<String, String>,
+) -> Result<Vec<String>, String> {
+    let session = match server.get("type").and_then(|t| t.as_str()) {
+        Some("remote") => Session::remote(client.clone(), server)?,
+        _ => Session::start_local(server, env)?,
+    };
+    tokio::time::timeout(Duration::from_secs(PROBE_TIMEOUT_SECS), list_tools(session))
+        .await
+        .map_err(|_| format!("No answer within {}s", PROBE_TIMEOUT_SECS))?
+}
+
+/// Enabled MCP servers of the rendered config, in config order
+pub fn enabled_mcp_servers(global: &OhMyOpenCodeGlobalConfig, profile: &OhMyOpenCodeConfig) -> Vec<(String, Value)> {
+    let config = render_opencode_json(global, profile);
+    let disabled = mcp::string_list(&config, "disabled_mcps");
+    mcp::mcp_servers(Some(&config))
+        .into_iter()
+        .flatten()
+        .filter(|(name, server)| {
+            !disabled.contains(&name.as_str()) && server.get("enabled").and_then(|e| e.as_bool()) != Some(false)
+        })
+        .map(|(name, server)| (name.clone(), server.clone()))
+        .collect()
+}
+
+/// Tool names listed by more than one server, in order of first appearance
+/// `tools` holds the listing of every server in config order
+pub fn tool_collisions(tools: &[(String, Vec<String>)]) -> Vec<ToolCollision> {
+    let mut providers: indexmap::IndexMap<&str, Vec<String>> = indexmap::IndexMap::new();
+    for (server, names) in tools {
+        for name in names {
+            let servers = providers.entry(name.as_str()).or_default();
+            if !servers.contains(server) {
+                servers.push(server.clone());
+            }
+        }
+    }
+    providers
+        .into_iter()
+        .filter(|(_, servers)| servers.len() > 1)
+        .map(|(tool, servers)| ToolCollision {
+            tool: tool.to_string(),
+            servers,
+        })
+        .collect()
+}
+
+/// Probe `servers` with bounded concurrency and report colliding tool names
+/// Servers that fail to answer are left out with a warning
+pub async fn find_tool_collisions_with<P, Fut>(servers: Vec<(String, Value)>, probe: P) -> Vec<ToolCollision>
+where
+    P: Fn(String, Value) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Vec<String>, String>> + Send + 'static,
+{
+    let probe = Arc::new(probe);
+    let results = run_bounded(servers, PROBE_CONCURRENCY, move |(name, server)| {
+        let probe = probe.clone();
+        async move {
+            let result = probe(name.clone(), server).await;
+            (name, result)
+        }
+    })
+    .await;
+
+    let listed: Vec<(String, Vec<String>)> = results
+        .into_iter()
+        .filter_map(|(name, result)| match result {
+            Ok(tools) => Some((name, tools)),
+            Err(e) => {
+                eprintln!("Skipping MCP server '{}' in tool collision check: {}", name, e);
+                None
+            }
+        })
+        .collect();
+    tool_collisions(&listed)
+}
+
+/// Tool names exposed by more than one enabled MCP server of a config
+/// Local servers are started with their launch env on top of `base_env`
+pub async fn find_mcp_tool_collisions(
+    client: &reqwest::Client,
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    base_env: &HashMap<String, String>,
+) -> Vec<ToolCollision> {
+    let servers = enabled_mcp_servers(global, profile);
+    let envs: HashMap<String, HashMap<String, String>> = servers
+        .iter()
+        .map(|(name, _)| {
+            let env = launch_env::resolved_launch_env(global, profile, base_env, Some(name));
+            (name.clone(), env)
+        })
+        .collect();
+    let envs = Arc::new(envs);
+    let client = client.clone();
+    find_tool_collisions_with(servers, move |name, server| {
+        let client = client.clone();
+        let envs = envs.clone();
+        async move {
+            let env = envs.get(&name).cloned().unwrap_or_default();
+            test_mcp_tools(&client, &server, &env).await
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tool_collisions_with_mock_servers() {
+        let servers = vec![
+            ("github".to_string(), json!({ "type": "local", "command": ["github-mcp"] })),
+            ("gitlab".to_string(), json!({ "type": "local", "command": ["gitlab-mcp"] })),
+            ("broken".to_string(), json!({ "type": "remote", "url": "https://example.invalid/mcp" })),
+            ("search".to_string(), json!({ "type": "remote", "url": "https://example.com/mcp" })),
+        ];
+        let mock = |name: String, _server: Value| async move {
+            let tools: &[&str] = match name.as_str() {
+                "github" => &["create_issue", "search", "list_prs"],
+                "gitlab" => &["create_issue", "list_mrs"],
+                "search" => &["search"],
+                _ => return Err("connection refused".to_string()),
+            };
+            Ok(tools.iter().map(|t| t.to_string()).collect::<Vec<_>>())
+        };
+
+        assert_eq!(
+            find_tool_collisions_with(servers, mock).await,
+            vec![
+                ToolCollision {
+                    tool: "create_issue".to_string(),
+                    servers: vec!["github".to_string(), "gitlab".to_string()],
+                },
+                ToolCollision {
+                    tool: "search".to_string(),
+                    servers: vec!["github".to_string(), "search".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tools_page_and_sse_body() {
+        let result = json!({ "tools": [{ "name": "a" }, { "name": "b" }], "nextCursor": "p2" });
+        assert_eq!(parse_tools_page(&result), (vec!["a".to_string(), "b".to_string()], Some("p2".to_string())));
+
+        let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":2,\"result\":{\"tools\":[]}}\n\n";
+        assert_eq!(parse_http_response(body, &json!(2)).unwrap()["result"], json!({ "tools": [] }));
+    }
+}
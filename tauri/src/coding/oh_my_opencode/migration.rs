@@ -0,0 +1,143 @@
+//! Oh My OpenCode Migration Module
+//!
+//! Versioned upgrades of stored config records (database rows and bundle entries).
+//! Records without `schema_version` are version 1.
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// Key holding the schema version of a record
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+/// Version written by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MigrationError {
+    #[error("Config record must be a JSON object")]
+    NotAnObject,
+    #[error("Cannot migrate from version {from} down to {to}, down-migrations are not defined")]
+    Downgrade { from: u32, to: u32 },
+    #[error("Unknown schema version {0}, latest is {CURRENT_SCHEMA_VERSION}")]
+    UnknownVersion(u32),
+}
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[i]` upgrades a record from version `i + 1` to `i + 2`
+const MIGRATIONS: &[Migration] = &[snake_case_record_keys, tools_list_to_map];
+
+/// v1 -> v2: record keys are snake_case (`isApplied` -> `is_applied`, ...)
+fn snake_case_record_keys(record: &mut Map<String, Value>) {
+    const RENAMES: &[(&str, &str)] = &[
+        ("isApplied", "is_applied"),
+        ("otherFields", "other_fields"),
+        ("createdAt", "created_at"),
+        ("updatedAt", "updated_at"),
+    ];
+    for (camel, snake) in RENAMES {
+        if let Some(value) = record.shift_remove(*camel) {
+            // An existing snake_case value is newer and wins
+            if !record.contains_key(*snake) {
+                record.insert(snake.to_string(), value);
+            }
+        }
+    }
+}
+
+/// v2 -> v3: agent `tools` lists (`["bash", "edit"]`) become enable maps (`{"bash": true}`)
+fn tools_list_to_map(record: &mut Map<String, Value>) {
+    let Some(agents) = record.get_mut("agents").and_then(|v| v.as_object_mut()) else {
+        return;
+    };
+    for agent in agents.values_mut() {
+        let Some(tools) = agent.get_mut("tools") else {
+            continue;
+        };
+        let map: Map<String, Value> = match tools {
+            Value::Array(list) => list
+                .iter()
+                .filter_map(|tool| tool.as_str())
+                .map(|tool| (tool.to_string(), Value::Bool(true)))
+                .collect(),
+            _ => continue,
+        };
+        *tools = Value::Object(map);
+    }
+}
+
+/// Schema version of a record, 1 when unset
+pub fn schema_version(value: &Value) -> u32 {
+    value
+        .get(SCHEMA_VERSION_KEY)
+        .and_then(|v| v.as_u64())
+        .map(|v| v as u32)
+        .unwrap_or(1)
+}
+
+/// Apply migrations up to `target`, migrating to the current version is a no-op for current records
+pub fn migrate_to_version(mut value: Value, target: u32) -> Result<Value, MigrationError> {
+    let from = schema_version(&value);
+    if target == 0 || target > CURRENT_SCHEMA_VERSION {
+        return Err(MigrationError::UnknownVersion(target));
+    }
+    if from > CURRENT_SCHEMA_VERSION {
+        return Err(MigrationError::UnknownVersion(from));
+    }
+    if target < from {
+        return Err(MigrationError::Downgrade { from, to: target });
+    }
+
+    let record = value.as_object_mut().ok_or(MigrationError::NotAnObject)?;
+    for version in from..target {
+        MIGRATIONS[(version - 1) as usize](record);
+    }
+    record.insert(SCHEMA_VERSION_KEY.to_string(), Value::from(target));
+    Ok(value)
+}
+
+/// Migrate a record to the current schema version
+pub fn migrate(value: Value) -> Result<Value, MigrationError> {
+    migrate_to_version(value, CURRENT_SCHEMA_VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn v1_record() -> Value {
+        json!({
+            "name": "Legacy",
+            "isApplied": true,
+            "agents": { "oracle": { "tools": ["bash", "edit"] } }
+        })
+    }
+
+    #[test]
+    fn test_migrate_to_intermediate_version() {
+        let migrated = migrate_to_version(v1_record(), 2).unwrap();
+
+        assert_eq!(migrated["schema_version"], json!(2));
+        assert_eq!(migrated["is_applied"], json!(true));
+        assert!(migrated.get("isApplied").is_none());
+        // The v3 migration has not run
+        assert_eq!(migrated["agents"]["oracle"]["tools"], json!(["bash", "edit"]));
+
+        let current = migrate(migrated).unwrap();
+        assert_eq!(current["schema_version"], json!(3));
+        assert_eq!(current["agents"]["oracle"]["tools"], json!({ "bash": true, "edit": true }));
+    }
+
+    #[test]
+    fn test_migrate_rejects_downgrade() {
+        let current = migrate(v1_record()).unwrap();
+        assert_eq!(
+            migrate_to_version(current, 2),
+            Err(MigrationError::Downgrade { from: 3, to: 2 })
+        );
+        assert_eq!(
+            migrate_to_version(v1_record(), 9),
+            Err(MigrationError::UnknownVersion(9))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! Oh My OpenCode Minimize Module
+//!
+//! Reduce a config to a single agent for shareable bug reproductions.
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use super::agent;
+use super::inheritance;
+use super::mcp::{self, mcp_servers, tool_key_references_mcp};
+use super::types::OhMyOpenCodeConfigContent;
+use crate::coding::redact::redact_secrets;
+
+/// `other_fields` sections an agent can never depend on, dropped when minimizing
+const UNREFERENCED_SECTIONS: &[&str] = &["lsp", "disabled_hooks"];
+
+/// Keep only `keep_agent` (with `extends` resolved) and the MCP servers it references
+/// Other agents, hooks and LSP servers are removed and secrets are redacted
+pub fn minimize_config(
+    content: &OhMyOpenCodeConfigContent,
+    keep_agent: &str,
+) -> Result<OhMyOpenCodeConfigContent, String> {
+    let agents = agent::parse_agents(content.agents.as_ref());
+    let kept = inheritance::resolve_agent(&agents, keep_agent).map_err(|e| e.to_string())?;
+
+    let tool_keys: Vec<&String> = kept.tools.iter().flat_map(|tools| tools.keys()).collect();
+    let known_mcps: Vec<&str> = content
+        .other_fields
+        .as_ref()
+        .into_iter()
+        .flat_map(|other| {
+            let servers = mcp_servers(Some(other)).into_iter().flat_map(|s| s.keys().map(String::as_str));
+            let disabled = mcp::string_list(other, "disabled_mcps");
+            servers.chain(disabled)
+        })
+        .collect();
+    let is_referenced = |mcp: &str| {
+        tool_keys
+            .iter()
+            .any(|key| tool_key_references_mcp(key, mcp, &known_mcps))
+    };
+
+    let mut minimized_agents = IndexMap::new();
+    minimized_agents.insert(keep_agent.to_string(), kept.clone());
+    let mut minimized_agents = agent::agents_to_value(&minimized_agents);
+    redact_secrets(&mut minimized_agents);
+
+    let other_fields = content.other_fields.as_ref().map(|other| {
+        let mut other = other.clone();
+        if let Some(obj) = other.as_object_mut() {
+            for section in UNREFERENCED_SECTIONS {
+                obj.remove(*section);
+            }
+            if let Some(servers) = obj.get_mut("mcp").and_then(|v| v.as_object_mut()) {
+                servers.retain(|name, _| is_referenced(name));
+            }
+            if let Some(disabled) = obj.get_mut("disabled_mcps").and_then(|v| v.as_array_mut()) {
+                disabled.retain(|name| name.as_str().map(is_referenced).unwrap_or(false));
+            }
+            if let Some(disabled) = obj.get_mut("disabled_agents").and_then(|v| v.as_array_mut()) {
+                disabled.retain(|name| name.as_str() == Some(keep_agent));
+            }
+        }
+        redact_secrets(&mut other);
+        other
+    });
+
+    Ok(OhMyOpenCodeConfigContent {
+        name: format!("{} (minimal: {})", content.name, keep_agent),
+        is_applied: false,
+        agents: Some(minimized_agents),
+        other_fields,
+        created_at: content.created_at.clone(),
+        updated_at: content.updated_at.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::redact::REDACTED;
+    use serde_json::json;
+
+    #[test]
+    fn test_minimize_config_keeps_agent_and_referenced_mcp() {
+        let content = OhMyOpenCodeConfigContent {
+            name: "Work".to_string(),
+            is_applied: true,
+            agents: Some(json!({
+                "base": { "model": "openai/gpt-4o" },
+                "librarian": { "extends": "base", "tools": { "context7_resolve": true } },
+                "oracle": { "model": "openai/o3", "tools": { "github*": true } }
+            })),
+            other_fields: Some(json!({
+                "mcp": {
+                    "context7": { "type": "remote", "headers": { "Authorization": "Bearer abc" } },
+                    "github": { "type": "local" }
+                },
+                "lsp": { "rust": { "command": ["rust-analyzer"] } },
+                "disabled_agents": ["oracle"]
+            })),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+        };
+
+        let minimized = minimize_config(&content, "librarian").unwrap();
+        assert!(!minimized.is_applied);
+
+        let agents = minimized.agents.unwrap();
+        assert_eq!(agents.as_object().unwrap().len(), 1);
+        assert_eq!(agents["librarian"]["model"], json!("openai/gpt-4o"));
+
+        let other = minimized.other_fields.unwrap();
+        let servers: Vec<&String> = other["mcp"].as_object().unwrap().keys().collect();
+        assert_eq!(servers, vec!["context7"]);
+        assert_eq!(other["mcp"]["context7"]["headers"]["Authorization"], json!(REDACTED));
+        assert!(other.get("lsp").is_none());
+        assert_eq!(other["disabled_agents"], json!([]));
+
+        assert!(minimize_config(&content, "ghost").is_err());
+    }
+}
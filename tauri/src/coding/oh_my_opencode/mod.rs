@@ -1,4 +1,54 @@
 pub mod types;
+pub mod agent;
+pub mod bulk_edit;
+pub mod inheritance;
+pub mod dependencies;
+pub mod cycles;
+pub mod tools;
+pub mod reasoning;
+pub mod experimental;
+pub mod capabilities;
+pub mod recommend;
+pub mod matrix;
+pub mod prompt_library;
+pub mod prompt_encoding;
+pub mod config_env;
+pub mod launch_env;
+pub mod failover;
+pub mod providers;
+pub mod fingerprint;
+pub mod integrity;
+pub mod footprint;
+pub mod duplicates;
+pub mod delete;
+pub mod timestamps;
+pub mod render;
+pub mod global_apply;
+pub mod targets;
+pub mod global_impact;
+pub mod backups;
+pub mod disabled;
+pub mod effective;
+pub mod overlay;
+pub mod policy;
+pub mod validation;
+pub mod sarif;
+pub mod preflight;
+pub mod migration;
+pub mod binary_cache;
+pub mod import;
+pub mod raw_json;
+pub mod key_case;
+pub mod bundle;
+pub mod bundle_stream;
+pub mod changelog;
+pub mod markdown;
+pub mod mcp;
+pub mod mcp_probe;
+pub mod minimize;
+pub mod repair;
+pub mod self_contained;
+pub mod vcs;
 pub mod commands;
 pub mod adapter;
 pub mod tray_support;
@@ -0,0 +1,271 @@
+//! Oh My OpenCode Overlay Module
+//!
+//! The smallest overlay turning one config into another under `deep_merge_json`.
+//! The merge only adds and replaces, it has no way to remove a key (an overlay `null`
+//! writes `null`), so removals are reported instead of being put in the overlay.
+//!
+//! Also a tracked version of the merge for the team/personal overlay workflow: every
+//! path where both sides set a different value is a conflict that can be resolved one by one.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayForChange {
+    pub overlay: Value,
+    /// Dotted paths present in base but not in desired, no overlay can remove them
+    pub unexpressible: Vec<String>,
+}
+
+fn diff(base: &Value, desired: &Value, path: &mut Vec<String>, removed: &mut Vec<String>) -> Option<Value> {
+    let (Some(base_obj), Some(desired_obj)) = (base.as_object(), desired.as_object()) else {
+        return (base != desired).then(|| desired.clone());
+    };
+
+    for key in base_obj.keys().filter(|key| !desired_obj.contains_key(*key)) {
+        path.push(key.clone());
+        removed.push(path.join("."));
+        path.pop();
+    }
+
+    let mut overlay = Map::new();
+    for (key, value) in desired_obj {
+        match base_obj.get(key) {
+            Some(base_value) => {
+                path.push(key.clone());
+                if let Some(changed) = diff(base_value, value, path, removed) {
+                    overlay.insert(key.clone(), changed);
+                }
+                path.pop();
+            }
+            None => {
+                overlay.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    (!overlay.is_empty()).then_some(Value::Object(overlay))
+}
+
+/// Minimal overlay such that `deep_merge_json(base, overlay) == desired`, `{}` when nothing changes
+/// Holds exactly when `unexpressible` is empty; removed keys are listed there and stay in the merge result
+pub fn overlay_for_change_report(base: &Value, desired: &Value) -> OverlayForChange {
+    let mut unexpressible = Vec::new();
+    let overlay = diff(base, desired, &mut Vec::new(), &mut unexpressible).unwrap_or_else(|| {
+        if desired.is_object() {
+            Value::Object(Map::new())
+        } else {
+            desired.clone()
+        }
+    });
+    OverlayForChange { overlay, unexpressible }
+}
+
+/// Minimal overlay for a change, see `overlay_for_change_report` for removals
+pub fn overlay_for_change(base: &Value, desired: &Value) -> Value {
+    overlay_for_change_report(base, desired).overlay
+}
+
+// ============================================================================
+// Tracked Merge
+// ============================================================================
+
+/// A path both sides set to a different value, objects on both sides are merged instead
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeConflict {
+    pub path: String,
+    pub base: Value,
+    pub overlay: Value,
+}
+
+/// How to resolve one conflict
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "choice", content = "value", rename_all = "camelCase")]
+pub enum MergeChoice {
+    Base,
+    Overlay,
+    Custom(Value),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum MergeError {
+    #[error("Unresolved conflicts: {}", .0.join(", "))]
+    Unresolved(Vec<String>),
+    #[error("No conflict at '{0}'")]
+    UnknownPath(String),
+}
+
+fn merge_tracked(
+    base: &mut Value,
+    overlay: &Value,
+    path: &mut Vec<String>,
+    on_conflict: &mut dyn FnMut(String, &Value, &Value) -> Value,
+) {
+    let (Some(base_obj), Some(overlay_obj)) = (base.as_object_mut(), overlay.as_object()) else {
+        return;
+    };
+    for (key, value) in overlay_obj {
+        match base_obj.get_mut(key) {
+            Some(base_value) if base_value.is_object() && value.is_object() => {
+                path.push(key.clone());
+                merge_tracked(base_value, value, path, on_conflict);
+                path.pop();
+            }
+            Some(base_value) if base_value != value => {
+                path.push(key.clone());
+                *base_value = on_conflict(path.join("."), base_value, value);
+                path.pop();
+            }
+            Some(_) => {}
+            None => {
+                base_obj.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Every conflict of merging `overlay` into `base`, in overlay order
+pub fn find_merge_conflicts(base: &Value, overlay: &Value) -> Vec<MergeConflict> {
+    let mut conflicts = Vec::new();
+    let mut merged = base.clone();
+    merge_tracked(&mut merged, overlay, &mut Vec::new(), &mut |path, base, overlay| {
+        conflicts.push(MergeConflict {
+            path,
+            base: base.clone(),
+            overlay: overlay.clone(),
+        });
+        overlay.clone()
+    });
+    conflicts
+}
+
+/// `deep_merge_json` with each conflict resolved by `resolutions` (dotted path -> choice)
+/// Unresolved conflicts take the overlay value, or fail when `strict`
+/// A resolution for a path that is not a conflict is an error
+pub fn merge_with_resolutions(
+    base: &Value,
+    overlay: &Value,
+    resolutions: &HashMap<String, MergeChoice>,
+    strict: bool,
+) -> Result<Value, MergeError> {
+    let mut merged = base.clone();
+    let mut seen = Vec::new();
+    let mut unresolved = Vec::new();
+    merge_tracked(&mut merged, overlay, &mut Vec::new(), &mut |path, base, overlay| {
+        let value = match resolutions.get(&path) {
+            Some(MergeChoice::Base) => base.clone(),
+            Some(MergeChoice::Overlay) => overlay.clone(),
+            Some(MergeChoice::Custom(value)) => value.clone(),
+            None => {
+                unresolved.push(path.clone());
+                overlay.clone()
+            }
+        };
+        seen.push(path);
+        value
+    });
+
+    let mut unknown: Vec<&String> = resolutions.keys().filter(|path| !seen.contains(*path)).collect();
+    unknown.sort();
+    if let Some(path) = unknown.first() {
+        return Err(MergeError::UnknownPath((*path).clone()));
+    }
+    if strict && !unresolved.is_empty() {
+        return Err(MergeError::Unresolved(unresolved));
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::adapter::deep_merge_json;
+    use serde_json::json;
+
+    #[test]
+    fn test_overlay_for_nested_change() {
+        let base = json!({
+            "agents": {
+                "oracle": { "model": "openai/o3", "tools": { "bash": true, "edit": true } },
+                "explore": { "model": "openai/gpt-4o-mini" }
+            },
+            "disabled_hooks": ["startup-toast"]
+        });
+        let desired = json!({
+            "agents": {
+                "oracle": { "model": "openai/o3", "tools": { "bash": false, "edit": true } },
+                "explore": { "model": "openai/gpt-4o-mini" },
+                "librarian": { "model": "anthropic/claude-sonnet-4" }
+            },
+            "disabled_hooks": ["startup-toast", "auto-update"]
+        });
+
+        let report = overlay_for_change_report(&base, &desired);
+        assert_eq!(
+            report.overlay,
+            json!({
+                "agents": {
+                    "oracle": { "tools": { "bash": false } },
+                    "librarian": { "model": "anthropic/claude-sonnet-4" }
+                },
+                "disabled_hooks": ["startup-toast", "auto-update"]
+            })
+        );
+        assert!(report.unexpressible.is_empty());
+
+        let mut merged = base.clone();
+        deep_merge_json(&mut merged, &report.overlay);
+        assert_eq!(merged, desired);
+        assert_eq!(overlay_for_change(&desired, &desired), json!({}));
+    }
+
+    #[test]
+    fn test_removals_are_reported() {
+        let base = json!({ "agents": { "oracle": { "model": "openai/o3", "prompt": "x" } } });
+        let desired = json!({ "agents": { "oracle": { "model": "openai/o3" } } });
+
+        let report = overlay_for_change_report(&base, &desired);
+        assert_eq!(report.overlay, json!({}));
+        assert_eq!(report.unexpressible, vec!["agents.oracle.prompt".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_with_resolutions() {
+        let team = json!({
+            "agents": { "oracle": { "model": "openai/o3", "temperature": 0.2 } },
+            "disabled_hooks": ["startup-toast"]
+        });
+        let personal = json!({
+            "agents": { "oracle": { "model": "anthropic/claude-opus-4", "temperature": 0.7 }, "explore": {} },
+            "disabled_hooks": []
+        });
+        let paths: Vec<String> = find_merge_conflicts(&team, &personal).into_iter().map(|c| c.path).collect();
+        assert_eq!(paths, vec!["agents.oracle.model", "agents.oracle.temperature", "disabled_hooks"]);
+
+        let resolutions = HashMap::from([
+            ("agents.oracle.model".to_string(), MergeChoice::Base),
+            ("agents.oracle.temperature".to_string(), MergeChoice::Custom(json!(0.4))),
+        ]);
+        let merged = merge_with_resolutions(&team, &personal, &resolutions, false).unwrap();
+        assert_eq!(
+            merged,
+            json!({
+                "agents": { "oracle": { "model": "openai/o3", "temperature": 0.4 }, "explore": {} },
+                "disabled_hooks": []
+            })
+        );
+
+        assert_eq!(
+            merge_with_resolutions(&team, &personal, &resolutions, true),
+            Err(MergeError::Unresolved(vec!["disabled_hooks".to_string()]))
+        );
+        let stray = HashMap::from([("agents.explore".to_string(), MergeChoice::Base)]);
+        assert_eq!(
+            merge_with_resolutions(&team, &personal, &stray, false),
+            Err(MergeError::UnknownPath("agents.explore".to_string()))
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! Oh My OpenCode Policy Module
+//!
+//! Org-level policy rules evaluated against the rendered config, usable as a pre-apply gate.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::failover::model_provider;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use crate::coding::open_code::provider_id::canonical_provider_id;
+
+/// A set of policy rules, typically distributed by an organization
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyRuleSet {
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// A single policy rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyRule {
+    pub id: String,
+    #[serde(flatten)]
+    pub kind: PolicyRuleKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PolicyRuleKind {
+    /// The agent must be defined and not disabled
+    RequiredAgent { agent: String },
+    /// No agent may use a model from this provider
+    ForbiddenProvider { provider: String },
+    /// The dotted path must exist in the rendered config, with the given value if set
+    RequiredSetting {
+        path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<Value>,
+    },
+    /// At most this many agents may be active
+    MaxAgents { max: usize },
+}
+
+/// A rule violation, `path` is a dotted path into the rendered config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyViolation {
+    pub rule_id: String,
+    pub path: String,
+    pub message: String,
+}
+
+/// Look up a dotted path (e.g. `experimental.telemetry`) in a JSON value
+fn get_by_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |current, segment| current.get(segment))
+}
+
+/// Evaluate every rule against the config that would be written for this profile
+pub fn lint_against_policy(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    rules: &PolicyRuleSet,
+) -> Vec<PolicyViolation> {
+    let rendered = render_opencode_json(global, profile);
+    let agents = profile.typed_agents();
+    let disabled = global.disabled_agents.clone().unwrap_or_default();
+    let is_active = |name: &str| !disabled.iter().any(|d| d == name);
+
+    let mut violations = Vec::new();
+
+    for rule in &rules.rules {
+        match &rule.kind {
+            PolicyRuleKind::RequiredAgent { agent: name } => {
+                if !agents.contains_key(name) {
+                    violations.push(PolicyViolation {
+                        rule_id: rule.id.clone(),
+                        path: format!("agents.{}", name),
+                        message: format!("Required agent '{}' is not defined", name),
+                    });
+                } else if !is_active(name) {
+                    violations.push(PolicyViolation {
+                        rule_id: rule.id.clone(),
+                        path: "disabled_agents".to_string(),
+                        message: format!("Required agent '{}' is disabled", name),
+                    });
+                }
+            }
+            PolicyRuleKind::ForbiddenProvider { provider } => {
+                for (name, agent) in &agents {
+                    let uses_provider = agent
+                        .model
+                        .as_deref()
+                        .and_then(model_provider)
+                        .is_some_and(|provider_id| provider_id == canonical_provider_id(provider));
+                    if uses_provider {
+                        violations.push(PolicyViolation {
+                            rule_id: rule.id.clone(),
+                            path: format!("agents.{}.model", name),
+                            message: format!(
+                                "Agent '{}' uses forbidden provider '{}'",
+                                name, provider
+                            ),
+                        });
+                    }
+                }
+            }
+            PolicyRuleKind::RequiredSetting { path, value } => {
+                match (get_by_path(&rendered, path), value) {
+                    (None, _) => violations.push(PolicyViolation {
+                        rule_id: rule.id.clone(),
+                        path: path.clone(),
+                        message: format!("Required setting '{}' is missing", path),
+                    }),
+                    (Some(actual), Some(expected)) if actual != expected => {
+                        violations.push(PolicyViolation {
+                            rule_id: rule.id.clone(),
+                            path: path.clone(),
+                            message: format!(
+                                "Setting '{}' must be {}, found {}",
+                                path, expected, actual
+                            ),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+            PolicyRuleKind::MaxAgents { max } => {
+                let active = agents.keys().filter(|name| is_active(name)).count();
+                if active > *max {
+                    violations.push(PolicyViolation {
+                        rule_id: rule.id.clone(),
+                        path: "agents".to_string(),
+                        message: format!("{} active agents exceed the maximum of {}", active, max),
+                    });
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rules(value: Value) -> PolicyRuleSet {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_required_agent() {
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["oracle".to_string()]),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "model": "openai/o3" } }));
+        let rules = rules(json!({ "rules": [
+            { "id": "need-oracle", "type": "requiredAgent", "agent": "oracle" },
+            { "id": "need-explore", "type": "requiredAgent", "agent": "explore" }
+        ]}));
+
+        let violations = lint_against_policy(&global, &profile, &rules);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule_id, "need-oracle");
+        assert_eq!(violations[0].path, "disabled_agents");
+        assert_eq!(violations[1].rule_id, "need-explore");
+        assert_eq!(violations[1].path, "agents.explore");
+    }
+
+    #[test]
+    fn test_forbidden_provider() {
+        let global = OhMyOpenCodeGlobalConfig::default();
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "Sisyphus": { "model": "OpenAI/gpt-4o" },
+            "explore": { "model": "qwen/qwen3" }
+        }));
+        let rules = rules(json!({ "rules": [
+            { "id": "no-openai", "type": "forbiddenProvider", "provider": "openai" }
+        ]}));
+
+        let violations = lint_against_policy(&global, &profile, &rules);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "agents.Sisyphus.model");
+    }
+
+    #[test]
+    fn test_required_setting() {
+        let global = OhMyOpenCodeGlobalConfig {
+            experimental: Some(json!({ "telemetry": false })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({}));
+        let rules = rules(json!({ "rules": [
+            { "id": "telemetry-on", "type": "requiredSetting", "path": "experimental.telemetry", "value": true },
+            { "id": "has-lsp", "type": "requiredSetting", "path": "lsp" },
+            { "id": "has-schema", "type": "requiredSetting", "path": "$schema" }
+        ]}));
+
+        let violations = lint_against_policy(&global, &profile, &rules);
+        let ids: Vec<&str> = violations.iter().map(|v| v.rule_id.as_str()).collect();
+        assert_eq!(ids, vec!["telemetry-on", "has-lsp"]);
+        assert_eq!(violations[0].path, "experimental.telemetry");
+    }
+
+    #[test]
+    fn test_max_agents() {
+        let global = OhMyOpenCodeGlobalConfig {
+            disabled_agents: Some(vec!["explore".to_string()]),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "Sisyphus": { "model": "openai/gpt-4o" },
+            "oracle": { "model": "openai/o3" },
+            "explore": { "model": "qwen/qwen3" }
+        }));
+
+        let within = rules(json!({ "rules": [{ "id": "max", "type": "maxAgents", "max": 2 }] }));
+        assert!(lint_against_policy(&global, &profile, &within).is_empty());
+
+        let exceeded = rules(json!({ "rules": [{ "id": "max", "type": "maxAgents", "max": 1 }] }));
+        let violations = lint_against_policy(&global, &profile, &exceeded);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "agents");
+    }
+}
@@ -0,0 +1,198 @@
+//! Oh My OpenCode Preflight Module
+//!
+//! Check that the MCP commands and LSP binaries a config needs are installed,
+//! before it is applied on a new machine.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Install hints for well-known binaries
+const INSTALL_HINTS: &[(&str, &str)] = &[
+    ("npx", "Install Node.js from https://nodejs.org"),
+    ("node", "Install Node.js from https://nodejs.org"),
+    ("bunx", "Install Bun from https://bun.sh"),
+    ("bun", "Install Bun from https://bun.sh"),
+    ("uvx", "Install uv from https://docs.astral.sh/uv"),
+    ("uv", "Install uv from https://docs.astral.sh/uv"),
+    ("docker", "Install Docker from https://docs.docker.com/get-docker"),
+    ("rust-analyzer", "rustup component add rust-analyzer"),
+    ("typescript-language-server", "npm install -g typescript-language-server typescript"),
+    ("pyright-langserver", "npm install -g pyright"),
+    ("gopls", "go install golang.org/x/tools/gopls@latest"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RequirementKind {
+    Mcp,
+    Lsp,
+}
+
+/// A binary required by an MCP server or LSP server
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Requirement {
+    pub kind: RequirementKind,
+    /// MCP or LSP server name
+    pub name: String,
+    pub binary: String,
+    /// Resolved location, `None` when missing
+    pub resolved: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_hint: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    pub requirements: Vec<Requirement>,
+}
+
+impl PreflightReport {
+    pub fn missing(&self) -> impl Iterator<Item = &Requirement> {
+        self.requirements.iter().filter(|r| r.resolved.is_none())
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.missing().next().is_none()
+    }
+}
+
+/// First element of a `command` (array or string form)
+fn command_binary(server: &Value) -> Option<String> {
+    let binary = match server.get("command")? {
+        Value::Array(parts) => parts.first()?.as_str()?.to_string(),
+        Value::String(command) => command.split_whitespace().next()?.to_string(),
+        _ => return None,
+    };
+    (!binary.is_empty()).then_some(binary)
+}
+
+/// Resolve a binary against the `PATH` of `env`, paths containing a separator are checked as-is
+pub fn find_binary(binary: &str, env: &HashMap<String, String>) -> Option<PathBuf> {
+    if binary.contains('/') || binary.contains('\\') {
+        let path = PathBuf::from(binary);
+        return path.is_file().then_some(path);
+    }
+
+    let separator = if cfg!(windows) { ';' } else { ':' };
+    let extensions: Vec<String> = if cfg!(windows) {
+        let pathext = env.get("PATHEXT").map(String::as_str).unwrap_or(".EXE;.CMD;.BAT");
+        std::iter::once(String::new())
+            .chain(pathext.split(';').map(str::to_lowercase))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    env.get("PATH")?
+        .split(separator)
+        .filter(|dir| !dir.trim().is_empty())
+        .flat_map(|dir| {
+            extensions
+                .iter()
+                .map(move |ext| Path::new(dir).join(format!("{}{}", binary, ext)))
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+fn install_hint(binary: &str) -> Option<String> {
+    let name = Path::new(binary).file_name()?.to_str()?;
+    INSTALL_HINTS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, hint)| hint.to_string())
+}
+
+/// Collect every local MCP command and LSP binary of the rendered config and resolve it
+/// Disabled MCP servers and remote servers are skipped
+pub fn preflight_check(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    env: &HashMap<String, String>,
+) -> PreflightReport {
+    let config = render_opencode_json(global, profile);
+    let disabled_mcps: Vec<&str> = config
+        .get("disabled_mcps")
+        .and_then(|v| v.as_array())
+        .map(|items| items.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    let sections = [("mcp", RequirementKind::Mcp), ("lsp", RequirementKind::Lsp)];
+    let mut report = PreflightReport::default();
+    for (section, kind) in sections {
+        let Some(servers) = config.get(section).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, server) in servers {
+            if kind == RequirementKind::Mcp && disabled_mcps.contains(&name.as_str()) {
+                continue;
+            }
+            let Some(binary) = command_binary(server) else {
+                continue;
+            };
+            let resolved = find_binary(&binary, env).map(|p| p.to_string_lossy().to_string());
+            report.requirements.push(Requirement {
+                kind,
+                name: name.clone(),
+                install_hint: resolved.is_none().then(|| install_hint(&binary)).flatten(),
+                binary,
+                resolved,
+            });
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_preflight_mocked_path() {
+        let bin_dir = std::env::temp_dir().join(format!("ai-toolbox-preflight-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&bin_dir).unwrap();
+        fs::write(bin_dir.join("context7-mcp"), "").unwrap();
+
+        let global = OhMyOpenCodeGlobalConfig {
+            lsp: Some(json!({ "rust": { "command": ["rust-analyzer"] } })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig {
+            id: "p1".to_string(),
+            name: "Profile".to_string(),
+            is_applied: false,
+            agents: None,
+            other_fields: Some(json!({
+                "mcp": {
+                    "context7": { "type": "local", "command": ["context7-mcp", "--stdio"] },
+                    "remote": { "type": "remote", "url": "https://example.com/mcp" }
+                }
+            })),
+            created_at: None,
+            updated_at: None,
+        };
+        let env = HashMap::from([("PATH".to_string(), bin_dir.to_string_lossy().to_string())]);
+
+        let report = preflight_check(&global, &profile, &env);
+        fs::remove_dir_all(&bin_dir).unwrap();
+
+        assert_eq!(report.requirements.len(), 2);
+        assert_eq!(report.requirements[0].name, "context7");
+        assert!(report.requirements[0].resolved.is_some());
+
+        let missing: Vec<&Requirement> = report.missing().collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].kind, RequirementKind::Lsp);
+        assert_eq!(missing[0].binary, "rust-analyzer");
+        assert_eq!(missing[0].install_hint.as_deref(), Some("rustup component add rust-analyzer"));
+        assert!(!report.is_ready());
+    }
+}
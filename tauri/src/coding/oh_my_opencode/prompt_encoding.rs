@@ -0,0 +1,223 @@
+//! Oh My OpenCode Prompt Encoding Module
+//!
+//! Copy-pasted prompts often carry characters that look harmless but confuse models:
+//! non-breaking and zero-width spaces, the U+FFFD replacement character and UTF-8 text
+//! decoded as Windows-1252 (`â€™`). They are reported by a validation rule and only
+//! rewritten when `sanitize_prompt` is called explicitly. Smart quotes are ordinary
+//! typography, they are only reported and replaced when asked for. Joiners (U+200C,
+//! U+200D, U+2060) are left alone, emoji sequences and many scripts need them.
+
+use serde::Serialize;
+
+use super::agent;
+use super::prompt_library;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const PROMPT_ENCODING_RULE: &str = "prompt-encoding";
+/// Positions listed per warning, the rest is summarized
+const MAX_LISTED_POSITIONS: usize = 5;
+
+/// Mojibake sequences and the text they were meant to be, longest first
+const MOJIBAKE: &[(&str, &str)] = &[
+    ("â€™", "\u{2019}"),
+    ("â€˜", "\u{2018}"),
+    ("â€œ", "\u{201c}"),
+    ("â€\u{9d}", "\u{201d}"),
+    ("â€”", "\u{2014}"),
+    ("â€“", "\u{2013}"),
+    ("â€¦", "\u{2026}"),
+    ("Â\u{a0}", " "),
+];
+
+/// Replacement of a suspicious character, `None` when it is removed
+/// Smart quotes only count when `smart_quotes` is set
+fn replacement(c: char, smart_quotes: bool) -> Option<Option<&'static str>> {
+    match c {
+        '\u{a0}' | '\u{202f}' | '\u{2007}' => Some(Some(" ")),
+        '\u{200b}' | '\u{feff}' | '\u{fffd}' => Some(None),
+        '\u{2018}' | '\u{2019}' if smart_quotes => Some(Some("'")),
+        '\u{201c}' | '\u{201d}' if smart_quotes => Some(Some("\"")),
+        _ => None,
+    }
+}
+
+fn describe(c: char) -> &'static str {
+    match c {
+        '\u{a0}' | '\u{202f}' | '\u{2007}' => "non-breaking space",
+        '\u{200b}' => "zero-width space",
+        '\u{feff}' => "byte order mark",
+        '\u{fffd}' => "replacement character",
+        _ => "smart quote",
+    }
+}
+
+/// A suspicious character or sequence, line and column are 1-based and count characters
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncodingIssue {
+    pub line: usize,
+    pub column: usize,
+    pub code_point: String,
+    pub kind: String,
+}
+
+/// Every suspicious character of a prompt, in text order
+pub fn find_encoding_issues(text: &str, smart_quotes: bool) -> Vec<EncodingIssue> {
+    let mut issues = Vec::new();
+    for (index, line) in text.split('\n').enumerate() {
+        let mut column = 0;
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            column += 1;
+            if let Some((sequence, _)) = MOJIBAKE.iter().find(|(sequence, _)| rest.starts_with(sequence)) {
+                issues.push(EncodingIssue {
+                    line: index + 1,
+                    column,
+                    code_point: sequence.chars().map(|c| format!("U+{:04X}", c as u32)).collect::<Vec<_>>().join(" "),
+                    kind: "mojibake".to_string(),
+                });
+                column += sequence.chars().count() - 1;
+                rest = &rest[sequence.len()..];
+                continue;
+            }
+            if replacement(c, smart_quotes).is_some() {
+                issues.push(EncodingIssue {
+                    line: index + 1,
+                    column,
+                    code_point: format!("U+{:04X}", c as u32),
+                    kind: describe(c).to_string(),
+                });
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    issues
+}
+
+/// Normalize suspicious characters: spaces become plain spaces, mojibake is decoded,
+/// zero-width spaces and replacement characters are removed, and with `smart_quotes`
+/// quotes become ASCII
+pub fn sanitize_prompt(s: &str, smart_quotes: bool) -> String {
+    let mut text = s.to_string();
+    for (sequence, fixed) in MOJIBAKE {
+        text = text.replace(sequence, fixed);
+    }
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match replacement(c, smart_quotes) {
+            Some(Some(fixed)) => out.push_str(fixed),
+            Some(None) => {}
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+fn issue_for(path: String, text: &str) -> Option<ValidationIssue> {
+    let found = find_encoding_issues(text, false);
+    if found.is_empty() {
+        return None;
+    }
+    let mut positions: Vec<String> = found
+        .iter()
+        .take(MAX_LISTED_POSITIONS)
+        .map(|i| format!("{} {} at {}:{}", i.kind, i.code_point, i.line, i.column))
+        .collect();
+    if found.len() > MAX_LISTED_POSITIONS {
+        positions.push(format!("{} more", found.len() - MAX_LISTED_POSITIONS));
+    }
+    Some(ValidationIssue::new(
+        PROMPT_ENCODING_RULE,
+        Severity::Warning,
+        path,
+        format!("Suspicious characters in prompt: {}", positions.join(", ")),
+    ))
+}
+
+/// Validation rule: warn about suspicious characters in agent prompts and prompt library entries
+pub fn validate_prompt_encoding(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    let agents = profile.typed_agents();
+    let agent_issues = agents.iter().filter_map(|(name, agent)| {
+        issue_for(format!("agents.{}.prompt", name), agent.prompt.as_deref()?)
+    });
+    let library_issues = prompt_library::prompt_library(global)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, text)| {
+            issue_for(
+                format!("{}.{}", prompt_library::PROMPT_LIBRARY_KEY, name),
+                text.as_str()?,
+            )
+        });
+    agent_issues.chain(library_issues).collect()
+}
+
+/// Sanitize the prompts of the named agents (all when `names` is `None`)
+/// Returns the names of the agents whose prompt changed
+pub fn sanitize_agent_prompts(
+    config: &mut OhMyOpenCodeConfig,
+    names: Option<&[String]>,
+    smart_quotes: bool,
+) -> Vec<String> {
+    agent::with_agents_mut(config, |agents| {
+        let mut changed = Vec::new();
+        for (name, agent) in agents.iter_mut() {
+            if names.is_some_and(|names| !names.contains(name)) {
+                continue;
+            }
+            let Some(prompt) = &agent.prompt else {
+                continue;
+            };
+            let sanitized = sanitize_prompt(prompt, smart_quotes);
+            if sanitized != *prompt {
+                agent.prompt = Some(sanitized);
+                changed.push(name.clone());
+            }
+        }
+        changed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_zero_width_space_and_replacement_char() {
+        let prompt = "You are a reviewer.\nCheck\u{200b} every diff \u{fffd} carefully.";
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "prompt": prompt }, "explore": { "prompt": "Plain" } }));
+
+        let found = find_encoding_issues(prompt, false);
+        assert_eq!(
+            found.iter().map(|i| (i.line, i.column, i.kind.as_str())).collect::<Vec<_>>(),
+            vec![(2, 6, "zero-width space"), (2, 19, "replacement character")]
+        );
+
+        let issues = validate_prompt_encoding(&OhMyOpenCodeGlobalConfig::default(), &profile);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].path, "agents.oracle.prompt");
+        assert!(issues[0].message.contains("U+200B at 2:6"));
+        assert!(issues[0].message.contains("U+FFFD at 2:19"));
+        // Reporting leaves the prompt alone
+        assert_eq!(profile.typed_agents()["oracle"].prompt.as_deref(), Some(prompt));
+
+        assert_eq!(sanitize_prompt(prompt, false), "You are a reviewer.\nCheck every diff  carefully.");
+        assert_eq!(sanitize_prompt("It\u{2019}s\u{a0}done â€” ok", false), "It\u{2019}s done \u{2014} ok");
+        assert_eq!(sanitize_prompt("It\u{2019}s \u{201c}done\u{201d}", true), "It's \"done\"");
+    }
+
+    #[test]
+    fn test_typography_and_joiners_are_not_issues() {
+        // Smart quotes, an emoji ZWJ sequence and a Persian ZWNJ
+        let prompt = "\u{201c}Ship it\u{201d} \u{1f469}\u{200d}\u{1f4bb} \u{645}\u{6cc}\u{200c}\u{62e}\u{648}\u{627}\u{647}\u{645}";
+        assert!(find_encoding_issues(prompt, false).is_empty());
+        assert_eq!(sanitize_prompt(prompt, false), prompt);
+        assert_eq!(find_encoding_issues(prompt, true).len(), 2);
+    }
+}
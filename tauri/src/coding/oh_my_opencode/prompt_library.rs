@@ -0,0 +1,168 @@
+//! Oh My OpenCode Prompt Library Module
+//!
+//! Shared prompts stored in the global config (`other_fields.prompt_library`, name -> text).
+//! Agents point to an entry with `prompt_ref`, which is inlined when rendering.
+
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+use super::agent::AgentProfile;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const PROMPT_REF_RULE: &str = "prompt-ref";
+
+/// Key of the prompt library in the global `other_fields`, never written to oh-my-opencode.json
+pub const PROMPT_LIBRARY_KEY: &str = "prompt_library";
+
+/// The prompt library of the global config, if any
+pub fn prompt_library(global: &OhMyOpenCodeGlobalConfig) -> Option<&Map<String, Value>> {
+    global
+        .other_fields
+        .as_ref()?
+        .get(PROMPT_LIBRARY_KEY)?
+        .as_object()
+}
+
+/// Inline `prompt_ref` into `prompt` for every agent without an explicit prompt
+/// Unresolved refs are left in place, returns the number of inlined prompts
+pub fn resolve_prompt_refs(
+    agents: &mut IndexMap<String, AgentProfile>,
+    library: Option<&Map<String, Value>>,
+) -> usize {
+    let Some(library) = library else {
+        return 0;
+    };
+
+    let mut resolved = 0;
+    for agent in agents.values_mut() {
+        if agent.prompt.is_some() {
+            continue;
+        }
+        let text = agent
+            .prompt_ref
+            .as_deref()
+            .and_then(|name| library.get(name))
+            .and_then(|v| v.as_str());
+        if let Some(text) = text {
+            agent.prompt = Some(text.to_string());
+            agent.prompt_ref = None;
+            resolved += 1;
+        }
+    }
+    resolved
+}
+
+/// Whether any agent uses `prompt_ref`
+pub fn has_prompt_refs(agents: &IndexMap<String, AgentProfile>) -> bool {
+    agents.values().any(|agent| agent.prompt_ref.is_some())
+}
+
+/// Validation rule: every `prompt_ref` must exist in the library, unused entries are warned about
+pub fn validate_prompt_refs(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    let library = prompt_library(global);
+    let agents = profile.typed_agents();
+    let mut issues = Vec::new();
+
+    for (name, agent) in &agents {
+        let Some(prompt_ref) = agent.prompt_ref.as_deref() else {
+            continue;
+        };
+        if !library.map(|l| l.contains_key(prompt_ref)).unwrap_or(false) {
+            issues.push(ValidationIssue::new(
+                PROMPT_REF_RULE,
+                Severity::Error,
+                format!("agents.{}.prompt_ref", name),
+                format!(
+                    "Agent '{}' references unknown prompt '{}'",
+                    name, prompt_ref
+                ),
+            ));
+        }
+    }
+
+    for entry in library.into_iter().flat_map(|l| l.keys()) {
+        let referenced = agents
+            .values()
+            .any(|agent| agent.prompt_ref.as_deref() == Some(entry.as_str()));
+        if !referenced {
+            issues.push(ValidationIssue::new(
+                PROMPT_REF_RULE,
+                Severity::Warning,
+                format!("{}.{}", PROMPT_LIBRARY_KEY, entry),
+                format!("Prompt '{}' is not referenced by any agent", entry),
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use serde_json::json;
+
+    #[test]
+    fn test_resolve_prompt_refs() {
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({ "prompt_library": { "reviewer": "Review carefully." } })),
+            ..Default::default()
+        };
+        let mut agents = parse_agents(Some(&json!({
+            "oracle": { "prompt_ref": "reviewer" },
+            "explore": { "prompt_ref": "reviewer", "prompt": "Explicit wins" },
+            "ghost": { "prompt_ref": "missing" }
+        })));
+
+        assert_eq!(resolve_prompt_refs(&mut agents, prompt_library(&global)), 1);
+        assert_eq!(agents["oracle"].prompt.as_deref(), Some("Review carefully."));
+        assert_eq!(agents["oracle"].prompt_ref, None);
+        assert_eq!(agents["explore"].prompt.as_deref(), Some("Explicit wins"));
+        assert_eq!(agents["ghost"].prompt_ref.as_deref(), Some("missing"));
+    }
+
+    #[test]
+    fn test_validate_unresolved_prompt_ref() {
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({ "prompt_library": { "reviewer": "Review carefully." } })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({
+            "oracle": { "prompt_ref": "reviewer" },
+            "explore": { "prompt_ref": "explorer" }
+        }));
+
+        let issues = validate_prompt_refs(&global, &profile);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].path, "agents.explore.prompt_ref");
+        assert!(issues[0].message.contains("'explorer'"));
+    }
+
+    #[test]
+    fn test_validate_unused_prompt() {
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({
+                "prompt_library": { "reviewer": "Review carefully.", "legacy": "Old prompt" }
+            })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "prompt_ref": "reviewer" } }));
+
+        let issues = validate_prompt_refs(&global, &profile);
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::new(
+                "prompt-ref",
+                Severity::Warning,
+                "prompt_library.legacy",
+                "Prompt 'legacy' is not referenced by any agent"
+            )]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Oh My OpenCode Providers Module
+//!
+//! Which configured OpenCode providers the agents of oh-my-opencode configs actually use.
+
+use serde_json::Value;
+use std::collections::HashSet;
+
+use super::failover::{model_provider, PROVIDER_FALLBACKS_KEY};
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use crate::coding::open_code::provider_id::canonical_provider_id;
+
+/// Collect the provider of every `model` / `small_model` string, recursively
+fn collect_model_providers(value: &Value, used: &mut HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map {
+                match item {
+                    Value::String(model) if key == "model" || key == "small_model" => {
+                        used.extend(model_provider(model));
+                    }
+                    _ => collect_model_providers(item, used),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| collect_model_providers(item, used)),
+        _ => {}
+    }
+}
+
+/// Canonical ids of the providers referenced anywhere: agent models of every config, models in the
+/// global defaults (e.g. `sisyphus_agent`) and the global fallback chains
+pub fn used_providers(configs: &[OhMyOpenCodeConfig], global: &OhMyOpenCodeGlobalConfig) -> HashSet<String> {
+    let mut used = HashSet::new();
+
+    for config in configs {
+        for agent in config.typed_agents().values() {
+            used.extend(agent.model.as_deref().and_then(model_provider));
+        }
+        if let Some(other_fields) = &config.other_fields {
+            collect_model_providers(other_fields, &mut used);
+        }
+    }
+
+    for section in [global.sisyphus_agent.as_ref(), global.other_fields.as_ref()].into_iter().flatten() {
+        collect_model_providers(section, &mut used);
+    }
+    let fallbacks = global
+        .other_fields
+        .as_ref()
+        .and_then(|fields| fields.get(PROVIDER_FALLBACKS_KEY))
+        .and_then(|v| v.as_object());
+    for (provider, chain) in fallbacks.into_iter().flatten() {
+        used.insert(canonical_provider_id(provider));
+        let chain = chain.as_array().into_iter().flatten().filter_map(|v| v.as_str());
+        used.extend(chain.map(canonical_provider_id));
+    }
+
+    used
+}
+
+/// Configured providers no config or global default refers to, in the given order
+/// Only a cleanup suggestion, nothing is removed
+pub fn find_unused_providers(
+    configs: &[OhMyOpenCodeConfig],
+    global: &OhMyOpenCodeGlobalConfig,
+    providers: &[String],
+) -> Vec<String> {
+    let used = used_providers(configs, global);
+    providers
+        .iter()
+        .filter(|provider| !used.contains(&canonical_provider_id(provider)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_unused_providers() {
+        let configs = vec![OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "model": "openai/o3" }, "explore": {} }))];
+        let global = OhMyOpenCodeGlobalConfig {
+            sisyphus_agent: Some(json!({ "planner": { "model": "anthropic/claude-sonnet-4" } })),
+            ..Default::default()
+        };
+        let providers = vec![
+            "openai".to_string(),
+            "deepseek".to_string(),
+            "anthropic".to_string(),
+        ];
+
+        assert_eq!(
+            find_unused_providers(&configs, &global, &providers),
+            vec!["deepseek".to_string()]
+        );
+    }
+}
@@ -0,0 +1,388 @@
+//! Oh My OpenCode Raw JSON Lint Module
+//!
+//! A pre-parse pass over the raw text of a config file, run before the typed parse so
+//! users get positions instead of bare serde errors. It accepts what the JSONC parser
+//! accepts (comments, trailing commas, single quotes, unquoted keys) and reports:
+//! syntax errors with line and column, duplicate keys (the parser keeps the last one
+//! silently) and unquoted keys, which suggest JSONC syntax in a `.json` file.
+//! Lines and columns are 1-based and count characters.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::validation::Severity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RawJsonIssueKind {
+    Syntax,
+    DuplicateKey,
+    UnquotedKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawJsonIssue {
+    pub kind: RawJsonIssueKind,
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// Position of a character, 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+/// Literal identifiers allowed as values
+const LITERALS: &[&str] = &["true", "false", "null", "Infinity", "NaN"];
+
+struct Scanner {
+    chars: Vec<char>,
+    index: usize,
+    line: usize,
+    column: usize,
+    issues: Vec<RawJsonIssue>,
+}
+
+/// Parsing stops at the first syntax error, it is already recorded
+type Scan<T> = Result<T, ()>;
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+impl Scanner {
+    fn new(raw: &str) -> Self {
+        Scanner {
+            chars: raw.chars().collect(),
+            index: 0,
+            line: 1,
+            column: 1,
+            issues: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.index + offset).copied()
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn issue(&mut self, kind: RawJsonIssueKind, severity: Severity, at: Position, message: String) {
+        self.issues.push(RawJsonIssue {
+            kind,
+            severity,
+            line: at.line,
+            column: at.column,
+            message,
+        });
+    }
+
+    fn error<T>(&mut self, at: Position, message: impl Into<String>) -> Scan<T> {
+        self.issue(RawJsonIssueKind::Syntax, Severity::Error, at, message.into());
+        Err(())
+    }
+
+    fn expected(&mut self, what: &str) -> Scan<()> {
+        let at = self.position();
+        match self.peek() {
+            Some(c) => self.error(at, format!("Expected {}, found '{}'", what, c)),
+            None => self.error(at, format!("Expected {}, found end of input", what)),
+        }
+    }
+
+    fn skip_trivia(&mut self) -> Scan<()> {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_whitespace() || c == '\u{feff}' => {
+                    self.bump();
+                }
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.bump();
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    let start = self.position();
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match (self.peek(), self.peek_at(1)) {
+                            (Some('*'), Some('/')) => {
+                                self.bump();
+                                self.bump();
+                                break;
+                            }
+                            (Some(_), _) => {
+                                self.bump();
+                            }
+                            (None, _) => return self.error(start, "Unterminated block comment"),
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Scan<String> {
+        let start = self.position();
+        let quote = self.bump().unwrap_or('"');
+        let mut text = String::new();
+        loop {
+            match self.bump() {
+                Some(c) if c == quote => return Ok(text),
+                Some('\\') => match self.bump() {
+                    Some(escaped) => text.push(escaped),
+                    None => return self.error(start, "Unterminated string"),
+                },
+                Some('\n') | None => return self.error(start, "Unterminated string"),
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.peek().filter(|c| is_ident_char(*c)) {
+            text.push(c);
+            self.bump();
+        }
+        text
+    }
+
+    fn number(&mut self) -> Scan<()> {
+        let start = self.position();
+        let mut text = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) {
+            text.push(c);
+            self.bump();
+        }
+        let unsigned = text.trim_start_matches(['+', '-']);
+        let hex = unsigned.strip_prefix("0x").or_else(|| unsigned.strip_prefix("0X"));
+        let valid = match hex {
+            Some(digits) => !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()),
+            // A leading digit or '.' rules out Rust's own "inf" and "nan"
+            None => {
+                ["Infinity", "NaN"].contains(&unsigned)
+                    || (unsigned.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+                        && unsigned.parse::<f64>().is_ok())
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            self.error(start, format!("Invalid number '{}'", text))
+        }
+    }
+
+    fn value(&mut self, path: &str) -> Scan<()> {
+        self.skip_trivia()?;
+        let start = self.position();
+        match self.peek() {
+            Some('{') => self.object(path),
+            Some('[') => self.array(path),
+            Some('"') | Some('\'') => self.string().map(|_| ()),
+            Some(c) if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => self.number(),
+            Some(c) if is_ident_start(c) => {
+                let ident = self.identifier();
+                if LITERALS.contains(&ident.as_str()) {
+                    Ok(())
+                } else {
+                    self.error(start, format!("Unexpected identifier '{}', strings must be quoted", ident))
+                }
+            }
+            _ => self.expected("a value"),
+        }
+    }
+
+    /// After an entry: a separator, the closing bracket, or a missing-comma error
+    /// reported right after the previous entry, where the comma belongs
+    fn separator(&mut self, close: char, entry_end: Position) -> Scan<bool> {
+        self.skip_trivia()?;
+        match self.peek() {
+            Some(',') => {
+                self.bump();
+                Ok(true)
+            }
+            Some(c) if c == close => {
+                self.bump();
+                Ok(false)
+            }
+            Some(c) if matches!(c, '"' | '\'' | '{' | '[') || is_ident_char(c) || c == '-' => {
+                self.error(entry_end, format!("Missing ',' before '{}'", c))
+            }
+            _ => self.expected(&format!("',' or '{}'", close)).map(|_| false),
+        }
+    }
+
+    fn object(&mut self, path: &str) -> Scan<()> {
+        self.bump();
+        let mut keys: HashMap<String, Position> = HashMap::new();
+        loop {
+            self.skip_trivia()?;
+            let key_at = self.position();
+            let key = match self.peek() {
+                Some('}') => {
+                    self.bump();
+                    return Ok(());
+                }
+                Some('"') | Some('\'') => self.string()?,
+                Some(c) if is_ident_start(c) => {
+                    let key = self.identifier();
+                    self.issue(
+                        RawJsonIssueKind::UnquotedKey,
+                        Severity::Warning,
+                        key_at,
+                        format!("Unquoted key '{}' is JSONC syntax, quote it for plain JSON", key),
+                    );
+                    key
+                }
+                _ => return self.expected("a key or '}'"),
+            };
+
+            let key_path = join_path(path, &key);
+            if let Some(first) = keys.get(&key) {
+                let message = format!(
+                    "Duplicate key '{}', first defined at {}:{}, the last value wins",
+                    key_path, first.line, first.column
+                );
+                self.issue(RawJsonIssueKind::DuplicateKey, Severity::Warning, key_at, message);
+            } else {
+                keys.insert(key, key_at);
+            }
+
+            self.skip_trivia()?;
+            if self.peek() != Some(':') {
+                return self.expected("':' after key");
+            }
+            self.bump();
+            self.value(&key_path)?;
+            let entry_end = self.position();
+            if !self.separator('}', entry_end)? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn array(&mut self, path: &str) -> Scan<()> {
+        self.bump();
+        let mut index = 0;
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(());
+            }
+            self.value(&join_path(path, &index.to_string()))?;
+            index += 1;
+            let entry_end = self.position();
+            if !self.separator(']', entry_end)? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn document(&mut self) -> Scan<()> {
+        self.skip_trivia()?;
+        if self.peek().is_none() {
+            let at = self.position();
+            return self.error(at, "Empty document");
+        }
+        self.value("")?;
+        self.skip_trivia()?;
+        if self.peek().is_some() {
+            let at = self.position();
+            return self.error(at, "Unexpected content after the end of the document");
+        }
+        Ok(())
+    }
+}
+
+/// Lint the raw text of a config file, issues in text order
+/// Scanning stops at the first syntax error
+pub fn lint_raw_json(raw: &str) -> Vec<RawJsonIssue> {
+    let mut scanner = Scanner::new(raw);
+    let _ = scanner.document();
+    scanner.issues
+}
+
+/// Lint, then parse with the JSONC parser
+/// A parse failure is reported with the position of the first syntax error found by the lint,
+/// other issues are logged
+pub fn parse_linted(raw: &str) -> Result<Value, String> {
+    let issues = lint_raw_json(raw);
+    for issue in issues.iter().filter(|i| i.severity != Severity::Error) {
+        eprintln!("Config lint at {}:{}: {}", issue.line, issue.column, issue.message);
+    }
+    json5::from_str(raw).map_err(|e| match issues.iter().find(|i| i.severity == Severity::Error) {
+        Some(issue) => format!("line {}, column {}: {}", issue.line, issue.column, issue.message),
+        None => e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_comma_reports_line() {
+        let raw = "{\n  \"agents\": {\n    \"oracle\": { \"model\": \"openai/o3\" }\n    \"explore\": {}\n  }\n}\n";
+        let issues = lint_raw_json(raw);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, RawJsonIssueKind::Syntax);
+        assert_eq!((issues[0].line, issues[0].column), (3, 39));
+        assert!(parse_linted(raw).unwrap_err().starts_with("line 3, column 39:"));
+    }
+
+    #[test]
+    fn test_duplicate_and_unquoted_keys() {
+        let raw = "{\n  // team settings\n  \"lsp\": {},\n  disabled_mcps: [\"websearch\",],\n  \"lsp\": { \"rust\": {} },\n}";
+        let issues = lint_raw_json(raw);
+        let kinds: Vec<(RawJsonIssueKind, usize)> = issues.iter().map(|i| (i.kind, i.line)).collect();
+        assert_eq!(
+            kinds,
+            vec![(RawJsonIssueKind::UnquotedKey, 4), (RawJsonIssueKind::DuplicateKey, 5)]
+        );
+        assert!(issues[1].message.contains("first defined at 3:3"));
+        assert!(parse_linted(raw).is_ok());
+    }
+}
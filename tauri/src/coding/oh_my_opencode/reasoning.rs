@@ -0,0 +1,181 @@
+//! Oh My OpenCode Reasoning Settings Module
+//!
+//! `reasoning_effort` / `thinking_budget` of agents: dropped at render time for models
+//! known not to support them, and validated before apply.
+
+use serde_json::Value;
+
+use super::agent::{self, AgentProfile};
+use super::inheritance;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+use crate::coding::open_code::model_metadata::model_supports_reasoning;
+
+const REASONING_RULE: &str = "reasoning";
+
+/// Smallest thinking budget providers accept
+pub const MIN_THINKING_BUDGET: u32 = 1024;
+
+/// Agent keys holding reasoning settings, including the camelCase aliases of older configs
+const REASONING_FIELDS: &[&str] = &["reasoning_effort", "thinking_budget", "reasoningEffort", "thinkingBudget"];
+
+/// Remove reasoning settings from agents whose model does not support them
+/// Agents with an unknown model keep their settings
+pub fn strip_unsupported_reasoning(agents: &mut Value) {
+    strip_unsupported_reasoning_with(agents, &model_supports_reasoning)
+}
+
+fn strip_unsupported_reasoning_with(agents: &mut Value, supports: &dyn Fn(&str) -> Option<bool>) {
+    let Some(agents) = agents.as_object_mut() else {
+        return;
+    };
+    for agent in agents.values_mut().filter_map(|a| a.as_object_mut()) {
+        let unsupported = agent
+            .get("model")
+            .and_then(|m| m.as_str())
+            .map(|model| supports(model) == Some(false))
+            .unwrap_or(false);
+        if unsupported {
+            for field in REASONING_FIELDS {
+                agent.remove(*field);
+            }
+        }
+    }
+}
+
+fn has_reasoning_settings(agent: &AgentProfile) -> bool {
+    agent.reasoning_effort.is_some() || agent.thinking_budget.is_some()
+}
+
+fn validate_reasoning_with(
+    profile: &OhMyOpenCodeConfig,
+    supports: &dyn Fn(&str) -> Option<bool>,
+) -> Vec<ValidationIssue> {
+    let parsed = profile.typed_agents();
+    let agents = inheritance::resolve_agents(&parsed).unwrap_or(parsed);
+    let mut issues = Vec::new();
+
+    for (name, agent) in &agents {
+        // A value the typed field rejects is kept raw in `extra`, the other fields stay typed
+        if let Some(effort) = agent.extra.get("reasoning_effort").or_else(|| agent.extra.get("reasoningEffort")) {
+            issues.push(ValidationIssue::new(
+                REASONING_RULE,
+                Severity::Error,
+                format!("agents.{}.reasoning_effort", name),
+                format!(
+                    "Agent '{}' has invalid reasoning_effort {}, expected low, medium or high",
+                    name, effort
+                ),
+            ));
+        }
+        if let Some(budget) = agent.extra.get("thinking_budget").or_else(|| agent.extra.get("thinkingBudget")) {
+            issues.push(ValidationIssue::new(
+                REASONING_RULE,
+                Severity::Error,
+                format!("agents.{}.thinking_budget", name),
+                format!(
+                    "Agent '{}' has invalid thinking_budget {}, expected a whole number of tokens",
+                    name, budget
+                ),
+            ));
+        }
+        if let Some(budget) = agent.thinking_budget.filter(|b| *b < MIN_THINKING_BUDGET) {
+            issues.push(ValidationIssue::new(
+                REASONING_RULE,
+                Severity::Error,
+                format!("agents.{}.thinking_budget", name),
+                format!(
+                    "Agent '{}' has thinking_budget {}, minimum is {}",
+                    name, budget, MIN_THINKING_BUDGET
+                ),
+            ));
+        }
+        if let Some(model) = agent.model.as_deref().filter(|_| has_reasoning_settings(agent)) {
+            if supports(model) == Some(false) {
+                issues.push(ValidationIssue::new(
+                    REASONING_RULE,
+                    Severity::Warning,
+                    format!("agents.{}", name),
+                    format!(
+                        "Model '{}' of agent '{}' does not support reasoning settings, they will not be written",
+                        model, name
+                    ),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+/// Validation rule: reasoning settings must be valid and set on models that support them
+pub fn validate_reasoning_settings(
+    _global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    validate_reasoning_with(profile, &model_supports_reasoning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::ReasoningEffort;
+    use serde_json::json;
+
+    fn supports(model: &str) -> Option<bool> {
+        match model {
+            "openai/o3" => Some(true),
+            "openai/gpt-4o" => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_valid_reasoning_setting() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "oracle": { "model": "openai/o3", "reasoning_effort": "high", "thinkingBudget": 8000 }
+        }));
+        let agents = config.typed_agents();
+        assert_eq!(agents["oracle"].reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(agents["oracle"].thinking_budget, Some(8000));
+        assert!(validate_reasoning_with(&config, &supports).is_empty());
+
+        let mut rendered = agent::agents_to_value(&agents);
+        strip_unsupported_reasoning_with(&mut rendered, &supports);
+        assert_eq!(rendered["oracle"]["reasoning_effort"], json!("high"));
+        assert_eq!(rendered["oracle"]["thinking_budget"], json!(8000));
+    }
+
+    #[test]
+    fn test_invalid_reasoning_values() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "oracle": { "model": "openai/o3", "reasoning_effort": "extreme", "thinking_budget": "lots" },
+            "explore": { "model": "openai/o3", "thinkingBudget": 512 }
+        }));
+        let issues = validate_reasoning_with(&config, &supports);
+        let paths: Vec<&str> = issues.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "agents.oracle.reasoning_effort",
+                "agents.oracle.thinking_budget",
+                "agents.explore.thinking_budget",
+            ]
+        );
+        assert!(issues.iter().all(|i| i.severity == Severity::Error));
+    }
+
+    #[test]
+    fn test_reasoning_on_unsupported_model() {
+        let config = OhMyOpenCodeConfig::test_with_agents(json!({
+            "explore": { "model": "openai/gpt-4o", "reasoning_effort": "low" }
+        }));
+        let issues = validate_reasoning_with(&config, &supports);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].path, "agents.explore");
+
+        let mut rendered = config.agents_value().unwrap();
+        strip_unsupported_reasoning_with(&mut rendered, &supports);
+        assert_eq!(rendered, json!({ "explore": { "model": "openai/gpt-4o" } }));
+    }
+}
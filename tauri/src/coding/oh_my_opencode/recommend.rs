@@ -0,0 +1,172 @@
+//! Oh My OpenCode Model Recommendation Module
+//!
+//! Advisory ranking of available models for an agent. A model is scored on the
+//! capabilities the agent needs (tools and vision, inferred like the capability
+//! rule does), its context window and its price. A role hint shifts the weight
+//! toward reasoning or toward cheap, fast models.
+
+use serde::Serialize;
+
+use crate::coding::open_code::model_metadata::ModelCapability;
+use crate::coding::open_code::models_api::FetchedModel;
+
+use super::agent::AgentProfile;
+use super::capabilities::{enables_tools, uses_vision};
+
+/// Length of the shortlist
+const MAX_RECOMMENDATIONS: usize = 5;
+/// A model without a required capability sinks below every model that has it
+const MISSING_CAPABILITY_PENALTY: f64 = 100.0;
+const UNKNOWN_CAPABILITY_PENALTY: f64 = 10.0;
+/// Context windows count up to this size, larger ones do not help an agent
+const CONTEXT_CAP: u64 = 256_000;
+
+const REASONING_HINTS: &[&str] = &["reason", "plan", "review", "architect", "oracle", "deep"];
+const SPEED_HINTS: &[&str] = &["fast", "speed", "quick", "cheap", "explore", "search"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RoleBias {
+    Balanced,
+    Reasoning,
+    Speed,
+}
+
+fn role_bias(role_hint: Option<&str>) -> RoleBias {
+    let Some(hint) = role_hint.map(|h| h.to_lowercase()) else {
+        return RoleBias::Balanced;
+    };
+    if REASONING_HINTS.iter().any(|h| hint.contains(h)) {
+        RoleBias::Reasoning
+    } else if SPEED_HINTS.iter().any(|h| hint.contains(h)) {
+        RoleBias::Speed
+    } else {
+        RoleBias::Balanced
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelRecommendation {
+    pub model_id: String,
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+fn score_model(
+    model: &FetchedModel,
+    required: &[(ModelCapability, &str)],
+    bias: RoleBias,
+) -> ModelRecommendation {
+    let metadata = model.metadata.clone().unwrap_or_default();
+    let mut score = 0.0;
+    let mut reasons = Vec::new();
+
+    for (capability, label) in required {
+        match &metadata.capabilities {
+            Some(capabilities) if capabilities.contains(capability) => reasons.push(format!("supports {}", label)),
+            Some(_) => {
+                score -= MISSING_CAPABILITY_PENALTY;
+                reasons.push(format!("does not support {}", label));
+            }
+            None => {
+                score -= UNKNOWN_CAPABILITY_PENALTY;
+                reasons.push(format!("{} support unknown", label));
+            }
+        }
+    }
+
+    if let Some(context) = metadata.context_window {
+        // 0 to 10 points, linear up to the cap
+        score += 10.0 * context.min(CONTEXT_CAP) as f64 / CONTEXT_CAP as f64;
+        reasons.push(format!("{}k context", context / 1000));
+    }
+
+    if let (Some(input), Some(output)) = (metadata.input_cost, metadata.output_cost) {
+        let price_weight = if bias == RoleBias::Speed { 20.0 } else { 10.0 };
+        // Free models get the full weight, $10 per million tokens (in + out) about half
+        score += price_weight * 10.0 / (10.0 + input + output);
+        reasons.push(format!("${} / ${} per million tokens", input, output));
+    }
+
+    match (bias, metadata.supports_reasoning) {
+        (RoleBias::Reasoning, Some(true)) => {
+            score += 15.0;
+            reasons.push("reasoning model".to_string());
+        }
+        (RoleBias::Speed, Some(true)) => score -= 5.0,
+        _ => {}
+    }
+
+    ModelRecommendation {
+        model_id: model.id.clone(),
+        score,
+        reasons,
+    }
+}
+
+/// Ranked shortlist of `available` models for `agent`, best first
+/// Ties keep the order of `available`
+pub fn recommend_models(
+    agent: &AgentProfile,
+    agent_name: &str,
+    available: &[FetchedModel],
+    role_hint: Option<String>,
+) -> Vec<ModelRecommendation> {
+    let mut required = Vec::new();
+    if enables_tools(agent) {
+        required.push((ModelCapability::Tool, "tools"));
+    }
+    if uses_vision(agent_name, agent) {
+        required.push((ModelCapability::Vision, "images"));
+    }
+    let bias = role_bias(role_hint.as_deref());
+
+    let mut ranked: Vec<ModelRecommendation> =
+        available.iter().map(|model| score_model(model, &required, bias)).collect();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked.truncate(MAX_RECOMMENDATIONS);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::open_code::model_metadata::ModelMetadata;
+    use indexmap::IndexMap;
+
+    fn model(id: &str, capabilities: Vec<ModelCapability>, context: u64, cost: f64) -> FetchedModel {
+        FetchedModel {
+            id: id.to_string(),
+            name: None,
+            owned_by: None,
+            created: None,
+            metadata: Some(ModelMetadata {
+                context_window: Some(context),
+                input_cost: Some(cost),
+                output_cost: Some(cost),
+                capabilities: Some(capabilities),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_tool_agent_ranks_tool_models_first() {
+        let agent = AgentProfile {
+            tools: Some(IndexMap::from([("bash".to_string(), true)])),
+            ..Default::default()
+        };
+        let available = vec![
+            // Cheapest with the largest context, but no tool calling
+            model("tiny-chat", Vec::new(), 1_000_000, 0.0),
+            model("gpt-4o", vec![ModelCapability::Tool, ModelCapability::Vision], 128_000, 5.0),
+            model("gpt-4o-mini", vec![ModelCapability::Tool], 128_000, 0.5),
+        ];
+
+        let ranked = recommend_models(&agent, "coder", &available, None);
+        let ids: Vec<&str> = ranked.iter().map(|r| r.model_id.as_str()).collect();
+        assert_eq!(ids, vec!["gpt-4o-mini", "gpt-4o", "tiny-chat"]);
+        assert!(ranked[0].reasons.contains(&"supports tools".to_string()));
+        assert!(ranked[2].reasons.contains(&"does not support tools".to_string()));
+    }
+}
@@ -0,0 +1,114 @@
+//! Oh My OpenCode Render Module
+//!
+//! Builds the final oh-my-opencode.json content from the global config and an agents profile.
+
+use serde_json::Value;
+
+use super::adapter;
+use super::agent;
+use super::config_env;
+use super::inheritance;
+use super::prompt_library;
+use super::reasoning;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+pub const DEFAULT_SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/code-yeongyu/oh-my-opencode/master/assets/oh-my-opencode.schema.json";
+
+/// Expand `extends` and inline `prompt_ref` in the `agents` JSON of a profile
+/// On a broken chain the agents are kept as-is, `extends` is stripped with the UI fields
+fn resolve_agents_value(agents: &Value, global_config: &OhMyOpenCodeGlobalConfig) -> Value {
+    let parsed = agent::parse_agents(Some(agents));
+    let needs_inheritance = inheritance::has_inheritance(&parsed);
+    if !needs_inheritance && !prompt_library::has_prompt_refs(&parsed) {
+        return agents.clone();
+    }
+
+    let mut resolved = if needs_inheritance {
+        match inheritance::resolve_agents(&parsed) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                eprintln!("Failed to resolve agent inheritance: {}", e);
+                parsed
+            }
+        }
+    } else {
+        parsed
+    };
+    prompt_library::resolve_prompt_refs(&mut resolved, prompt_library::prompt_library(global_config));
+    agent::agents_to_value(&resolved)
+}
+
+/// Render the oh-my-opencode.json value for a profile
+///
+/// 合并配置的优先级顺序（从低到高）：
+/// 1. 全局配置的明确字段（最低优先级）
+/// 2. 全局配置的 other_fields
+/// 3. Agents Profile 的 agents
+/// 4. Agents Profile 的 other_fields（最高优先级，可以覆盖所有）
+pub fn render_opencode_json(
+    global_config: &OhMyOpenCodeGlobalConfig,
+    agents_profile: &OhMyOpenCodeConfig,
+) -> Value {
+    let mut final_json = serde_json::Map::new();
+
+    // 使用保存的 schema 或默认 schema
+    let schema_url = global_config
+        .schema
+        .clone()
+        .unwrap_or_else(|| DEFAULT_SCHEMA_URL.to_string());
+    final_json.insert("$schema".to_string(), serde_json::json!(schema_url));
+
+    // 1. 先设置全局配置的明确字段（优先级最低）
+    if let Some(sisyphus) = &global_config.sisyphus_agent {
+        final_json.insert("sisyphus_agent".to_string(), sisyphus.clone());
+    }
+    if let Some(disabled_agents) = &global_config.disabled_agents {
+        final_json.insert("disabled_agents".to_string(), serde_json::json!(disabled_agents));
+    }
+    if let Some(disabled_mcps) = &global_config.disabled_mcps {
+        final_json.insert("disabled_mcps".to_string(), serde_json::json!(disabled_mcps));
+    }
+    if let Some(disabled_hooks) = &global_config.disabled_hooks {
+        final_json.insert("disabled_hooks".to_string(), serde_json::json!(disabled_hooks));
+    }
+    if let Some(lsp) = &global_config.lsp {
+        final_json.insert("lsp".to_string(), lsp.clone());
+    }
+    if let Some(experimental) = &global_config.experimental {
+        final_json.insert("experimental".to_string(), experimental.clone());
+    }
+
+    // 2. 然后平铺全局配置的 other_fields（会覆盖上面的明确字段）
+    if let Some(others_obj) = global_config.other_fields.as_ref().and_then(|v| v.as_object()) {
+        for (key, value) in others_obj {
+            final_json.insert(key.clone(), value.clone());
+        }
+    }
+
+    // 3. 设置 Agents Profile 的 agents（会覆盖前面的 agents）
+    if let Some(agents) = &agents_profile.agents {
+        let mut agents = resolve_agents_value(&agent::agents_to_value(agents), global_config);
+        agent::strip_ui_fields(&mut agents);
+        reasoning::strip_unsupported_reasoning(&mut agents);
+        final_json.insert("agents".to_string(), agents);
+    }
+
+    // 4. 最后平铺 Agents Profile 的 other_fields（最高优先级，可以覆盖所有字段）
+    if let Some(others_obj) = agents_profile.other_fields.as_ref().and_then(|v| v.as_object()) {
+        for (key, value) in others_obj {
+            final_json.insert(key.clone(), value.clone());
+        }
+    }
+
+    // prompt library 和配置级 env 只在 AI Toolbox 内使用，不写入文件
+    final_json.remove(prompt_library::PROMPT_LIBRARY_KEY);
+    final_json.remove(config_env::CONFIG_ENV_KEY);
+
+    let mut final_json = Value::Object(final_json);
+
+    // 清理空值：删除空对象和 null 值
+    adapter::clean_empty_values(&mut final_json);
+
+    final_json
+}
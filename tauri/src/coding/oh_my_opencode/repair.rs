@@ -0,0 +1,295 @@
+//! Oh My OpenCode Repair Module
+//!
+//! Safe fixes for common mistakes in oh-my-opencode.json content. Every fix keeps
+//! what the config means: whitespace around model ids, duplicate or blank entries of
+//! the disabled lists, names in `disabled_agents`/`disabled_mcps` that match nothing,
+//! BOM and control characters in prompts, and trailing slashes or whitespace in base
+//! URLs. Repairing twice gives the same result as once.
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Prompt fields of an agent
+const PROMPT_FIELDS: &[&str] = &["prompt", "prompt_append"];
+/// Keys holding a provider base URL
+const BASE_URL_KEYS: &[&str] = &["baseURL", "baseUrl", "base_url"];
+/// Disabled lists whose names are checked, with the content section defining entries
+const NAMED_DISABLED_LISTS: &[(&str, &str)] = &[("disabled_agents", "agents"), ("disabled_mcps", "mcp")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RepairKind {
+    TrimModelId,
+    DedupDisabled,
+    PruneDisabled,
+    StripPromptChars,
+    NormalizeBaseUrl,
+}
+
+/// One fix, `path` is a dotted path into the content
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairAction {
+    pub kind: RepairKind,
+    pub path: String,
+    pub message: String,
+}
+
+impl RepairAction {
+    fn new(kind: RepairKind, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// BOM and control characters other than newline, carriage return and tab
+fn is_stripped_char(c: char) -> bool {
+    c == '\u{feff}' || (c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+}
+
+fn repair_agents(agents: &mut Value, actions: &mut Vec<RepairAction>) {
+    let Some(agents) = agents.as_object_mut() else {
+        return;
+    };
+    for (name, agent) in agents.iter_mut() {
+        let Some(agent) = agent.as_object_mut() else {
+            continue;
+        };
+        if let Some(Value::String(model)) = agent.get_mut("model") {
+            let trimmed = model.trim();
+            if trimmed.len() != model.len() {
+                *model = trimmed.to_string();
+                actions.push(RepairAction::new(
+                    RepairKind::TrimModelId,
+                    format!("agents.{}.model", name),
+                    format!("Trimmed whitespace around model '{}'", model),
+                ));
+            }
+        }
+        for field in PROMPT_FIELDS {
+            if let Some(Value::String(prompt)) = agent.get_mut(*field) {
+                let stripped = prompt.chars().filter(|c| is_stripped_char(*c)).count();
+                if stripped > 0 {
+                    prompt.retain(|c| !is_stripped_char(c));
+                    actions.push(RepairAction::new(
+                        RepairKind::StripPromptChars,
+                        format!("agents.{}.{}", name, field),
+                        format!("Removed {} BOM or control character(s)", stripped),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Built-in names the schema allows in a disabled list (`items.enum`), `None` when it lists none
+fn builtin_names<'a>(schema: Option<&'a Value>, key: &str) -> Option<Vec<&'a str>> {
+    let names = schema?
+        .get("properties")?
+        .get(key)?
+        .pointer("/items/enum")?
+        .as_array()?;
+    Some(names.iter().filter_map(|v| v.as_str()).collect())
+}
+
+/// Drop names of `disabled_agents`/`disabled_mcps` that are neither defined in the content
+/// nor built in; without the built-in names from the schema, every name is kept
+fn prune_unknown_names(content: &mut Value, schema: Option<&Value>, actions: &mut Vec<RepairAction>) {
+    for (key, section) in NAMED_DISABLED_LISTS {
+        let Some(builtin) = builtin_names(schema, key) else {
+            continue;
+        };
+        let defined: Vec<String> = content
+            .get(*section)
+            .and_then(|v| v.as_object())
+            .map(|entries| entries.keys().cloned().collect())
+            .unwrap_or_default();
+        let Some(list) = content.get_mut(*key).and_then(|v| v.as_array_mut()) else {
+            continue;
+        };
+        let mut pruned = Vec::new();
+        list.retain(|entry| {
+            let name = entry.as_str().unwrap_or_default();
+            let known = builtin.contains(&name) || defined.iter().any(|d| d == name);
+            if !known {
+                pruned.push(name.to_string());
+            }
+            known
+        });
+        if !pruned.is_empty() {
+            actions.push(RepairAction::new(
+                RepairKind::PruneDisabled,
+                *key,
+                format!("Removed unknown name(s): {}", pruned.join(", ")),
+            ));
+        }
+    }
+}
+
+/// Dedup a `disabled_*` list in place, keeping the first occurrence
+/// Non-string and blank entries name nothing and are dropped
+fn repair_disabled_list(key: &str, list: &mut Vec<Value>, actions: &mut Vec<RepairAction>) {
+    let before = list.len();
+    list.retain(|entry| entry.as_str().is_some_and(|name| !name.trim().is_empty()));
+    if list.len() != before {
+        actions.push(RepairAction::new(
+            RepairKind::PruneDisabled,
+            key,
+            format!("Removed {} blank or non-string entr(ies)", before - list.len()),
+        ));
+    }
+
+    let before = list.len();
+    let mut seen: Vec<Value> = Vec::new();
+    list.retain(|entry| {
+        if seen.contains(entry) {
+            return false;
+        }
+        seen.push(entry.clone());
+        true
+    });
+    if list.len() != before {
+        actions.push(RepairAction::new(
+            RepairKind::DedupDisabled,
+            key,
+            format!("Removed {} duplicate entr(ies)", before - list.len()),
+        ));
+    }
+}
+
+fn repair_base_urls(value: &mut Value, path: &str, actions: &mut Vec<RepairAction>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                let child_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                if let (true, Value::String(url)) = (BASE_URL_KEYS.contains(&key.as_str()), &mut *child) {
+                    // Strip until stable, `https://x/ /` needs the slash and the space gone
+                    let normalized = url.trim().trim_end_matches(|c: char| c == '/' || c.is_whitespace());
+                    if normalized.len() != url.len() && !normalized.is_empty() {
+                        *url = normalized.to_string();
+                        actions.push(RepairAction::new(
+                            RepairKind::NormalizeBaseUrl,
+                            child_path,
+                            format!("Normalized base URL to '{}'", url),
+                        ));
+                    }
+                    continue;
+                }
+                repair_base_urls(child, &child_path, actions);
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                repair_base_urls(item, &format!("{}.{}", path, index), actions);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Apply every safe fix to oh-my-opencode.json content, returning the fixed content
+/// and the fixes made, in content order
+/// `schema` is the oh-my-opencode schema, it knows the built-in agent and MCP names
+pub fn auto_repair(content: Value, schema: Option<&Value>) -> (Value, Vec<RepairAction>) {
+    let mut content = content;
+    let mut actions = Vec::new();
+    let Some(map) = content.as_object_mut() else {
+        return (content, actions);
+    };
+
+    if let Some(agents) = map.get_mut("agents") {
+        repair_agents(agents, &mut actions);
+    }
+    for (key, value) in map.iter_mut() {
+        if let (true, Value::Array(list)) = (key.starts_with("disabled_"), value) {
+            repair_disabled_list(key, list, &mut actions);
+        }
+    }
+    prune_unknown_names(&mut content, schema, &mut actions);
+    repair_base_urls(&mut content, "", &mut actions);
+
+    (content, actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_auto_repair_is_idempotent() {
+        let content = json!({
+            "agents": {
+                "oracle": { "model": " openai/o3\n", "prompt": "\u{feff}Think\u{0007} hard.\nReally." },
+                "librarian": { "model": "anthropic/claude-sonnet-4" }
+            },
+            "disabled_mcps": ["websearch", "", "websearch", 3, "context7"],
+            "disabled_hooks": ["comment-checker"],
+            "provider": {
+                "acme": { "options": { "baseURL": " https://api.acme.dev/v1// " } },
+                "spaced": { "options": { "baseURL": "https://x/ /" } }
+            }
+        });
+
+        let (repaired, actions) = auto_repair(content, None);
+        assert_eq!(repaired["agents"]["oracle"]["model"], json!("openai/o3"));
+        assert_eq!(repaired["agents"]["oracle"]["prompt"], json!("Think hard.\nReally."));
+        assert_eq!(repaired["disabled_mcps"], json!(["websearch", "context7"]));
+        assert_eq!(repaired["disabled_hooks"], json!(["comment-checker"]));
+        assert_eq!(repaired["provider"]["acme"]["options"]["baseURL"], json!("https://api.acme.dev/v1"));
+        assert_eq!(repaired["provider"]["spaced"]["options"]["baseURL"], json!("https://x"));
+
+        let kinds: Vec<RepairKind> = actions.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RepairKind::TrimModelId,
+                RepairKind::StripPromptChars,
+                RepairKind::PruneDisabled,
+                RepairKind::DedupDisabled,
+                RepairKind::NormalizeBaseUrl,
+                RepairKind::NormalizeBaseUrl,
+            ]
+        );
+        assert_eq!(actions[4].path, "provider.acme.options.baseURL");
+
+        let (again, actions) = auto_repair(repaired.clone(), None);
+        assert_eq!(again, repaired);
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn test_auto_repair_prunes_unknown_disabled_names() {
+        let schema = json!({
+            "properties": {
+                "disabled_agents": { "type": "array", "items": { "type": "string", "enum": ["oracle", "explore"] } },
+                "disabled_mcps": { "type": "array", "items": { "type": "string", "enum": ["context7"] } }
+            }
+        });
+        let content = json!({
+            "agents": { "reviewer": { "model": "openai/o3" } },
+            "mcp": { "github": { "type": "local" } },
+            "disabled_agents": ["oracle", "reviewer", "oracel"],
+            "disabled_mcps": ["context7", "github", "websearch"],
+            "disabled_hooks": ["made-up-hook"]
+        });
+
+        let (repaired, actions) = auto_repair(content.clone(), Some(&schema));
+        assert_eq!(repaired["disabled_agents"], json!(["oracle", "reviewer"]));
+        assert_eq!(repaired["disabled_mcps"], json!(["context7", "github"]));
+        assert_eq!(repaired["disabled_hooks"], json!(["made-up-hook"]));
+        let paths: Vec<&str> = actions.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(paths, vec!["disabled_agents", "disabled_mcps"]);
+
+        let (again, actions) = auto_repair(repaired.clone(), Some(&schema));
+        assert_eq!(again, repaired);
+        assert!(actions.is_empty());
+
+        // Without built-in names nothing can be told apart
+        let (unchanged, _) = auto_repair(content.clone(), None);
+        assert_eq!(unchanged, content);
+    }
+}
@@ -0,0 +1,123 @@
+//! Oh My OpenCode SARIF Module
+//!
+//! Validation results as a SARIF 2.1.0 log, for code-scanning dashboards in CI.
+//! Configs are not files, so a location is the config id as artifact plus the
+//! dotted path of the issue as logical location.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::validation::{Severity, ValidationIssue};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "ai-toolbox";
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// One run with every issue of every config (config id -> issues), configs in id order
+/// Rules are listed in order of first use and referenced by index
+pub fn validation_to_sarif(results: &HashMap<String, Vec<ValidationIssue>>) -> Value {
+    let mut config_ids: Vec<&String> = results.keys().collect();
+    config_ids.sort();
+
+    let mut rules: Vec<&str> = Vec::new();
+    let mut sarif_results = Vec::new();
+    for config_id in config_ids {
+        for issue in &results[config_id] {
+            let rule_index = match rules.iter().position(|rule| *rule == issue.rule) {
+                Some(index) => index,
+                None => {
+                    rules.push(&issue.rule);
+                    rules.len() - 1
+                }
+            };
+            sarif_results.push(json!({
+                "ruleId": issue.rule,
+                "ruleIndex": rule_index,
+                "level": sarif_level(issue.severity),
+                "message": { "text": issue.message },
+                "locations": [{
+                    "physicalLocation": { "artifactLocation": { "uri": config_id } },
+                    "logicalLocations": [{
+                        "name": issue.path,
+                        "fullyQualifiedName": format!("{}:{}", config_id, issue.path),
+                        "kind": "member"
+                    }]
+                }]
+            }));
+        }
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<_>>()
+                }
+            },
+            "results": sarif_results
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_to_sarif() {
+        let results = HashMap::from([
+            (
+                "work".to_string(),
+                vec![
+                    ValidationIssue::new("prompt-ref", Severity::Error, "agents.oracle.prompt_ref", "Unknown prompt 'x'"),
+                    ValidationIssue::new("unused-mcp", Severity::Info, "mcp.github", "Unused MCP server"),
+                ],
+            ),
+            (
+                "home".to_string(),
+                vec![ValidationIssue::new("unused-mcp", Severity::Warning, "mcp.slack", "Unused MCP server")],
+            ),
+            ("clean".to_string(), Vec::new()),
+        ]);
+
+        let sarif = validation_to_sarif(&results);
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"], json!([{ "id": "unused-mcp" }, { "id": "prompt-ref" }]));
+
+        let summary: Vec<(&str, &str, u64, &str)> = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                (
+                    r["ruleId"].as_str().unwrap(),
+                    r["level"].as_str().unwrap(),
+                    r["ruleIndex"].as_u64().unwrap(),
+                    r["locations"][0]["logicalLocations"][0]["fullyQualifiedName"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("unused-mcp", "warning", 0, "home:mcp.slack"),
+                ("prompt-ref", "error", 1, "work:agents.oracle.prompt_ref"),
+                ("unused-mcp", "note", 0, "work:mcp.github"),
+            ]
+        );
+        assert_eq!(run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "home");
+    }
+}
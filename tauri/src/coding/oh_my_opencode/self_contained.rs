@@ -0,0 +1,158 @@
+//! Oh My OpenCode Self-Contained Export Module
+//!
+//! Export a profile with every reference to the global config inlined, so it can be
+//! shared and imported on its own.
+
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+use super::agent;
+use super::config_env::{self, CONFIG_ENV_KEY};
+use super::inheritance;
+use super::mcp::{mcp_servers, tool_key_references_mcp};
+use super::prompt_library;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeConfigContent, OhMyOpenCodeGlobalConfig};
+use crate::coding::redact::{is_secret_key, mask_secret_values, redact_secrets};
+
+/// Inline inheritance, prompt refs and config-level env, and copy the global sections
+/// the profile depends on (referenced MCP servers, hooks, LSP) into `other_fields`
+/// Secrets are redacted
+pub fn export_self_contained(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> OhMyOpenCodeConfigContent {
+    let env = config_env::config_env(profile);
+
+    let parsed = profile.typed_agents();
+    let mut agents = inheritance::resolve_agents(&parsed).unwrap_or_else(|e| {
+        eprintln!("Failed to resolve agent inheritance: {}", e);
+        parsed
+    });
+    prompt_library::resolve_prompt_refs(&mut agents, prompt_library::prompt_library(global));
+
+    let tool_keys: Vec<String> = agents
+        .values()
+        .flat_map(|agent| agent.tools.iter().flat_map(|tools| tools.keys().cloned()))
+        .collect();
+    let known_mcps: Vec<&String> = [global.other_fields.as_ref(), profile.other_fields.as_ref()]
+        .into_iter()
+        .filter_map(mcp_servers)
+        .flat_map(|servers| servers.keys())
+        .collect();
+    let is_referenced = |mcp: &str| {
+        tool_keys
+            .iter()
+            .any(|key| tool_key_references_mcp(key, mcp, &known_mcps))
+    };
+
+    let mut other_fields = profile
+        .other_fields
+        .as_ref()
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+    other_fields.remove(CONFIG_ENV_KEY);
+
+    // Referenced global MCP servers, profile definitions win
+    if let Some(global_servers) = mcp_servers(global.other_fields.as_ref()) {
+        let servers = other_fields
+            .entry("mcp")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Some(servers) = servers.as_object_mut() {
+            for (name, server) in global_servers.iter().filter(|(name, _)| is_referenced(name)) {
+                servers.entry(name.clone()).or_insert_with(|| server.clone());
+            }
+        }
+        if servers.as_object().map(|s| s.is_empty()).unwrap_or(false) {
+            other_fields.remove("mcp");
+        }
+    }
+    if let Some(hooks) = &global.disabled_hooks {
+        other_fields
+            .entry("disabled_hooks")
+            .or_insert_with(|| serde_json::json!(hooks));
+    }
+    if let Some(lsp) = &global.lsp {
+        other_fields.entry("lsp").or_insert_with(|| lsp.clone());
+    }
+
+    // Redact before folding, and only fold values that are no secrets themselves: a secret
+    // env entry stays a `{env:NAME}` placeholder instead of landing under a harmless key
+    let env: IndexMap<String, String> = env
+        .into_iter()
+        .filter(|(name, value)| !is_secret_key(name) && mask_secret_values(value) == *value)
+        .collect();
+    let mut agents = agent::agents_to_value(&agents);
+    let mut other_fields = Value::Object(other_fields);
+    for value in [&mut agents, &mut other_fields] {
+        redact_secrets(value);
+        config_env::fold_env(value, &env);
+    }
+
+    OhMyOpenCodeConfigContent {
+        name: profile.name.clone(),
+        is_applied: false,
+        agents: Some(agents),
+        other_fields: other_fields
+            .as_object()
+            .filter(|fields| !fields.is_empty())
+            .map(|_| other_fields.clone()),
+        created_at: profile.created_at.clone().unwrap_or_default(),
+        updated_at: profile.updated_at.clone().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::redact::REDACTED;
+    use serde_json::json;
+
+    #[test]
+    fn test_export_self_contained_inlines_references() {
+        let global = OhMyOpenCodeGlobalConfig {
+            lsp: Some(json!({ "rust": { "command": ["rust-analyzer"] } })),
+            other_fields: Some(json!({
+                "prompt_library": { "reviewer": "Review carefully." },
+                "mcp": {
+                    "github": { "type": "remote", "url": "https://api.github.com/mcp", "token": "ghp_secret" },
+                    "unused": { "type": "remote", "url": "https://example.com" }
+                }
+            })),
+            ..Default::default()
+        };
+        let profile = OhMyOpenCodeConfig {
+            name: "Work".to_string(),
+            is_applied: true,
+            other_fields: Some(json!({
+                "config_env": { "WORK_MODEL": "openai/o3", "SEARCH_KEY": "sk-abcdefghijklmnop" },
+                "env": { "LOG_LEVEL": "debug" },
+                "mcp": { "search": { "type": "remote", "url": "https://search.example.com/{env:SEARCH_KEY}" } }
+            })),
+            ..OhMyOpenCodeConfig::test_with_agents(json!({
+                "base": { "model": "{env:WORK_MODEL}", "tools": { "github_create_issue": true } },
+                "oracle": { "extends": "base", "prompt_ref": "reviewer" }
+            }))
+        };
+
+        let exported = export_self_contained(&global, &profile);
+        let oracle = &exported.agents.as_ref().unwrap()["oracle"];
+        assert_eq!(oracle["model"], json!("openai/o3"));
+        assert_eq!(oracle["prompt"], json!("Review carefully."));
+        assert_eq!(oracle["tools"], json!({ "github_create_issue": true }));
+        assert!(oracle.get("extends").is_none());
+        assert!(oracle.get("prompt_ref").is_none());
+
+        let other = exported.other_fields.unwrap();
+        assert!(other.get("config_env").is_none());
+        // A user's own `env` section is not the config env
+        assert_eq!(other["env"], json!({ "LOG_LEVEL": "debug" }));
+        // Secret env values are never folded in
+        assert_eq!(other["mcp"]["search"]["url"], json!("https://search.example.com/{env:SEARCH_KEY}"));
+        assert!(!serde_json::to_string(&other).unwrap().contains("sk-abcdefghijklmnop"));
+        assert_eq!(other["mcp"]["github"]["token"], json!(REDACTED));
+        assert!(other["mcp"].get("unused").is_none());
+        assert_eq!(other["lsp"], global.lsp.unwrap());
+        assert!(!exported.is_applied);
+    }
+}
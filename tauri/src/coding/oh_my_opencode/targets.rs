@@ -0,0 +1,167 @@
+//! Oh My OpenCode Targets Module
+//!
+//! Apply one config to several oh-my-opencode files, e.g. the configs of different
+//! project directories. The config is rendered once; every target is written
+//! atomically on its own, so a failing target does not stop the others.
+//! Each write is backed up and recorded in the apply log as a target apply, which
+//! neither revert nor backup compaction treat as the current apply.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::backups;
+use super::global_apply::ApplyError;
+use super::render::render_opencode_json;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+/// Result of one target, for the frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetOutcome {
+    pub target: String,
+    /// `None` when the target was written
+    pub error: Option<String>,
+}
+
+/// Whether a config value carries anything besides `$schema`
+fn has_content(value: &Value) -> bool {
+    value
+        .as_object()
+        .is_some_and(|map| map.keys().any(|key| key != "$schema"))
+}
+
+/// Refuse to replace a file that has content with a config that has none
+/// A missing or unparseable file is not protected
+pub fn guard_empty_write(path: &Path, rendered: &Value) -> Result<(), ApplyError> {
+    if has_content(rendered) {
+        return Ok(());
+    }
+    let existing = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| json5::from_str::<Value>(&content).ok());
+    if existing.as_ref().is_some_and(has_content) {
+        return Err(ApplyError::EmptyWrite(path.to_string_lossy().to_string()));
+    }
+    Ok(())
+}
+
+/// Write through a temp file in the same directory and rename it over the target
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), ApplyError> {
+    let write_error = |e: std::io::Error| ApplyError::Write {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4().simple()));
+    fs::write(&temp, content).map_err(write_error)?;
+    fs::rename(&temp, path).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        write_error(e)
+    })
+}
+
+fn apply_to_target(
+    path: &Path,
+    content: &str,
+    rendered: &Value,
+    backup_dir: &Path,
+    config_id: &str,
+) -> Result<(), ApplyError> {
+    guard_empty_write(path, rendered)?;
+    let backup_error = |e: backups::BackupError| ApplyError::Write {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    };
+    let backup = backups::backup_config(path, backup_dir, None).map_err(backup_error)?;
+    write_atomic(path, content)?;
+    backups::record_target_apply(path, backup_dir, config_id, backup).map_err(backup_error)?;
+    Ok(())
+}
+
+/// Render `profile` once and write it to every target path, in order
+/// Returns the result of every target
+pub fn apply_to_targets(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    targets: &[String],
+    backup_dir: &Path,
+) -> Vec<(String, Result<(), ApplyError>)> {
+    let rendered = render_opencode_json(global, profile);
+    let content = match serde_json::to_string_pretty(&rendered) {
+        Ok(content) => content,
+        Err(e) => {
+            return targets
+                .iter()
+                .map(|target| {
+                    let error = ApplyError::Write {
+                        path: target.clone(),
+                        message: e.to_string(),
+                    };
+                    (target.clone(), Err(error))
+                })
+                .collect();
+        }
+    };
+
+    targets
+        .iter()
+        .map(|target| {
+            let result = apply_to_target(Path::new(target), &content, &rendered, backup_dir, &profile.id);
+            (target.clone(), result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_to_targets_partial_success() {
+        let dir = std::env::temp_dir().join(format!("ai-toolbox-targets-{}", uuid::Uuid::new_v4()));
+        let backup_dir = dir.join("backups");
+        fs::create_dir_all(&dir).unwrap();
+        // A file where a directory is expected makes the second target unwritable
+        fs::write(dir.join("blocked"), "").unwrap();
+        let good = dir.join("project-a").join("oh-my-opencode.json");
+        let bad = dir.join("blocked").join("oh-my-opencode.json");
+        let targets = vec![good.to_string_lossy().to_string(), bad.to_string_lossy().to_string()];
+
+        let profile = OhMyOpenCodeConfig {
+            id: "work".to_string(),
+            name: "Work".to_string(),
+            is_applied: false,
+            agents: Some(parse_agents(Some(&json!({ "oracle": { "model": "openai/o3" } })))),
+            other_fields: None,
+            created_at: None,
+            updated_at: None,
+        };
+        let results = apply_to_targets(&OhMyOpenCodeGlobalConfig::default(), &profile, &targets, &backup_dir);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0], (targets[0].clone(), Ok(())));
+        assert!(matches!(results[1].1, Err(ApplyError::Write { .. })));
+        let written: Value = serde_json::from_str(&fs::read_to_string(&good).unwrap()).unwrap();
+        assert_eq!(written["agents"]["oracle"]["model"], json!("openai/o3"));
+
+        let log = backups::read_apply_log(&backup_dir).unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].target_path, targets[0]);
+        assert_eq!(log[0].config_id, "work");
+        assert_eq!(log[0].kind, backups::ApplyKind::Target);
+
+        // An empty profile does not wipe the file it just wrote
+        let empty = OhMyOpenCodeConfig { agents: None, ..profile };
+        let results = apply_to_targets(&OhMyOpenCodeGlobalConfig::default(), &empty, &targets[..1], &backup_dir);
+        assert_eq!(results[0].1, Err(ApplyError::EmptyWrite(targets[0].clone())));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
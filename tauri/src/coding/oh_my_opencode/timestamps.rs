@@ -0,0 +1,200 @@
+//! Oh My OpenCode Timestamps Module
+//!
+//! Rewrite timestamps to UTC RFC 3339 so they sort correctly as strings.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::types::OhMyOpenCodeConfigContent;
+
+/// Formats without offset, interpreted as local time
+const NAIVE_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampChangeKind {
+    /// Rewritten to UTC RFC 3339
+    Normalized,
+    /// Could not be parsed, left unchanged
+    Unparseable,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimestampChange {
+    pub path: String,
+    pub kind: TimestampChangeKind,
+    pub before: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+fn local_to_utc(naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Parse a timestamp in one of the supported formats
+/// RFC 3339 / RFC 2822 keep their offset, naive values are local time, digits are a Unix epoch
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc2822(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    for format in NAIVE_DATETIME_FORMATS {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+            return local_to_utc(naive);
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return local_to_utc(date.and_hms_opt(0, 0, 0)?);
+    }
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        let number: i64 = value.parse().ok()?;
+        // 13 digits and more are milliseconds
+        return if value.len() >= 13 {
+            DateTime::from_timestamp_millis(number)
+        } else {
+            DateTime::from_timestamp(number, 0)
+        };
+    }
+    None
+}
+
+/// Canonical form: UTC, `Z` suffix, fractional seconds only when present
+pub fn format_timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn normalize_field(path: String, value: &mut String, changes: &mut Vec<TimestampChange>) {
+    if value.is_empty() {
+        return;
+    }
+    match parse_timestamp(value) {
+        Some(time) => {
+            let normalized = format_timestamp(&time);
+            if normalized != *value {
+                changes.push(TimestampChange {
+                    path,
+                    kind: TimestampChangeKind::Normalized,
+                    before: std::mem::replace(value, normalized.clone()),
+                    after: Some(normalized),
+                });
+            }
+        }
+        None => changes.push(TimestampChange {
+            path,
+            kind: TimestampChangeKind::Unparseable,
+            before: value.clone(),
+            after: None,
+        }),
+    }
+}
+
+/// Only the record timestamps, other `*_at` keys (`look_at`, `stop_at`) belong to the user
+fn is_timestamp_key(key: &str) -> bool {
+    matches!(key, "created_at" | "updated_at")
+}
+
+/// Normalize string values under `created_at` / `updated_at` keys of nested sections
+fn normalize_sections(value: &mut Value, path: &str, changes: &mut Vec<TimestampChange>) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let item_path = format!("{}.{}", path, key);
+                match item {
+                    Value::String(s) if is_timestamp_key(key) => {
+                        normalize_field(item_path, s, changes)
+                    }
+                    _ => normalize_sections(item, &item_path, changes),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                normalize_sections(item, &format!("{}.{}", path, index), changes);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rewrite `created_at`, `updated_at` and section timestamps to UTC RFC 3339
+/// Already normalized values are untouched, so running it twice reports nothing the second time
+pub fn normalize_timestamps(content: &mut OhMyOpenCodeConfigContent) -> Vec<TimestampChange> {
+    let mut changes = Vec::new();
+    normalize_field("created_at".to_string(), &mut content.created_at, &mut changes);
+    normalize_field("updated_at".to_string(), &mut content.updated_at, &mut changes);
+    if let Some(agents) = content.agents.as_mut() {
+        normalize_sections(agents, "agents", &mut changes);
+    }
+    if let Some(other_fields) = content.other_fields.as_mut() {
+        normalize_sections(other_fields, "other_fields", &mut changes);
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn content(created_at: &str, updated_at: &str) -> OhMyOpenCodeConfigContent {
+        OhMyOpenCodeConfigContent {
+            name: "Test".to_string(),
+            is_applied: false,
+            agents: None,
+            other_fields: Some(json!({
+                "imported": { "created_at": "2026-01-02 08:30:00", "imported_at": "2026-01-02 08:30:00" },
+                "look_at": "1767225600"
+            })),
+            created_at: created_at.to_string(),
+            updated_at: updated_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_local_time_to_utc() {
+        let mut content = content("2026-03-01T10:00:00+08:00", "yesterday");
+        let changes = normalize_timestamps(&mut content);
+
+        assert_eq!(content.created_at, "2026-03-01T02:00:00Z");
+        assert_eq!(changes[0].kind, TimestampChangeKind::Normalized);
+        assert_eq!(changes[0].before, "2026-03-01T10:00:00+08:00");
+
+        // Unparseable values are reported and kept
+        assert_eq!(changes[1].path, "updated_at");
+        assert_eq!(changes[1].kind, TimestampChangeKind::Unparseable);
+        assert_eq!(content.updated_at, "yesterday");
+
+        // Naive section timestamps are local time
+        let naive = NaiveDateTime::parse_from_str("2026-01-02 08:30:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let expected = format_timestamp(&local_to_utc(naive).unwrap());
+        assert_eq!(changes[2].path, "other_fields.imported.created_at");
+        let other_fields = content.other_fields.as_ref().unwrap();
+        assert_eq!(other_fields["imported"]["created_at"], json!(expected));
+
+        // Other keys ending in `_at` are not timestamps
+        assert_eq!(changes.len(), 3);
+        assert_eq!(other_fields["imported"]["imported_at"], json!("2026-01-02 08:30:00"));
+        assert_eq!(other_fields["look_at"], json!("1767225600"));
+    }
+
+    #[test]
+    fn test_normalize_is_idempotent() {
+        let mut content = content("2026-03-01T02:00:00Z", "1767225600");
+        assert_eq!(normalize_timestamps(&mut content).len(), 2);
+        assert_eq!(content.updated_at, "2026-01-01T00:00:00Z");
+        assert!(normalize_timestamps(&mut content).is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+//! Oh My OpenCode Tools Module
+//!
+//! Registry of known tool names, used to catch typos in agent `tools` maps.
+//! Built-in names can be extended in the global config (`other_fields.known_tools`).
+
+use indexmap::IndexMap;
+use std::collections::HashMap;
+
+use super::agent;
+use super::mcp::{mcp_servers, tool_key_references_mcp};
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+use super::validation::{Severity, ValidationIssue};
+
+const TOOL_NAME_RULE: &str = "tool-name";
+
+/// Key of the user-maintained tool names in the global `other_fields`
+pub const KNOWN_TOOLS_KEY: &str = "known_tools";
+
+/// Tools shipped with OpenCode and oh-my-opencode
+pub const BUILTIN_TOOLS: &[&str] = &[
+    // OpenCode
+    "bash",
+    "edit",
+    "write",
+    "read",
+    "grep",
+    "glob",
+    "list",
+    "patch",
+    "todowrite",
+    "todoread",
+    "webfetch",
+    "task",
+    // oh-my-opencode
+    "lsp_hover",
+    "lsp_goto_definition",
+    "lsp_find_references",
+    "lsp_document_symbols",
+    "lsp_workspace_symbols",
+    "lsp_diagnostics",
+    "lsp_servers",
+    "lsp_prepare_rename",
+    "lsp_rename",
+    "lsp_code_actions",
+    "lsp_code_action_resolve",
+    "ast_grep_search",
+    "ast_grep_replace",
+    "call_omo_agent",
+    "background_task",
+    "background_output",
+    "background_cancel",
+    "look_at",
+    "interactive_bash",
+    "skill",
+];
+
+/// Largest edit distance still offered as a suggestion
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Built-in tools plus the names added in the global config
+pub fn known_tools(global: &OhMyOpenCodeGlobalConfig) -> Vec<String> {
+    let mut tools: Vec<String> = BUILTIN_TOOLS.iter().map(|t| t.to_string()).collect();
+    let custom = global
+        .other_fields
+        .as_ref()
+        .and_then(|fields| fields.get(KNOWN_TOOLS_KEY))
+        .and_then(|v| v.as_array());
+    for tool in custom.into_iter().flatten().filter_map(|v| v.as_str()) {
+        if !tools.iter().any(|t| t == tool) {
+            tools.push(tool.to_string());
+        }
+    }
+    tools
+}
+
+/// Levenshtein distance between two names
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// The closest known tool name, if close enough to be a typo
+pub fn suggest_tool_name(name: &str, known: &[String]) -> Option<String> {
+    known
+        .iter()
+        .map(|tool| (edit_distance(name, tool), tool))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, tool)| tool.clone())
+}
+
+/// Whether a tools key is checked against the registry
+/// Wildcards and MCP tool keys (`{mcp}_...`) are not
+fn is_checked_key(key: &str, mcps: &[&String]) -> bool {
+    !key.contains('*') && !mcps.iter().any(|mcp| tool_key_references_mcp(key, mcp, mcps))
+}
+
+/// Unknown tool names per agent, with a suggestion when one is close
+pub fn unknown_tool_names(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<(String, String, Option<String>)> {
+    let known = known_tools(global);
+    let mcps: Vec<&String> = [global.other_fields.as_ref(), profile.other_fields.as_ref()]
+        .into_iter()
+        .filter_map(mcp_servers)
+        .flat_map(|servers| servers.keys())
+        .collect();
+
+    let mut unknown = Vec::new();
+    for (name, agent) in profile.typed_agents() {
+        for key in agent.tools.iter().flat_map(|tools| tools.keys()) {
+            if is_checked_key(key, &mcps) && !known.contains(key) {
+                unknown.push((name.clone(), key.clone(), suggest_tool_name(key, &known)));
+            }
+        }
+    }
+    unknown
+}
+
+/// Validation rule: agent tools must be known, typos get a "did you mean" hint
+pub fn validate_tool_names(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    unknown_tool_names(global, profile)
+        .into_iter()
+        .map(|(agent, tool, suggestion)| {
+            let message = match suggestion {
+                Some(suggestion) => format!(
+                    "Agent '{}' uses unknown tool '{}', did you mean '{}'?",
+                    agent, tool, suggestion
+                ),
+                None => format!("Agent '{}' uses unknown tool '{}'", agent, tool),
+            };
+            ValidationIssue::new(
+                TOOL_NAME_RULE,
+                Severity::Warning,
+                format!("agents.{}.tools.{}", agent, tool),
+                message,
+            )
+        })
+        .collect()
+}
+
+/// Merge an overlay `tools` map over a base one key by key
+/// Overlay keys add or override single tools, keys only in the base are kept in their order
+pub fn merge_tools(
+    base: Option<&IndexMap<String, bool>>,
+    overlay: Option<&IndexMap<String, bool>>,
+) -> Option<IndexMap<String, bool>> {
+    match (base, overlay) {
+        (None, None) => None,
+        (Some(tools), None) | (None, Some(tools)) => Some(tools.clone()),
+        (Some(base), Some(overlay)) => {
+            let mut merged = base.clone();
+            for (tool, enabled) in overlay {
+                merged.insert(tool.clone(), *enabled);
+            }
+            Some(merged)
+        }
+    }
+}
+
+/// Rename tool keys in every agent (`fixes`: wrong name -> correct name)
+/// The setting moves to the new key, an existing correct key keeps its value
+/// Returns the number of renamed keys
+pub fn fix_tool_names(config: &mut OhMyOpenCodeConfig, fixes: &HashMap<String, String>) -> usize {
+    if config.agents.is_none() || fixes.is_empty() {
+        return 0;
+    }
+    agent::with_agents_mut(config, |agents| {
+        let mut fixed = 0;
+        for tools in agents.values_mut().filter_map(|agent| agent.tools.as_mut()) {
+            for (wrong, correct) in fixes {
+                let Some(index) = tools.get_index_of(wrong) else {
+                    continue;
+                };
+                let (_, enabled) = tools.shift_remove_index(index).unwrap_or_default();
+                if !tools.contains_key(correct) {
+                    tools.shift_insert(index.min(tools.len()), correct.clone(), enabled);
+                }
+                fixed += 1;
+            }
+        }
+        fixed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn profile() -> OhMyOpenCodeConfig {
+        OhMyOpenCodeConfig {
+            other_fields: Some(json!({ "mcp": { "github": { "type": "remote" } } })),
+            ..OhMyOpenCodeConfig::test_with_agents(json!({
+                "oracle": { "tools": { "bassh": false, "read": true, "github_search": true, "lsp_*": true } }
+            }))
+        }
+    }
+
+    #[test]
+    fn test_flag_misspelled_tool() {
+        let issues = validate_tool_names(&OhMyOpenCodeGlobalConfig::default(), &profile());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "agents.oracle.tools.bassh");
+        assert_eq!(
+            issues[0].message,
+            "Agent 'oracle' uses unknown tool 'bassh', did you mean 'bash'?"
+        );
+
+        // Names added to the registry are accepted
+        let global = OhMyOpenCodeGlobalConfig {
+            other_fields: Some(json!({ "known_tools": ["bassh"] })),
+            ..Default::default()
+        };
+        assert!(validate_tool_names(&global, &profile()).is_empty());
+    }
+
+    #[test]
+    fn test_fix_tool_names() {
+        let mut config = profile();
+        let fixes = HashMap::from([("bassh".to_string(), "bash".to_string())]);
+        assert_eq!(fix_tool_names(&mut config, &fixes), 1);
+
+        let agents = config.agents_value().unwrap();
+        let tools = &agents["oracle"]["tools"];
+        let keys: Vec<&String> = tools.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["bash", "read", "github_search", "lsp_*"]);
+        assert_eq!(tools["bash"], json!(false));
+    }
+}
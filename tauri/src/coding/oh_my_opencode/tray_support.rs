@@ -97,7 +97,7 @@ pub async fn is_enabled_for_tray<R: Runtime>(app: &AppHandle<R>) -> bool {
     use crate::coding::open_code::types::ReadConfigResult;
 
     let state = app.state::<DbState>();
-    let config = match read_opencode_config(state).await {
+    let config = match read_opencode_config(state, app.clone()).await {
         Ok(ReadConfigResult::Success { config }) => config,
         _ => return false,
     };
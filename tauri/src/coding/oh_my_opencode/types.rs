@@ -1,6 +1,9 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::agent::AgentProfile;
+
 /// Config path info
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -28,7 +31,13 @@ pub struct OhMyOpenCodeAgentsProfile {
     pub id: String,
     pub name: String,
     pub is_applied: bool,
-    pub agents: Option<Value>, // JSON, no specific structure
+    /// Typed agents in config order, a bad agent keeps its valid fields (see `agent::parse_agents`)
+    #[serde(
+        default,
+        deserialize_with = "super::agent::deserialize_agents",
+        serialize_with = "super::agent::serialize_agents"
+    )]
+    pub agents: Option<IndexMap<String, AgentProfile>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub other_fields: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -96,6 +105,24 @@ pub struct OhMyOpenCodeGlobalConfig {
     pub updated_at: Option<String>,
 }
 
+impl Default for OhMyOpenCodeGlobalConfig {
+    /// 默认空配置
+    fn default() -> Self {
+        OhMyOpenCodeGlobalConfig {
+            id: "global".to_string(),
+            schema: None,
+            sisyphus_agent: None,
+            disabled_agents: None,
+            disabled_mcps: None,
+            disabled_hooks: None,
+            lsp: None,
+            experimental: None,
+            other_fields: None,
+            updated_at: None,
+        }
+    }
+}
+
 /// Oh My OpenCode Global Config content for database storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OhMyOpenCodeGlobalConfigContent {
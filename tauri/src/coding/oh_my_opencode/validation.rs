@@ -0,0 +1,192 @@
+//! Oh My OpenCode Validation Module
+//!
+//! Pre-apply checks on a profile and the global config.
+//! Each rule is a plain function registered in `RULES`.
+//! The last result is cached on the config record (`last_validation`) against a fingerprint
+//! of the config and global content, so unchanged configs are not validated again.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::capabilities;
+use super::dependencies;
+use super::disabled;
+use super::duplicates;
+use super::fingerprint;
+use super::mcp;
+use super::prompt_encoding;
+use super::prompt_library;
+use super::reasoning;
+use super::tools;
+use super::types::{OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// A single validation finding, `path` is a dotted path into the rendered config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    pub rule: String,
+    pub severity: Severity,
+    pub path: String,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(rule: &str, severity: Severity, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            severity,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+pub type ValidationRule = fn(&OhMyOpenCodeGlobalConfig, &OhMyOpenCodeConfig) -> Vec<ValidationIssue>;
+
+/// All rules run by `validate_config`, in order
+const RULES: &[ValidationRule] = &[
+    prompt_library::validate_prompt_refs,
+    prompt_encoding::validate_prompt_encoding,
+    dependencies::check_agent_dependencies,
+    tools::validate_tool_names,
+    reasoning::validate_reasoning_settings,
+    capabilities::validate_model_capabilities,
+    mcp::validate_unused_mcps,
+    disabled::validate_something_enabled,
+];
+
+/// Run every validation rule, errors first
+pub fn validate_config(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> = RULES.iter().flat_map(|rule| rule(global, profile)).collect();
+    issues.sort_by_key(|issue| issue.severity);
+    issues
+}
+
+/// Whether the issues block an apply
+pub fn has_errors(issues: &[ValidationIssue]) -> bool {
+    issues.iter().any(|issue| issue.severity == Severity::Error)
+}
+
+/// Field of the config record holding the last `ValidationSummary`
+pub const LAST_VALIDATION_KEY: &str = "last_validation";
+
+/// Outcome of a validation run, stored with the config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationSummary {
+    pub validated_at: String,
+    /// `validation_fingerprint` of the validated content
+    pub fingerprint: String,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+impl ValidationSummary {
+    pub fn from_issues(issues: &[ValidationIssue], fingerprint: String, validated_at: String) -> Self {
+        let count = |severity: Severity| issues.iter().filter(|i| i.severity == severity).count();
+        ValidationSummary {
+            validated_at,
+            fingerprint,
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            infos: count(Severity::Info),
+        }
+    }
+}
+
+/// Fingerprint of everything a validation result depends on: the profile content and the global config
+pub fn validation_fingerprint(global: &OhMyOpenCodeGlobalConfig, profile: &OhMyOpenCodeConfig) -> String {
+    let mut global = serde_json::to_value(global).unwrap_or_default();
+    if let Some(obj) = global.as_object_mut() {
+        obj.remove("updatedAt");
+    }
+    fingerprint::fingerprint(&json!({
+        "profile": duplicates::config_fingerprint(profile),
+        "global": global,
+    }))
+}
+
+/// The cached summary when it still matches the content, otherwise the summary of a new run
+/// Returns whether validation ran
+pub fn validation_status(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    cached: Option<ValidationSummary>,
+    now: &str,
+) -> (ValidationSummary, bool) {
+    validation_status_with(global, profile, cached, now, validate_config)
+}
+
+fn validation_status_with(
+    global: &OhMyOpenCodeGlobalConfig,
+    profile: &OhMyOpenCodeConfig,
+    cached: Option<ValidationSummary>,
+    now: &str,
+    validate: impl Fn(&OhMyOpenCodeGlobalConfig, &OhMyOpenCodeConfig) -> Vec<ValidationIssue>,
+) -> (ValidationSummary, bool) {
+    let fingerprint = validation_fingerprint(global, profile);
+    match cached {
+        Some(summary) if summary.fingerprint == fingerprint => (summary, false),
+        _ => {
+            let issues = validate(global, profile);
+            (ValidationSummary::from_issues(&issues, fingerprint, now.to_string()), true)
+        }
+    }
+}
+
+/// A config with its validation status, returned by the status list
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigWithStatus {
+    #[serde(flatten)]
+    pub config: OhMyOpenCodeConfig,
+    pub last_validation: ValidationSummary,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::oh_my_opencode::agent::parse_agents;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_validation_skipped_until_content_changes() {
+        let global = OhMyOpenCodeGlobalConfig::default();
+        let mut profile = OhMyOpenCodeConfig::test_with_agents(json!({ "oracle": { "prompt_ref": "missing" } }));
+        let runs = Cell::new(0);
+        let validate = |global: &OhMyOpenCodeGlobalConfig, profile: &OhMyOpenCodeConfig| {
+            runs.set(runs.get() + 1);
+            validate_config(global, profile)
+        };
+
+        let (first, ran) = validation_status_with(&global, &profile, None, "t1", validate);
+        assert!(ran);
+        assert_eq!(first.errors, 1);
+
+        // Unchanged content reuses the cached summary, even after an unrelated save
+        profile.updated_at = Some("2026-02-01T00:00:00+00:00".to_string());
+        let (second, ran) = validation_status_with(&global, &profile, Some(first.clone()), "t2", validate);
+        assert!(!ran);
+        assert_eq!(second, first);
+        assert_eq!(runs.get(), 1);
+
+        profile.agents = Some(parse_agents(Some(&json!({ "oracle": { "model": "openai/o3" } }))));
+        let (third, ran) = validation_status_with(&global, &profile, Some(second), "t3", validate);
+        assert!(ran);
+        assert_eq!(third.errors, 0);
+        assert_eq!(third.validated_at, "t3");
+        assert_eq!(runs.get(), 2);
+    }
+}
@@ -0,0 +1,241 @@
+//! Oh My OpenCode VCS Form Module
+//!
+//! A canonical, byte-stable JSON form of a config record for committing to git.
+//! Secrets are moved to a separate secrets file and replaced by `{env:NAME}` references,
+//! so the VCS form plus the secrets file reconstruct the record.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::config_env;
+use super::migration;
+use crate::coding::redact;
+
+/// Record keys that change on every save or apply, left out of the VCS form
+const VOLATILE_KEYS: &[&str] = &["id", "is_applied", "created_at", "updated_at"];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum VcsError {
+    #[error("Invalid config record: {0}")]
+    InvalidRecord(String),
+    #[error("Invalid VCS form: {0}")]
+    Parse(String),
+}
+
+/// The committed form and the secrets it references (env name -> value)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VcsExport {
+    pub content: String,
+    pub secrets: IndexMap<String, String>,
+}
+
+/// `apiKey` / `api-key` -> `API_KEY`
+fn env_segment(segment: &str) -> String {
+    let mut out = String::new();
+    let mut previous_lower = false;
+    for c in segment.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && previous_lower {
+                out.push('_');
+            }
+            out.push(c.to_ascii_uppercase());
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !out.ends_with('_') {
+                out.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn is_env_name(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_uppercase())
+        && key.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Env name for a secret: the key itself when it already is one (`GITHUB_TOKEN`),
+/// otherwise built from the JSON path (`mcp.github.headers.Authorization` -> `MCP_GITHUB_HEADERS_AUTHORIZATION`)
+fn secret_env_name(path: &[String], key: &str) -> String {
+    if is_env_name(key) {
+        return key.to_string();
+    }
+    path.iter()
+        .map(String::as_str)
+        .chain(std::iter::once(key))
+        .map(env_segment)
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Names of the `{env:NAME}` placeholders already used in a value
+fn collect_placeholders(value: &Value, names: &mut Vec<String>) {
+    match value {
+        Value::String(s) => {
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{env:") {
+                let Some(len) = rest[start..].find('}') else {
+                    break;
+                };
+                names.push(rest[start + 5..start + len].to_string());
+                rest = &rest[start + len + 1..];
+            }
+        }
+        Value::Object(map) => map.values().for_each(|item| collect_placeholders(item, names)),
+        Value::Array(items) => items.iter().for_each(|item| collect_placeholders(item, names)),
+        _ => {}
+    }
+}
+
+/// Replace secret strings by `{env:NAME}` references, recording them in `secrets`
+/// Values that already are references are kept
+fn extract_secrets(
+    value: &mut Value,
+    path: &mut Vec<String>,
+    reserved: &[String],
+    secrets: &mut IndexMap<String, String>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                match item {
+                    Value::String(s) if redact::is_secret_key(key) && !s.is_empty() && !s.starts_with("{env:") => {
+                        let base = secret_env_name(path, key);
+                        let mut name = base.clone();
+                        let mut n = 2;
+                        while reserved.contains(&name) || secrets.get(&name).is_some_and(|v| *v != *s) {
+                            name = format!("{}_{}", base, n);
+                            n += 1;
+                        }
+                        secrets.insert(name.clone(), s.clone());
+                        *item = Value::String(format!("{{env:{}}}", name));
+                    }
+                    _ => {
+                        path.push(key.clone());
+                        extract_secrets(item, path, reserved, secrets);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                path.push(index.to_string());
+                extract_secrets(item, path, reserved, secrets);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sort object keys recursively
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sort_keys(v))).collect::<Map<_, _>>())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+/// Canonical form of a config record: migrated to the current schema (snake_case keys),
+/// volatile keys dropped, keys sorted, secrets replaced by env references, pretty-printed
+pub fn to_vcs_export(content: &Value) -> Result<VcsExport, VcsError> {
+    let mut record = migration::migrate(content.clone()).map_err(|e| VcsError::InvalidRecord(e.to_string()))?;
+    if let Some(obj) = record.as_object_mut() {
+        for key in VOLATILE_KEYS {
+            obj.shift_remove(*key);
+        }
+    }
+
+    let mut record = sort_keys(record);
+    let mut reserved = Vec::new();
+    collect_placeholders(&record, &mut reserved);
+    let mut secrets = IndexMap::new();
+    extract_secrets(&mut record, &mut Vec::new(), &reserved, &mut secrets);
+    secrets.sort_keys();
+
+    let mut content =
+        serde_json::to_string_pretty(&record).map_err(|e| VcsError::InvalidRecord(e.to_string()))?;
+    content.push('\n');
+    Ok(VcsExport { content, secrets })
+}
+
+/// Canonical JSON text of a config record for committing, see `to_vcs_export`
+pub fn to_vcs_form(content: &Value) -> Result<String, VcsError> {
+    to_vcs_export(content).map(|export| export.content)
+}
+
+/// Rebuild a config record from its VCS form and secrets file
+/// References missing from `secrets` are kept, they resolve from the environment at load time
+pub fn from_vcs_form(content: &str, secrets: &IndexMap<String, String>) -> Result<Value, VcsError> {
+    let mut record: Value = serde_json::from_str(content).map_err(|e| VcsError::Parse(e.to_string()))?;
+    if !record.is_object() {
+        return Err(VcsError::Parse("Expected a JSON object".to_string()));
+    }
+    config_env::fold_env(&mut record, secrets);
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record() -> Value {
+        json!({
+            "name": "Team",
+            "isApplied": true,
+            "updatedAt": "2026-01-01T00:00:00+00:00",
+            "other_fields": {
+                "mcp": {
+                    "github": { "type": "local", "env": { "GITHUB_TOKEN": "ghp_x" } },
+                    "exa": { "type": "remote", "headers": { "apiKey": "exa-123", "region": "{env:REGION}" } }
+                }
+            },
+            "agents": { "oracle": { "tools": ["bash"], "model": "openai/o3" } }
+        })
+    }
+
+    #[test]
+    fn test_vcs_form_is_stable() {
+        let export = to_vcs_export(&record()).unwrap();
+        assert_eq!(
+            export.secrets,
+            IndexMap::from([
+                ("GITHUB_TOKEN".to_string(), "ghp_x".to_string()),
+                ("OTHER_FIELDS_MCP_EXA_HEADERS_API_KEY".to_string(), "exa-123".to_string()),
+            ])
+        );
+        assert!(export.content.contains("\"{env:GITHUB_TOKEN}\""));
+        assert!(!export.content.contains("ghp_x"));
+        assert!(!export.content.contains("is_applied"));
+
+        // Byte-stable across runs and idempotent on its own output
+        assert_eq!(to_vcs_form(&record()).unwrap(), export.content);
+        let rebuilt = from_vcs_form(&export.content, &IndexMap::new()).unwrap();
+        assert_eq!(to_vcs_form(&rebuilt).unwrap(), export.content);
+    }
+
+    #[test]
+    fn test_vcs_form_reconstructs_record() {
+        let export = to_vcs_export(&record()).unwrap();
+        let rebuilt = from_vcs_form(&export.content, &export.secrets).unwrap();
+
+        let mcp = &rebuilt["other_fields"]["mcp"];
+        assert_eq!(mcp["github"]["env"]["GITHUB_TOKEN"], json!("ghp_x"));
+        assert_eq!(mcp["exa"]["headers"]["apiKey"], json!("exa-123"));
+        // Pre-existing references are not secrets
+        assert_eq!(mcp["exa"]["headers"]["region"], json!("{env:REGION}"));
+        assert_eq!(rebuilt["agents"]["oracle"]["tools"], json!({ "bash": true }));
+    }
+}
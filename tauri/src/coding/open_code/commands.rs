@@ -148,7 +148,10 @@ pub fn get_default_config_path() -> Result<String, String> {
 
 /// Read OpenCode configuration file with detailed result
 #[tauri::command]
-pub async fn read_opencode_config(state: tauri::State<'_, DbState>) -> Result<ReadConfigResult, String> {
+pub async fn read_opencode_config<R: tauri::Runtime>(
+    state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle<R>,
+) -> Result<ReadConfigResult, String> {
     let config_path_str = get_opencode_config_path(state).await?;
     let config_path = Path::new(&config_path_str);
 
@@ -156,12 +159,22 @@ pub async fn read_opencode_config(state: tauri::State<'_, DbState>) -> Result<Re
         return Ok(ReadConfigResult::NotFound { path: config_path_str });
     }
 
-    let content = match fs::read_to_string(config_path) {
-        Ok(c) => c,
-        Err(e) => return Ok(ReadConfigResult::Error { error: format!("Failed to read config file: {}", e) }),
-    };
+    // 文件未变化时直接读取二进制缓存，跳过 JSON 解析
+    enum LoadError {
+        Read(String),
+        Parse(String),
+    }
+    impl From<String> for LoadError {
+        fn from(e: String) -> Self {
+            LoadError::Read(e)
+        }
+    }
+    let cache_dir = crate::coding::oh_my_opencode::binary_cache::cache_dir(&app);
+    let loaded = crate::coding::oh_my_opencode::binary_cache::load_with_cache(cache_dir.as_deref(), config_path, |content| {
+        json5::from_str::<OpenCodeConfig>(content).map_err(|e| LoadError::Parse(e.to_string()))
+    });
 
-    match json5::from_str::<OpenCodeConfig>(&content) {
+    match loaded.map(|(config, _)| config) {
         Ok(mut config) => {
             // Initialize provider if missing
             if config.provider.is_none() {
@@ -199,7 +212,9 @@ pub async fn read_opencode_config(state: tauri::State<'_, DbState>) -> Result<Re
 
             Ok(ReadConfigResult::Success { config })
         }
-        Err(e) => {
+        Err(LoadError::Read(e)) => Ok(ReadConfigResult::Error { error: format!("Failed to read config file: {}", e) }),
+        Err(LoadError::Parse(e)) => {
+            let content = fs::read_to_string(config_path).unwrap_or_default();
             // Truncate content preview to first 500 chars
             let preview = if content.len() > 500 {
                 format!("{}...", &content[..500])
@@ -209,7 +224,7 @@ pub async fn read_opencode_config(state: tauri::State<'_, DbState>) -> Result<Re
 
             Ok(ReadConfigResult::ParseError {
                 path: config_path_str,
-                error: e.to_string(),
+                error: e,
                 content_preview: Some(preview),
             })
         }
@@ -385,12 +400,13 @@ pub async fn get_provider_models(
 #[tauri::command]
 pub async fn get_opencode_unified_models(
     state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
 ) -> Result<Vec<UnifiedModelOption>, String> {
     // Read auth.json to get official provider ids
     let auth_channels = super::free_models::read_auth_channels();
 
     // Read config to get custom providers
-    let result = read_opencode_config(state.clone()).await?;
+    let result = read_opencode_config(state.clone(), app).await?;
     let custom_providers = match result {
         ReadConfigResult::Success { config } => config.provider,
         _ => None,
@@ -411,9 +427,10 @@ pub async fn get_opencode_unified_models(
 #[tauri::command]
 pub async fn get_opencode_auth_providers(
     state: tauri::State<'_, DbState>,
+    app: tauri::AppHandle,
 ) -> Result<GetAuthProvidersResponse, String> {
     // Read config to get custom providers
-    let result = read_opencode_config(state.clone()).await?;
+    let result = read_opencode_config(state.clone(), app).await?;
     let custom_providers = match result {
         ReadConfigResult::Success { config } => config.provider,
         _ => None,
@@ -1,3 +1,4 @@
+use crate::coding::metrics;
 use crate::db::DbState;
 use crate::http_client;
 use super::types::{FreeModel, ProviderModelsData, UnifiedModelOption, OpenCodeProvider, OfficialModel, OfficialProvider, GetAuthProvidersResponse};
@@ -6,6 +7,8 @@ use std::collections::{HashMap, HashSet};
 use indexmap::IndexMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 // Load default models data from resources/models.json at compile time
 const DEFAULT_MODELS_JSON: &str = include_str!("../../../resources/models.json");
@@ -14,10 +17,13 @@ const MODELS_API_URL: &str = "https://models.dev/api.json";
 const DB_TABLE: &str = "provider_models";
 const OPENCODE_PROVIDER_ID: &str = "opencode"; // Default provider for free models
 const CACHE_DURATION_HOURS: u64 = 6; // 6 hours cache duration
+/// After a failed background refresh no new one starts for this long, so offline users
+/// do not wait on models.dev over and over
+const FAILED_REFRESH_BACKOFF: Duration = Duration::from_secs(10 * 60);
 
 /// Get all providers data from resources/models.json
 /// Returns the complete JSON object containing all providers
-fn get_all_default_providers_data() -> serde_json::Value {
+pub fn get_all_default_providers_data() -> serde_json::Value {
     serde_json::from_str(DEFAULT_MODELS_JSON).unwrap_or_else(|e| {
         eprintln!("Failed to parse default models.json: {}", e);
         serde_json::json!({})
@@ -51,7 +57,11 @@ pub fn get_default_free_models() -> Vec<FreeModel> {
 /// Returns the complete JSON object containing all providers
 async fn fetch_all_providers_from_api(state: &DbState) -> Result<serde_json::Value, String> {
     let client = http_client::client_with_timeout(state, 30).await?;
+    fetch_all_providers(&client).await
+}
 
+/// Fetch all providers data from API with the given client
+pub async fn fetch_all_providers(client: &reqwest::Client) -> Result<serde_json::Value, String> {
     let response = client
         .get(MODELS_API_URL)
         .send()
@@ -261,6 +271,8 @@ pub async fn get_free_models(state: &DbState, force_refresh: bool) -> Result<(Ve
     if !force_refresh {
         match read_provider_models_from_db(state, OPENCODE_PROVIDER_ID).await {
             Ok(Some(cached_data)) => {
+                // Stale data is still served from the cache
+                metrics::recorder().record_model_cache(true);
                 if !is_cache_expired(&cached_data.updated_at) {
                     // Cache is fresh: filter free models from cached provider data
                     let free_models = filter_free_models(OPENCODE_PROVIDER_ID, &cached_data.value);
@@ -272,20 +284,7 @@ pub async fn get_free_models(state: &DbState, force_refresh: bool) -> Result<(Ve
                 let updated_at = cached_data.updated_at.clone();
                 eprintln!("[CACHE EXPIRED] (updated_at: {}), returning {} stale models and refreshing in background...", updated_at, cached_models.len());
 
-                // Spawn background task to refresh cache
-                let db_arc = state.0.clone();
-                let db_state = DbState(db_arc);
-                tauri::async_runtime::spawn(async move {
-                    eprintln!("[Background] Starting all providers data refresh...");
-                    match fetch_and_update_all_providers(&db_state).await {
-                        Ok(count) => {
-                            eprintln!("[Background] Successfully refreshed {} providers", count);
-                        }
-                        Err(e) => {
-                            eprintln!("[Background] Failed to refresh providers: {}", e);
-                        }
-                    }
-                });
+                refresh_all_providers_in_background(state);
 
                 return Ok((cached_models, true, Some(updated_at)));
             }
@@ -299,6 +298,7 @@ pub async fn get_free_models(state: &DbState, force_refresh: bool) -> Result<(Ve
     }
 
     // 2. No cache or force_refresh: fetch all providers from API (synchronous)
+    metrics::recorder().record_model_cache(false);
     eprintln!("[FETCH] No cache or force_refresh, fetching all providers from API...");
     fetch_and_update_all_providers(state).await?;
 
@@ -338,6 +338,93 @@ async fn fetch_and_update_all_providers(state: &DbState) -> Result<usize, String
     save_all_provider_models_to_db(state, &final_providers, &updated_at).await
 }
 
+/// Whether a background refresh is running and when the last one failed
+#[derive(Debug, Default)]
+struct RefreshGate {
+    in_flight: bool,
+    failed_at: Option<Instant>,
+}
+
+impl RefreshGate {
+    /// Claim the refresh, unless one is running or the last one failed too recently
+    fn try_begin(&mut self, now: Instant) -> bool {
+        let backing_off = self
+            .failed_at
+            .is_some_and(|failed_at| now.duration_since(failed_at) < FAILED_REFRESH_BACKOFF);
+        if self.in_flight || backing_off {
+            return false;
+        }
+        self.in_flight = true;
+        true
+    }
+
+    fn finish(&mut self, succeeded: bool, now: Instant) {
+        self.in_flight = false;
+        self.failed_at = (!succeeded).then_some(now);
+    }
+}
+
+static REFRESH_GATE: Mutex<RefreshGate> = Mutex::new(RefreshGate {
+    in_flight: false,
+    failed_at: None,
+});
+
+/// Refresh every cached provider from models.dev in a background task
+/// At most one refresh runs at a time, and failures are cached for `FAILED_REFRESH_BACKOFF`
+pub fn refresh_all_providers_in_background(state: &DbState) {
+    if !REFRESH_GATE.lock().map(|mut gate| gate.try_begin(Instant::now())).unwrap_or(false) {
+        return;
+    }
+
+    let db_state = DbState(state.0.clone());
+    tauri::async_runtime::spawn(async move {
+        eprintln!("[Background] Starting all providers data refresh...");
+        let result = fetch_and_update_all_providers(&db_state).await;
+        match &result {
+            Ok(count) => {
+                eprintln!("[Background] Successfully refreshed {} providers", count);
+            }
+            Err(e) => {
+                eprintln!("[Background] Failed to refresh providers: {}", e);
+            }
+        }
+        if let Ok(mut gate) = REFRESH_GATE.lock() {
+            gate.finish(result.is_ok(), Instant::now());
+        }
+    });
+}
+
+/// All cached providers as one models.dev shaped object (provider id -> provider data)
+/// Never waits on the network: a stale cache is returned as is and refreshed in the background
+pub async fn read_cached_providers_data(state: &DbState) -> Result<serde_json::Value, String> {
+    let records: Vec<serde_json::Value> = {
+        let db = state.0.lock().await;
+        db.query(format!("SELECT * FROM {}", DB_TABLE))
+            .await
+            .map_err(|e| format!("Failed to query provider models: {}", e))?
+            .take(0)
+            .map_err(|e| e.to_string())?
+    };
+
+    let mut providers = serde_json::Map::new();
+    let mut expired = records.is_empty();
+    for record in records {
+        let Some(provider_id) = record.get("provider_id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if provider_id == OPENCODE_PROVIDER_ID {
+            expired |= record.get("updated_at").and_then(|v| v.as_str()).map_or(true, is_cache_expired);
+        }
+        providers.insert(provider_id.to_string(), record.get("value").cloned().unwrap_or_default());
+    }
+
+    metrics::recorder().record_model_cache(!providers.is_empty());
+    if expired {
+        refresh_all_providers_in_background(state);
+    }
+    Ok(serde_json::Value::Object(providers))
+}
+
 /// Initialize default provider models in database (called on app startup)
 /// Only writes if no cached data exists (checks opencode as indicator)
 pub async fn init_default_provider_models(state: &DbState) -> Result<(), String> {
@@ -797,3 +884,27 @@ pub async fn get_auth_providers_data(
 
     response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_gate_single_flight_and_failure_backoff() {
+        let start = Instant::now();
+        let mut gate = RefreshGate::default();
+
+        assert!(gate.try_begin(start));
+        // Only one refresh at a time
+        assert!(!gate.try_begin(start));
+
+        // A failure blocks new refreshes until the backoff has passed
+        gate.finish(false, start);
+        assert!(!gate.try_begin(start + Duration::from_secs(60)));
+        assert!(gate.try_begin(start + FAILED_REFRESH_BACKOFF));
+
+        // A success does not
+        gate.finish(true, start + FAILED_REFRESH_BACKOFF);
+        assert!(gate.try_begin(start + FAILED_REFRESH_BACKOFF));
+    }
+}
@@ -0,0 +1,434 @@
+//! Idle Prefetch Module
+//!
+//! Refreshes the model lists of configured providers while the user is idle, so the model
+//! picker can show them without waiting. Providers are fetched one at a time, only when
+//! their cached list is stale and the per-provider rate limiter allows it. Any activity
+//! reported by the frontend cancels the running fetch immediately; prefetching resumes
+//! after the next idle period. Progress is emitted as `prefetch-progress` events.
+//! Lists fetched by the model picker through `fetch_provider_models` land in the same
+//! cache and count against the same rate limiter.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{watch, Mutex};
+use tokio::time::Instant;
+
+use super::models_api::{fetch_enriched_models, FetchModelsRequest, FetchModelsResponse};
+use crate::db::DbState;
+
+/// Default idle period
+const DEFAULT_IDLE_SECS: u64 = 60;
+/// Cached model lists older than this are refreshed
+const STALE_AFTER_SECS: u64 = 6 * 60 * 60;
+/// Minimum time between two fetches of the same provider
+const MIN_FETCH_INTERVAL_SECS: u64 = 5 * 60;
+/// How often a completed pass looks for stale caches again without activity
+const RECHECK_SECS: u64 = 10 * 60;
+
+/// Provider whose model list is prefetched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchTarget {
+    pub provider_id: String,
+    pub request: FetchModelsRequest,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum PrefetchStatus {
+    Started,
+    Done { models: usize },
+    Failed { error: String },
+    /// Fetched too recently, skipped this pass
+    RateLimited,
+    /// The user became active, the fetch was dropped
+    Cancelled,
+}
+
+/// Payload of the `prefetch-progress` event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchProgress {
+    pub provider_id: String,
+    #[serde(flatten)]
+    pub status: PrefetchStatus,
+}
+
+/// When a pass starts and which caches it refreshes
+#[derive(Debug, Clone, Copy)]
+pub struct PrefetchTiming {
+    /// No activity for this long counts as idle
+    pub idle_after: Duration,
+    /// Cached lists older than this are refreshed
+    pub stale_after: Duration,
+}
+
+/// How a prefetch pass ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassOutcome {
+    Completed,
+    Cancelled,
+    /// The activity sender is gone, prefetching stops
+    Closed,
+}
+
+/// Allows one fetch per provider per `min_interval`
+#[derive(Debug)]
+pub struct ProviderRateLimiter {
+    min_interval: Duration,
+    last_fetch: HashMap<String, Instant>,
+}
+
+impl ProviderRateLimiter {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last_fetch: HashMap::new(),
+        }
+    }
+
+    /// Record a fetch of `provider_id` when allowed
+    pub fn try_acquire(&mut self, provider_id: &str) -> bool {
+        let now = Instant::now();
+        match self.last_fetch.get(provider_id) {
+            Some(last) if now.duration_since(*last) < self.min_interval => false,
+            _ => {
+                self.last_fetch.insert(provider_id.to_string(), now);
+                true
+            }
+        }
+    }
+
+    /// Record a fetch of `provider_id` made outside the limiter
+    pub fn record(&mut self, provider_id: &str) {
+        self.last_fetch.insert(provider_id.to_string(), Instant::now());
+    }
+}
+
+/// Prefetched model lists by provider id
+#[derive(Debug, Default)]
+pub struct PrefetchCache(StdMutex<HashMap<String, (Instant, FetchModelsResponse)>>);
+
+impl PrefetchCache {
+    pub fn get(&self, provider_id: &str) -> Option<FetchModelsResponse> {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries.get(provider_id).map(|(_, response)| response.clone())
+    }
+
+    fn is_stale(&self, provider_id: &str, stale_after: Duration) -> bool {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries
+            .get(provider_id)
+            .is_none_or(|(fetched_at, _)| fetched_at.elapsed() >= stale_after)
+    }
+
+    fn insert(&self, provider_id: &str, response: FetchModelsResponse) {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(provider_id.to_string(), (Instant::now(), response));
+    }
+}
+
+/// Wait until nothing was reported for `idle_after`, false when the sender is gone
+async fn wait_for_idle(activity: &mut watch::Receiver<Instant>, idle_after: Duration) -> bool {
+    loop {
+        let elapsed = activity.borrow_and_update().elapsed();
+        if elapsed >= idle_after {
+            return true;
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(idle_after - elapsed) => {}
+            changed = activity.changed() => {
+                if changed.is_err() {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// Wait for an idle period, then fetch every stale target in order, one at a time
+/// Activity during a fetch cancels it and ends the pass
+pub async fn run_prefetch_pass<F, Fut>(
+    targets: &[PrefetchTarget],
+    timing: PrefetchTiming,
+    activity: &mut watch::Receiver<Instant>,
+    cache: &PrefetchCache,
+    limiter: &StdMutex<ProviderRateLimiter>,
+    fetch: &F,
+    emit: &(dyn Fn(PrefetchProgress) + Send + Sync),
+) -> PassOutcome
+where
+    F: Fn(PrefetchTarget) -> Fut,
+    Fut: Future<Output = Result<FetchModelsResponse, String>>,
+{
+    if !wait_for_idle(activity, timing.idle_after).await {
+        return PassOutcome::Closed;
+    }
+
+    for target in targets {
+        if !cache.is_stale(&target.provider_id, timing.stale_after) {
+            continue;
+        }
+        let progress = |status| PrefetchProgress {
+            provider_id: target.provider_id.clone(),
+            status,
+        };
+        let allowed = limiter
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .try_acquire(&target.provider_id);
+        if !allowed {
+            emit(progress(PrefetchStatus::RateLimited));
+            continue;
+        }
+
+        emit(progress(PrefetchStatus::Started));
+        tokio::select! {
+            result = fetch(target.clone()) => match result {
+                Ok(response) => {
+                    let models = response.models.len();
+                    cache.insert(&target.provider_id, response);
+                    emit(progress(PrefetchStatus::Done { models }));
+                }
+                Err(error) => emit(progress(PrefetchStatus::Failed { error })),
+            },
+            changed = activity.changed() => {
+                emit(progress(PrefetchStatus::Cancelled));
+                return if changed.is_err() { PassOutcome::Closed } else { PassOutcome::Cancelled };
+            }
+        }
+    }
+    PassOutcome::Completed
+}
+
+/// Prefetch scheduler, managed as Tauri state
+pub struct IdlePrefetchState {
+    activity: watch::Sender<Instant>,
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+    cache: Arc<PrefetchCache>,
+    limiter: Arc<StdMutex<ProviderRateLimiter>>,
+}
+
+impl Default for IdlePrefetchState {
+    fn default() -> Self {
+        Self {
+            activity: watch::Sender::new(Instant::now()),
+            task: Mutex::new(None),
+            cache: Arc::new(PrefetchCache::default()),
+            limiter: Arc::new(StdMutex::new(ProviderRateLimiter::new(Duration::from_secs(
+                MIN_FETCH_INTERVAL_SECS,
+            )))),
+        }
+    }
+}
+
+impl IdlePrefetchState {
+    /// Share a list fetched on demand: cache it and hold off the next prefetch of the provider
+    pub fn record_fetch(&self, provider_id: &str, response: FetchModelsResponse) {
+        self.limiter
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .record(provider_id);
+        self.cache.insert(provider_id, response);
+    }
+}
+
+/// Fetch one provider exactly like `fetch_provider_models`
+async fn prefetch_provider(app: &AppHandle, target: PrefetchTarget) -> Result<FetchModelsResponse, String> {
+    fetch_enriched_models(&app.state::<DbState>(), &target.request).await
+}
+
+/// Start (or restart) prefetching for the given providers
+#[tauri::command]
+pub async fn start_idle_prefetch(
+    app: AppHandle,
+    prefetch: tauri::State<'_, IdlePrefetchState>,
+    providers: Vec<PrefetchTarget>,
+    idle_secs: Option<u64>,
+) -> Result<(), String> {
+    let mut running = prefetch.task.lock().await;
+    if let Some(handle) = running.take() {
+        handle.abort();
+    }
+
+    let timing = PrefetchTiming {
+        idle_after: Duration::from_secs(idle_secs.unwrap_or(DEFAULT_IDLE_SECS)),
+        stale_after: Duration::from_secs(STALE_AFTER_SECS),
+    };
+    let mut activity = prefetch.activity.subscribe();
+    let cache = prefetch.cache.clone();
+    let limiter = prefetch.limiter.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        let fetch = |target| prefetch_provider(&app, target);
+        let emit = |progress: PrefetchProgress| {
+            let _ = app.emit("prefetch-progress", progress);
+        };
+        loop {
+            let outcome = run_prefetch_pass(
+                &providers,
+                timing,
+                &mut activity,
+                &cache,
+                &limiter,
+                &fetch,
+                &emit,
+            )
+            .await;
+            match outcome {
+                PassOutcome::Closed => break,
+                PassOutcome::Cancelled => {}
+                // Nothing left to do until the user comes back or caches go stale
+                PassOutcome::Completed => {
+                    tokio::select! {
+                        _ = activity.changed() => {}
+                        _ = tokio::time::sleep(Duration::from_secs(RECHECK_SECS)) => {}
+                    }
+                }
+            }
+        }
+    });
+
+    *running = Some(handle);
+    Ok(())
+}
+
+/// Stop prefetching, the cached lists are kept
+#[tauri::command]
+pub async fn stop_idle_prefetch(prefetch: tauri::State<'_, IdlePrefetchState>) -> Result<(), String> {
+    if let Some(handle) = prefetch.task.lock().await.take() {
+        handle.abort();
+    }
+    Ok(())
+}
+
+/// Called by the frontend on user input, cancels a running prefetch
+#[tauri::command]
+pub fn report_user_activity(prefetch: tauri::State<'_, IdlePrefetchState>) {
+    prefetch.activity.send_replace(Instant::now());
+}
+
+/// Prefetched model list of a provider, if any
+#[tauri::command]
+pub fn get_prefetched_provider_models(
+    prefetch: tauri::State<'_, IdlePrefetchState>,
+    provider_id: String,
+) -> Option<FetchModelsResponse> {
+    prefetch.cache.get(&provider_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::open_code::models_api::ApiType;
+
+    fn target(provider_id: &str) -> PrefetchTarget {
+        PrefetchTarget {
+            provider_id: provider_id.to_string(),
+            request: FetchModelsRequest {
+                base_url: "http://localhost".to_string(),
+                api_key: None,
+                headers: None,
+                api_type: ApiType::OpenaiCompat,
+                sdk_type: None,
+                custom_url: None,
+                allowed_models: None,
+                denied_models: None,
+            },
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_prefetch_runs_when_idle_and_cancels_on_activity() {
+        let (activity_tx, mut activity) = watch::channel(Instant::now());
+        let cache = PrefetchCache::default();
+        let limiter = StdMutex::new(ProviderRateLimiter::new(Duration::from_secs(60)));
+        let events = Arc::new(StdMutex::new(Vec::new()));
+        let events_c = events.clone();
+        let emit = move |progress: PrefetchProgress| events_c.lock().unwrap().push(progress);
+        let fetch = |target: PrefetchTarget| async move {
+            let delay = if target.provider_id == "slow" { 5_000 } else { 5 };
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            Ok::<_, String>(FetchModelsResponse { models: Vec::new(), total: 0 })
+        };
+        let targets = vec![target("fast"), target("slow")];
+        let timing = PrefetchTiming {
+            idle_after: Duration::from_millis(30),
+            stale_after: Duration::from_secs(60),
+        };
+
+        // Activity before the idle period elapses pushes the start out, then "fast" is
+        // fetched and the user comes back while "slow" is running
+        let started = Instant::now();
+        let pass = run_prefetch_pass(&targets, timing, &mut activity, &cache, &limiter, &fetch, &emit);
+        let signal = async {
+            tokio::time::sleep(Duration::from_millis(15)).await;
+            activity_tx.send_replace(Instant::now());
+            tokio::time::sleep(Duration::from_millis(80)).await;
+            activity_tx.send_replace(Instant::now());
+        };
+        let (outcome, _) = tokio::join!(pass, signal);
+        assert_eq!(outcome, PassOutcome::Cancelled);
+        assert!(started.elapsed() >= Duration::from_millis(45));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let status = |events: &[PrefetchProgress]| {
+            events.iter().map(|e| (e.provider_id.clone(), e.status.clone())).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            status(&events.lock().unwrap()),
+            vec![
+                ("fast".to_string(), PrefetchStatus::Started),
+                ("fast".to_string(), PrefetchStatus::Done { models: 0 }),
+                ("slow".to_string(), PrefetchStatus::Started),
+                ("slow".to_string(), PrefetchStatus::Cancelled),
+            ]
+        );
+        assert!(cache.get("fast").is_some());
+        assert!(cache.get("slow").is_none());
+
+        // Next pass: "fast" is fresh, "slow" was fetched too recently
+        events.lock().unwrap().clear();
+        let outcome = run_prefetch_pass(&targets, timing, &mut activity, &cache, &limiter, &fetch, &emit).await;
+        assert_eq!(outcome, PassOutcome::Completed);
+        assert_eq!(
+            status(&events.lock().unwrap()),
+            vec![("slow".to_string(), PrefetchStatus::RateLimited)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_on_demand_fetch_is_shared_with_prefetch() {
+        let state = IdlePrefetchState::default();
+        let mut activity = state.activity.subscribe();
+        let events = Arc::new(StdMutex::new(Vec::new()));
+        let events_c = events.clone();
+        let emit = move |progress: PrefetchProgress| events_c.lock().unwrap().push(progress);
+        let fetch = |_: PrefetchTarget| async { Err::<FetchModelsResponse, _>("not expected".to_string()) };
+        let timing = PrefetchTiming {
+            idle_after: Duration::from_secs(1),
+            stale_after: Duration::from_secs(60),
+        };
+
+        // The picker fetched "fast", so a prefetch neither needs nor is allowed to fetch it
+        state.record_fetch("fast", FetchModelsResponse { models: Vec::new(), total: 0 });
+        assert!(state.cache.get("fast").is_some());
+        assert!(!state.limiter.lock().unwrap().try_acquire("fast"));
+
+        let outcome = run_prefetch_pass(
+            &[target("fast")],
+            timing,
+            &mut activity,
+            &state.cache,
+            &state.limiter,
+            &fetch,
+            &emit,
+        )
+        .await;
+        assert_eq!(outcome, PassOutcome::Completed);
+        assert!(events.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+//! OpenCode Install Module
+//!
+//! The locally installed opencode binary and the version it reports. The binary is
+//! looked up on the `PATH` of the user's shell, like `get_shell_env` sees it, since
+//! the app itself is often started without the shell's `PATH`.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::coding::oh_my_opencode::preflight::find_binary;
+
+/// The opencode binary found on `PATH`, `version` is `None` when it could not be run
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeInstall {
+    pub version: Option<String>,
+}
+
+/// `major.minor.patch` of a version string like `v0.15.3` or `0.15.3-beta.1`
+pub fn parse_version(raw: &str) -> Option<(u32, u32, u32)> {
+    let core = raw.trim().trim_start_matches('v');
+    let core = core.split(['-', '+', ' ']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u32>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch))
+}
+
+/// Run `opencode --version`, resolved on the shell `PATH` (and `PATHEXT` on Windows)
+pub async fn detect_opencode_install() -> OpenCodeInstall {
+    let env = super::shell_env::get_shell_env();
+    let Some(binary) = find_binary("opencode", &env) else {
+        return OpenCodeInstall::default();
+    };
+    OpenCodeInstall {
+        version: run_version(&binary, &env).await,
+    }
+}
+
+async fn run_version(binary: &Path, env: &HashMap<String, String>) -> Option<String> {
+    let mut command = tokio::process::Command::new(binary);
+    command.arg("--version");
+    // The npm shim starts node, which has to be found on the same PATH
+    if let Some(path) = env.get("PATH") {
+        command.env("PATH", path);
+    }
+    let output = command
+        .output()
+        .await
+        .ok()
+        .filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    stdout
+        .split_whitespace()
+        .last()
+        .map(str::to_string)
+        .filter(|version| parse_version(version).is_some())
+}
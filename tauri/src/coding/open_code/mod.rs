@@ -1,7 +1,15 @@
 pub mod adapter;
 pub mod commands;
 pub mod free_models;
+pub mod idle_prefetch;
+pub mod install;
+pub mod model_metadata;
 pub mod models_api;
+pub mod provider_diff;
+pub mod provider_health;
+pub mod provider_id;
+#[cfg(any(test, debug_assertions, feature = "sample-models"))]
+pub mod sample_models;
 pub mod shell_env;
 pub mod tray_support;
 pub mod types;
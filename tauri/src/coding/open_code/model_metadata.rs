@@ -0,0 +1,242 @@
+//! Model Metadata Module
+//!
+//! Context window and pricing for fetched models, from the bundled models.dev snapshot
+//! and the fresher copy in the provider models cache (see `free_models`).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::free_models;
+use super::models_api::FetchedModel;
+use super::provider_id::canonical_provider_id;
+
+/// Features a model supports beyond plain text chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModelCapability {
+    /// Tool (function) calling
+    Tool,
+    /// Image input
+    Vision,
+}
+
+/// Known facts about a model, every field is optional
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    /// USD per million input tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_cost: Option<f64>,
+    /// USD per million output tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_cost: Option<f64>,
+    /// Accepts reasoning settings (effort / thinking budget)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supports_reasoning: Option<bool>,
+    /// Complete capability list when known, `None` means unknown (not "none")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<Vec<ModelCapability>>,
+}
+
+impl ModelMetadata {
+    /// Overlay the fields `other` knows about
+    pub fn merge(&mut self, other: &ModelMetadata) {
+        self.context_window = other.context_window.or(self.context_window);
+        self.max_output_tokens = other.max_output_tokens.or(self.max_output_tokens);
+        self.input_cost = other.input_cost.or(self.input_cost);
+        self.output_cost = other.output_cost.or(self.output_cost);
+        self.supports_reasoning = other.supports_reasoning.or(self.supports_reasoning);
+        self.capabilities = other.capabilities.clone().or(self.capabilities.take());
+    }
+}
+
+/// A place model metadata can be looked up in
+pub trait ModelMetadataSource {
+    fn lookup(&self, model_id: &str) -> Option<ModelMetadata>;
+}
+
+/// Metadata by model id, built from models.dev data (provider -> models -> model)
+#[derive(Debug, Clone, Default)]
+pub struct MetadataTable {
+    /// By bare model id, the first provider listing a model wins
+    models: HashMap<String, ModelMetadata>,
+    /// By canonical provider id and model id
+    by_provider: HashMap<(String, String), ModelMetadata>,
+}
+
+impl MetadataTable {
+    /// Zero limits are treated as unknown
+    pub fn from_models_dev(data: &Value) -> Self {
+        let mut table = MetadataTable::default();
+        let providers = data.as_object().into_iter().flatten();
+        for (provider, models) in providers.filter_map(|(id, p)| Some((id, p.get("models")?.as_object()?))) {
+            let provider = canonical_provider_id(provider);
+            for (id, model) in models {
+                let limit = |key: &str| {
+                    model
+                        .get("limit")
+                        .and_then(|l| l.get(key))
+                        .and_then(|v| v.as_u64())
+                        .filter(|v| *v > 0)
+                };
+                let cost = |key: &str| model.get("cost").and_then(|c| c.get(key)).and_then(|v| v.as_f64());
+                let input_modalities = model
+                    .get("modalities")
+                    .and_then(|m| m.get("input"))
+                    .and_then(|v| v.as_array());
+                let tool_call = model.get("tool_call").and_then(|v| v.as_bool());
+                // Only listed when models.dev says anything about the features
+                let capabilities = (tool_call.is_some() || input_modalities.is_some()).then(|| {
+                    let mut capabilities = Vec::new();
+                    if tool_call == Some(true) {
+                        capabilities.push(ModelCapability::Tool);
+                    }
+                    if input_modalities.is_some_and(|m| m.iter().any(|v| v == "image")) {
+                        capabilities.push(ModelCapability::Vision);
+                    }
+                    capabilities
+                });
+                let metadata = ModelMetadata {
+                    context_window: limit("context"),
+                    max_output_tokens: limit("output"),
+                    input_cost: cost("input"),
+                    output_cost: cost("output"),
+                    supports_reasoning: model.get("reasoning").and_then(|v| v.as_bool()),
+                    capabilities,
+                };
+                table.models.entry(id.clone()).or_insert_with(|| metadata.clone());
+                table.by_provider.insert((provider.clone(), id.clone()), metadata);
+            }
+        }
+        table
+    }
+
+    /// Metadata of a `provider/model` id as that provider lists it, falling back to the
+    /// bare model id for providers the table does not know (proxies, custom ids)
+    pub fn lookup_qualified(&self, model_id: &str) -> Option<ModelMetadata> {
+        if let Some((provider, id)) = model_id.split_once('/') {
+            let key = (canonical_provider_id(provider), id.to_string());
+            if let Some(metadata) = self.by_provider.get(&key) {
+                return Some(metadata.clone());
+            }
+            if let Some(metadata) = self.models.get(id) {
+                return Some(metadata.clone());
+            }
+        }
+        self.lookup(model_id)
+    }
+}
+
+impl ModelMetadataSource for MetadataTable {
+    fn lookup(&self, model_id: &str) -> Option<ModelMetadata> {
+        self.models.get(model_id).cloned()
+    }
+}
+
+/// Built-in table from the bundled models.json, parsed once
+pub fn static_source() -> &'static MetadataTable {
+    static TABLE: OnceLock<MetadataTable> = OnceLock::new();
+    TABLE.get_or_init(|| MetadataTable::from_models_dev(&free_models::get_all_default_providers_data()))
+}
+
+/// Whether the built-in table knows a `provider/model` (or bare) model id to support reasoning settings
+pub fn model_supports_reasoning(model_id: &str) -> Option<bool> {
+    static_source().lookup_qualified(model_id)?.supports_reasoning
+}
+
+/// Capabilities the built-in table knows for a `provider/model` (or bare) model id
+pub fn model_capabilities(model_id: &str) -> Option<Vec<ModelCapability>> {
+    static_source().lookup_qualified(model_id)?.capabilities
+}
+
+/// Attach metadata to fetched models, later sources override earlier ones field by field
+pub fn enrich_models(models: &mut [FetchedModel], sources: &[&dyn ModelMetadataSource]) {
+    for model in models.iter_mut() {
+        let mut metadata = model.metadata.clone().unwrap_or_default();
+        let mut found = model.metadata.is_some();
+        for source in sources {
+            if let Some(source_metadata) = source.lookup(&model.id) {
+                metadata.merge(&source_metadata);
+                found = true;
+            }
+        }
+        if found {
+            model.metadata = Some(metadata);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_remote_metadata_overrides_static() {
+        let static_table = MetadataTable::from_models_dev(&json!({
+            "openai": { "models": {
+                "gpt-4o": { "limit": { "context": 128000, "output": 4096 }, "cost": { "input": 5, "output": 15 } }
+            } }
+        }));
+        let remote_table = MetadataTable::from_models_dev(&json!({
+            "openai": { "models": {
+                "gpt-4o": { "limit": { "context": 0, "output": 16384 }, "cost": { "input": 2.5, "output": 10 } }
+            } }
+        }));
+
+        let mut models = vec![
+            FetchedModel {
+                id: "gpt-4o".to_string(),
+                name: None,
+                owned_by: None,
+                created: None,
+                metadata: None,
+            },
+            FetchedModel {
+                id: "unknown".to_string(),
+                name: None,
+                owned_by: None,
+                created: None,
+                metadata: None,
+            },
+        ];
+        enrich_models(&mut models, &[&static_table, &remote_table]);
+
+        assert_eq!(
+            models[0].metadata,
+            Some(ModelMetadata {
+                // Unknown (zero) in the remote table, kept from the static one
+                context_window: Some(128000),
+                max_output_tokens: Some(16384),
+                input_cost: Some(2.5),
+                output_cost: Some(10.0),
+                supports_reasoning: None,
+                capabilities: None,
+            })
+        );
+        assert_eq!(models[1].metadata, None);
+    }
+
+    #[test]
+    fn test_lookup_qualified_uses_provider_prefix() {
+        let table = MetadataTable::from_models_dev(&json!({
+            "openrouter": { "models": { "deepseek-r1": { "reasoning": false }, "o3": { "reasoning": true } } },
+            "deepseek": { "models": { "deepseek-r1": { "reasoning": true } } }
+        }));
+
+        let reasoning = |id: &str| table.lookup_qualified(id).and_then(|m| m.supports_reasoning);
+        assert_eq!(reasoning("deepseek/deepseek-r1"), Some(true));
+        assert_eq!(reasoning("DeepSeek/deepseek-r1"), Some(true));
+        assert_eq!(reasoning("openrouter/deepseek-r1"), Some(false));
+        // Unknown providers and bare ids fall back to the bare model id
+        assert_eq!(reasoning("my-proxy/o3"), Some(true));
+        assert_eq!(reasoning("o3"), Some(true));
+        assert_eq!(reasoning("deepseek/unknown"), None);
+    }
+}
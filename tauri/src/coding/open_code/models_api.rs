@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use super::free_models;
+use super::idle_prefetch::IdlePrefetchState;
+use super::model_metadata::{self, MetadataTable, ModelMetadata, ModelMetadataSource};
 use crate::db::DbState;
 use crate::http_client;
 
@@ -24,6 +27,12 @@ pub struct FetchModelsRequest {
     pub sdk_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_url: Option<String>,
+    /// Only these model ids are returned, empty means all
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_models: Option<Vec<String>>,
+    /// These model ids are never returned, wins over `allowed_models`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub denied_models: Option<Vec<String>>,
 }
 
 /// OpenAI compatible models list response
@@ -98,6 +107,9 @@ pub struct FetchedModel {
     pub name: Option<String>,
     pub owned_by: Option<String>,
     pub created: Option<i64>,
+    /// Context window and pricing, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ModelMetadata>,
 }
 
 /// Response for fetch models command
@@ -158,14 +170,70 @@ fn build_models_url(
 }
 
 /// Fetch models list from provider API
+/// With `provider_id` the list is shared with the idle prefetch: it is cached for the
+/// picker and counts as a fetch for the prefetch rate limiter
 #[tauri::command]
 pub async fn fetch_provider_models(
     state: tauri::State<'_, DbState>,
+    prefetch: tauri::State<'_, IdlePrefetchState>,
     request: FetchModelsRequest,
+    provider_id: Option<String>,
+) -> Result<FetchModelsResponse, String> {
+    let response = fetch_enriched_models(&state, &request).await?;
+    if let Some(provider_id) = provider_id {
+        prefetch.record_fetch(&provider_id, response.clone());
+    }
+    Ok(response)
+}
+
+/// Fetch the models of a provider with their metadata
+pub async fn fetch_enriched_models(
+    state: &DbState,
+    request: &FetchModelsRequest,
 ) -> Result<FetchModelsResponse, String> {
     // Create HTTP client with timeout and proxy support
-    let client = http_client::client_with_timeout(&state, 30).await?;
+    let client = http_client::client_with_timeout(state, 30).await?;
 
+    let mut response = fetch_models(&client, request).await?;
+
+    // Built-in metadata, overridden by the provider models cache; never waits on models.dev
+    let remote = match free_models::read_cached_providers_data(state).await {
+        Ok(data) => Some(MetadataTable::from_models_dev(&data)),
+        Err(e) => {
+            eprintln!("Failed to load cached model metadata: {}", e);
+            None
+        }
+    };
+    let mut sources: Vec<&dyn ModelMetadataSource> = vec![model_metadata::static_source()];
+    if let Some(remote) = &remote {
+        sources.push(remote);
+    }
+    model_metadata::enrich_models(&mut response.models, &sources);
+
+    Ok(response)
+}
+
+/// Sample models for a provider SDK, for the model picker in development
+#[tauri::command]
+pub fn get_sample_provider_models(sdk_type: Option<String>) -> Result<FetchModelsResponse, String> {
+    #[cfg(any(debug_assertions, feature = "sample-models"))]
+    {
+        let models = super::sample_models::sample_models(sdk_type.as_deref().unwrap_or_default());
+        let total = models.len();
+        Ok(FetchModelsResponse { models, total })
+    }
+    #[cfg(not(any(debug_assertions, feature = "sample-models")))]
+    {
+        let _ = sdk_type;
+        Err("Sample models are not available in this build".to_string())
+    }
+}
+
+/// Fetch models list from provider API with the given client
+pub async fn fetch_models(
+    client: &reqwest::Client,
+    request: &FetchModelsRequest,
+) -> Result<FetchModelsResponse, String> {
     // Build request URL based on API type and SDK type
     // Use custom_url if provided, otherwise calculate it
     let url = if let Some(custom) = &request.custom_url {
@@ -244,7 +312,7 @@ pub async fn fetch_provider_models(
     }
 
     // Parse response based on SDK type and API type
-    let models: Vec<FetchedModel> = match (request.api_type, request.sdk_type.as_deref()) {
+    let models: Vec<FetchedModel> = match (&request.api_type, request.sdk_type.as_deref()) {
         (ApiType::Native, Some("@ai-sdk/google")) => {
             // Parse Google AI response format
             let google_response: GoogleModelsResponse = response
@@ -268,6 +336,7 @@ pub async fn fetch_provider_models(
                         name: m.display_name.or(Some(id)),
                         owned_by: Some("google".to_string()),
                         created: None,
+                        metadata: None,
                     }
                 })
                 .collect()
@@ -289,6 +358,7 @@ pub async fn fetch_provider_models(
                         name: Some(name),
                         owned_by: Some("anthropic".to_string()),
                         created: None,
+                        metadata: None,
                     }
                 })
                 .collect()
@@ -310,20 +380,74 @@ pub async fn fetch_provider_models(
                     name: Some(m.id),
                     owned_by: m.owned_by,
                     created: m.created,
+                    metadata: None,
                 })
                 .collect()
         }
     };
 
+    let models = filter_models(
+        models,
+        request.allowed_models.as_deref().unwrap_or_default(),
+        request.denied_models.as_deref().unwrap_or_default(),
+    );
     let total = models.len();
 
     Ok(FetchModelsResponse { models, total })
 }
 
+/// Apply a provider's allow/deny lists to fetched models
+/// Deny wins over allow, an empty allow list permits every model
+pub fn filter_models(
+    models: Vec<FetchedModel>,
+    allowed: &[String],
+    denied: &[String],
+) -> Vec<FetchedModel> {
+    models
+        .into_iter()
+        .filter(|m| allowed.is_empty() || allowed.contains(&m.id))
+        .filter(|m| !denied.contains(&m.id))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn fetched(ids: &[&str]) -> Vec<FetchedModel> {
+        ids.iter()
+            .map(|id| FetchedModel {
+                id: id.to_string(),
+                name: Some(id.to_string()),
+                owned_by: None,
+                created: None,
+                metadata: None,
+            })
+            .collect()
+    }
+
+    fn ids(models: &[FetchedModel]) -> Vec<&str> {
+        models.iter().map(|m| m.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_models_allow_list() {
+        let models = fetched(&["gpt-4o", "gpt-4o-mini", "o3", "dall-e-3", "whisper-1"]);
+        let allowed = vec!["gpt-4o".to_string(), "o3".to_string()];
+        assert_eq!(ids(&filter_models(models, &allowed, &[])), vec!["gpt-4o", "o3"]);
+    }
+
+    #[test]
+    fn test_filter_models_deny_list() {
+        let models = fetched(&["gpt-4o", "gpt-4o-mini", "o3"]);
+        let denied = vec!["gpt-4o-mini".to_string()];
+        assert_eq!(ids(&filter_models(models.clone(), &[], &denied)), vec!["gpt-4o", "o3"]);
+
+        // Deny wins over allow
+        let allowed = vec!["gpt-4o".to_string(), "gpt-4o-mini".to_string()];
+        assert_eq!(ids(&filter_models(models, &allowed, &denied)), vec!["gpt-4o"]);
+    }
+
     #[test]
     fn test_build_models_url_openai_compat() {
         // Base URL without /v1
@@ -0,0 +1,183 @@
+//! Provider Diff Module
+//!
+//! Compares two sets of configured providers, e.g. mine and a teammate's, matched by id.
+//! Secret values are masked, so the result can be shared; `{env:...}` references are
+//! not secrets and stay visible.
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::types::OpenCodeProvider;
+use crate::coding::redact::{is_secret_key, REDACTED};
+
+/// A field that differs between two providers with the same id, absent values are `null`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldDiff {
+    pub field: String,
+    pub mine: Value,
+    pub theirs: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ProviderDiff {
+    OnlyMine { id: String },
+    OnlyTheirs { id: String },
+    Changed { id: String, fields: Vec<FieldDiff> },
+}
+
+/// Shown value of a field, secret strings are replaced
+fn masked(field: &str, value: &Value) -> Value {
+    let secret = field == "apiKey" || field.strip_prefix("headers.").is_some_and(is_secret_key);
+    match value.as_str() {
+        Some(s) if secret && !s.is_empty() && !s.starts_with("{env:") => json!(REDACTED),
+        _ => value.clone(),
+    }
+}
+
+/// How a provider authenticates: `apiKey`, `header:<name>` or `none`
+pub fn auth_scheme(provider: &OpenCodeProvider) -> String {
+    let options = provider.options.as_ref();
+    if options.and_then(|o| o.api_key.as_deref()).is_some_and(|key| !key.is_empty()) {
+        return "apiKey".to_string();
+    }
+    let headers = options.and_then(|o| o.headers.as_ref()).and_then(|h| h.as_object());
+    match headers.into_iter().flatten().find(|(name, _)| is_secret_key(name)) {
+        Some((name, _)) => format!("header:{}", name),
+        None => "none".to_string(),
+    }
+}
+
+/// Comparable fields of a provider, unmasked
+fn provider_fields(provider: &OpenCodeProvider) -> IndexMap<String, Value> {
+    let options = provider.options.as_ref();
+    let mut fields = IndexMap::new();
+    fields.insert("npm".to_string(), json!(provider.npm));
+    fields.insert("name".to_string(), json!(provider.name));
+    fields.insert("baseURL".to_string(), json!(options.and_then(|o| o.base_url.as_deref())));
+    fields.insert("authScheme".to_string(), json!(auth_scheme(provider)));
+    fields.insert("apiKey".to_string(), json!(options.and_then(|o| o.api_key.as_deref())));
+    let headers = options.and_then(|o| o.headers.as_ref()).and_then(|h| h.as_object());
+    for (name, value) in headers.into_iter().flatten() {
+        fields.insert(format!("headers.{}", name), value.clone());
+    }
+    fields.insert("timeout".to_string(), json!(options.and_then(|o| o.timeout.as_ref())));
+    fields.insert("setCacheKey".to_string(), json!(options.and_then(|o| o.set_cache_key)));
+    let mut models: Vec<&String> = provider.models.keys().collect();
+    models.sort();
+    fields.insert("models".to_string(), json!(models));
+    fields
+}
+
+/// Fields compared on their real values, secrets are masked afterwards
+fn diff_fields(mine: &OpenCodeProvider, theirs: &OpenCodeProvider) -> Vec<FieldDiff> {
+    let mine = provider_fields(mine);
+    let theirs = provider_fields(theirs);
+    let names = mine.keys().chain(theirs.keys().filter(|name| !mine.contains_key(*name)));
+    names
+        .filter_map(|name| {
+            let mine = mine.get(name).unwrap_or(&Value::Null);
+            let theirs = theirs.get(name).unwrap_or(&Value::Null);
+            (mine != theirs).then(|| FieldDiff {
+                field: name.clone(),
+                mine: masked(name, mine),
+                theirs: masked(name, theirs),
+            })
+        })
+        .collect()
+}
+
+/// Providers only on one side and the differing fields of common ones:
+/// changed providers in my order, then mine only, then theirs only
+pub fn diff_providers(
+    mine: &IndexMap<String, OpenCodeProvider>,
+    theirs: &IndexMap<String, OpenCodeProvider>,
+) -> Vec<ProviderDiff> {
+    let mut diffs = Vec::new();
+    for (id, provider) in mine {
+        if let Some(other) = theirs.get(id) {
+            let fields = diff_fields(provider, other);
+            if !fields.is_empty() {
+                diffs.push(ProviderDiff::Changed { id: id.clone(), fields });
+            }
+        }
+    }
+    for id in mine.keys().filter(|id| !theirs.contains_key(*id)) {
+        diffs.push(ProviderDiff::OnlyMine { id: id.clone() });
+    }
+    for id in theirs.keys().filter(|id| !mine.contains_key(*id)) {
+        diffs.push(ProviderDiff::OnlyTheirs { id: id.clone() });
+    }
+    diffs
+}
+
+/// Compare my providers with a teammate's, secrets masked
+#[tauri::command]
+pub fn diff_opencode_providers(
+    mine: IndexMap<String, OpenCodeProvider>,
+    theirs: IndexMap<String, OpenCodeProvider>,
+) -> Vec<ProviderDiff> {
+    diff_providers(&mine, &theirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coding::open_code::types::OpenCodeProviderOptions;
+    use std::collections::HashMap;
+
+    fn provider(base_url: &str, api_key: &str) -> OpenCodeProvider {
+        OpenCodeProvider {
+            npm: Some("@ai-sdk/openai-compatible".to_string()),
+            name: Some("Example".to_string()),
+            options: Some(OpenCodeProviderOptions {
+                base_url: Some(base_url.to_string()),
+                api_key: Some(api_key.to_string()),
+                headers: None,
+                timeout: None,
+                set_cache_key: None,
+            }),
+            models: HashMap::new(),
+            whitelist: None,
+            blacklist: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_providers() {
+        let mine = IndexMap::from([
+            ("example".to_string(), provider("https://api.example.com/v1", "sk-mine")),
+            ("local".to_string(), provider("http://localhost:11434/v1", "")),
+        ]);
+        let theirs = IndexMap::from([
+            ("example".to_string(), provider("https://proxy.example.com/v1", "sk-theirs")),
+            ("deepseek".to_string(), provider("https://api.deepseek.com", "{env:DEEPSEEK_API_KEY}")),
+        ]);
+
+        assert_eq!(
+            diff_providers(&mine, &theirs),
+            vec![
+                ProviderDiff::Changed {
+                    id: "example".to_string(),
+                    fields: vec![
+                        FieldDiff {
+                            field: "baseURL".to_string(),
+                            mine: json!("https://api.example.com/v1"),
+                            theirs: json!("https://proxy.example.com/v1"),
+                        },
+                        FieldDiff {
+                            field: "apiKey".to_string(),
+                            mine: json!(REDACTED),
+                            theirs: json!(REDACTED),
+                        },
+                    ],
+                },
+                ProviderDiff::OnlyMine { id: "local".to_string() },
+                ProviderDiff::OnlyTheirs { id: "deepseek".to_string() },
+            ]
+        );
+        assert!(diff_providers(&mine, &mine).is_empty());
+    }
+}
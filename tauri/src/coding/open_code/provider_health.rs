@@ -0,0 +1,217 @@
+//! Provider Health Monitor Module
+//!
+//! Periodically checks configured providers and emits `provider-health` events.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::Mutex;
+
+use super::models_api::{fetch_models, FetchModelsRequest};
+use crate::coding::concurrency::run_bounded;
+use crate::db::DbState;
+use crate::http_client;
+
+/// Max providers checked at the same time
+const MONITOR_CONCURRENCY: usize = 4;
+/// Timeout for a single health check
+const CHECK_TIMEOUT_SECS: u64 = 10;
+/// Lower bound for the check interval
+const MIN_INTERVAL_SECS: u64 = 10;
+
+/// Provider to monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthTarget {
+    pub provider_id: String,
+    pub request: FetchModelsRequest,
+}
+
+/// Status change since the previous check
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthTransition {
+    /// Was up, now down
+    WentDown,
+    /// Was down, now up
+    Recovered,
+}
+
+/// Health of a single provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealth {
+    pub provider_id: String,
+    pub up: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transition: Option<HealthTransition>,
+}
+
+/// Payload of the `provider-health` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderHealthEvent {
+    pub checked_at: String,
+    pub providers: Vec<ProviderHealth>,
+}
+
+/// Running monitor task, managed as Tauri state
+#[derive(Default)]
+pub struct ProviderMonitorState(pub Mutex<Option<tauri::async_runtime::JoinHandle<()>>>);
+
+/// Test a provider connection by listing its models
+/// Returns the latency in milliseconds
+pub async fn test_provider_connection(
+    client: &reqwest::Client,
+    request: &FetchModelsRequest,
+) -> Result<u64, String> {
+    let started = Instant::now();
+    fetch_models(client, request).await?;
+    Ok(started.elapsed().as_millis() as u64)
+}
+
+/// Build health entries from check results, flagging changes against the previous status
+pub fn evaluate_health(
+    previous: &HashMap<String, bool>,
+    results: Vec<(String, Result<u64, String>)>,
+) -> Vec<ProviderHealth> {
+    results
+        .into_iter()
+        .map(|(provider_id, result)| {
+            let up = result.is_ok();
+            let transition = match previous.get(&provider_id) {
+                Some(true) if !up => Some(HealthTransition::WentDown),
+                Some(false) if up => Some(HealthTransition::Recovered),
+                _ => None,
+            };
+            let (latency_ms, error) = match result {
+                Ok(latency) => (Some(latency), None),
+                Err(e) => (None, Some(e)),
+            };
+            ProviderHealth {
+                provider_id,
+                up,
+                latency_ms,
+                error,
+                transition,
+            }
+        })
+        .collect()
+}
+
+/// Check all providers once, with bounded concurrency
+async fn check_providers<R: Runtime>(
+    app: &AppHandle<R>,
+    targets: &[ProviderHealthTarget],
+) -> Vec<(String, Result<u64, String>)> {
+    let db_state = app.state::<DbState>();
+    let client = match http_client::client_with_timeout(&db_state, CHECK_TIMEOUT_SECS).await {
+        Ok(client) => client,
+        Err(e) => {
+            return targets
+                .iter()
+                .map(|t| (t.provider_id.clone(), Err(e.clone())))
+                .collect();
+        }
+    };
+
+    run_bounded(targets.to_vec(), MONITOR_CONCURRENCY, move |target| {
+        let client = client.clone();
+        async move {
+            let result = test_provider_connection(&client, &target.request).await;
+            (target.provider_id, result)
+        }
+    })
+    .await
+}
+
+/// Start (or restart) the provider monitor
+#[tauri::command]
+pub async fn start_provider_monitor(
+    app: AppHandle,
+    monitor: tauri::State<'_, ProviderMonitorState>,
+    providers: Vec<ProviderHealthTarget>,
+    interval_secs: u64,
+) -> Result<(), String> {
+    let mut running = monitor.0.lock().await;
+    if let Some(handle) = running.take() {
+        handle.abort();
+    }
+
+    let interval = Duration::from_secs(interval_secs.max(MIN_INTERVAL_SECS));
+    let app_handle = app.clone();
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut previous: HashMap<String, bool> = HashMap::new();
+        loop {
+            let results = check_providers(&app_handle, &providers).await;
+            let health = evaluate_health(&previous, results);
+            previous = health
+                .iter()
+                .map(|h| (h.provider_id.clone(), h.up))
+                .collect();
+
+            let _ = app_handle.emit(
+                "provider-health",
+                ProviderHealthEvent {
+                    checked_at: chrono::Local::now().to_rfc3339(),
+                    providers: health,
+                },
+            );
+
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    *running = Some(handle);
+    Ok(())
+}
+
+/// Stop the provider monitor if running
+#[tauri::command]
+pub async fn stop_provider_monitor(
+    monitor: tauri::State<'_, ProviderMonitorState>,
+) -> Result<(), String> {
+    if let Some(handle) = monitor.0.lock().await.take() {
+        handle.abort();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_health_flags_transitions() {
+        let first = evaluate_health(
+            &HashMap::new(),
+            vec![
+                ("openai".to_string(), Ok(120)),
+                ("qwen".to_string(), Err("timeout".to_string())),
+            ],
+        );
+        // No previous status, no transition
+        assert!(first.iter().all(|h| h.transition.is_none()));
+
+        let previous: HashMap<String, bool> =
+            first.iter().map(|h| (h.provider_id.clone(), h.up)).collect();
+        let second = evaluate_health(
+            &previous,
+            vec![
+                ("openai".to_string(), Err("connection refused".to_string())),
+                ("qwen".to_string(), Ok(80)),
+            ],
+        );
+
+        assert!(!second[0].up);
+        assert_eq!(second[0].transition, Some(HealthTransition::WentDown));
+        assert_eq!(second[0].error.as_deref(), Some("connection refused"));
+        assert_eq!(second[1].transition, Some(HealthTransition::Recovered));
+        assert_eq!(second[1].latency_ms, Some(80));
+    }
+}
@@ -0,0 +1,85 @@
+//! Provider Id Module
+//!
+//! Canonical provider ids. Model ids reference providers by prefix, and the same
+//! provider shows up as `openai`, `OpenAI` or `open-ai`; matching always goes
+//! through `canonical_provider_id` so those spellings are the same provider.
+//! Only comparisons use it, saved provider keys are never rewritten since opencode
+//! resolves models by the exact key.
+
+/// Known spellings, keyed by the folded form (lowercase, no `-`, `_`, `.` or spaces)
+const PROVIDER_ALIASES: &[(&str, &str)] = &[
+    ("openai", "openai"),
+    ("anthropic", "anthropic"),
+    ("claude", "anthropic"),
+    ("google", "google"),
+    ("googleai", "google"),
+    ("gemini", "google"),
+    ("googlevertex", "google-vertex"),
+    ("vertex", "google-vertex"),
+    ("vertexai", "google-vertex"),
+    ("azure", "azure"),
+    ("azureopenai", "azure"),
+    ("openrouter", "openrouter"),
+    ("deepseek", "deepseek"),
+    ("mistral", "mistral"),
+    ("mistralai", "mistral"),
+    ("xai", "xai"),
+    ("grok", "xai"),
+    ("groq", "groq"),
+    ("together", "togetherai"),
+    ("togetherai", "togetherai"),
+    ("bedrock", "amazon-bedrock"),
+    ("amazonbedrock", "amazon-bedrock"),
+    ("awsbedrock", "amazon-bedrock"),
+    ("githubcopilot", "github-copilot"),
+    ("copilot", "github-copilot"),
+    ("zhipu", "zhipuai"),
+    ("zhipuai", "zhipuai"),
+    ("moonshot", "moonshotai"),
+    ("moonshotai", "moonshotai"),
+    ("kimi", "moonshotai"),
+    ("ollama", "ollama"),
+    ("opencode", "opencode"),
+];
+
+fn fold(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| !matches!(c, '-' | '_' | '.' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Canonical id of a provider; unknown providers pass through trimmed and lowercased
+pub fn canonical_provider_id(raw: &str) -> String {
+    let folded = fold(raw);
+    PROVIDER_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == folded)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or_else(|| raw.trim().to_lowercase())
+}
+
+/// Canonical id of a provider, for the frontend
+#[tauri::command]
+pub fn get_canonical_provider_id(raw: String) -> String {
+    canonical_provider_id(&raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_map_to_canonical_id() {
+        for raw in ["openai", "OpenAI", "open-ai", " Open_AI "] {
+            assert_eq!(canonical_provider_id(raw), "openai", "{raw}");
+        }
+        for raw in ["gemini", "Google", "google-ai"] {
+            assert_eq!(canonical_provider_id(raw), "google", "{raw}");
+        }
+        assert_eq!(canonical_provider_id("AWS Bedrock"), "amazon-bedrock");
+        assert_eq!(canonical_provider_id("GitHub-Copilot"), "github-copilot");
+        // Unknown providers only lose case and surrounding whitespace
+        assert_eq!(canonical_provider_id(" My-Proxy "), "my-proxy");
+    }
+}
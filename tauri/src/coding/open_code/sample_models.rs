@@ -0,0 +1,113 @@
+//! Sample Models Module
+//!
+//! Representative model lists per provider SDK for the model picker in development,
+//! so the UI can be worked on without provider accounts. Samples go through the same
+//! filtering and metadata enrichment as fetched models, with the sample metadata taking
+//! the place of the remote models.dev copy.
+//! Only built for tests, debug builds and the `sample-models` feature.
+
+use serde_json::{json, Value};
+
+use super::model_metadata::{self, MetadataTable, ModelMetadataSource};
+use super::models_api::{filter_models, FetchedModel};
+
+/// Provider SDKs with samples, anything else gets the OpenAI compatible set
+pub const SAMPLE_PROVIDER_KINDS: &[&str] = &[
+    "@ai-sdk/openai-compatible",
+    "@ai-sdk/openai",
+    "@ai-sdk/anthropic",
+    "@ai-sdk/google",
+];
+
+/// (owner, models in models.dev shape)
+fn sample_data(provider_kind: &str) -> (&'static str, Value) {
+    match provider_kind {
+        "@ai-sdk/anthropic" => (
+            "anthropic",
+            json!({
+                "claude-opus-4-20250514": { "name": "Claude Opus 4", "reasoning": true,
+                    "limit": { "context": 200000, "output": 32000 }, "cost": { "input": 15, "output": 75 } },
+                "claude-sonnet-4-20250514": { "name": "Claude Sonnet 4", "reasoning": true,
+                    "limit": { "context": 200000, "output": 64000 }, "cost": { "input": 3, "output": 15 } },
+                "claude-3-5-haiku-20241022": { "name": "Claude Haiku 3.5", "reasoning": false,
+                    "limit": { "context": 200000, "output": 8192 }, "cost": { "input": 0.8, "output": 4 } }
+            }),
+        ),
+        "@ai-sdk/google" => (
+            "google",
+            json!({
+                "gemini-2.5-pro": { "name": "Gemini 2.5 Pro", "reasoning": true,
+                    "limit": { "context": 1048576, "output": 65536 }, "cost": { "input": 1.25, "output": 10 } },
+                "gemini-2.5-flash": { "name": "Gemini 2.5 Flash", "reasoning": true,
+                    "limit": { "context": 1048576, "output": 65536 }, "cost": { "input": 0.3, "output": 2.5 } },
+                "gemini-2.0-flash": { "name": "Gemini 2.0 Flash", "reasoning": false,
+                    "limit": { "context": 1048576, "output": 8192 }, "cost": { "input": 0.1, "output": 0.4 } }
+            }),
+        ),
+        "@ai-sdk/openai" => (
+            "openai",
+            json!({
+                "gpt-4o": { "name": "GPT-4o", "reasoning": false,
+                    "limit": { "context": 128000, "output": 16384 }, "cost": { "input": 2.5, "output": 10 } },
+                "gpt-4o-mini": { "name": "GPT-4o mini", "reasoning": false,
+                    "limit": { "context": 128000, "output": 16384 }, "cost": { "input": 0.15, "output": 0.6 } },
+                "o3": { "name": "o3", "reasoning": true,
+                    "limit": { "context": 200000, "output": 100000 }, "cost": { "input": 2, "output": 8 } }
+            }),
+        ),
+        _ => (
+            "openai-compatible",
+            json!({
+                "deepseek-chat": { "name": "DeepSeek V3", "reasoning": false,
+                    "limit": { "context": 65536, "output": 8192 }, "cost": { "input": 0.27, "output": 1.1 } },
+                "deepseek-reasoner": { "name": "DeepSeek R1", "reasoning": true,
+                    "limit": { "context": 65536, "output": 32768 }, "cost": { "input": 0.55, "output": 2.19 } },
+                "qwen3-coder-plus": { "name": "Qwen3 Coder Plus", "reasoning": false,
+                    "limit": { "context": 1048576, "output": 65536 }, "cost": { "input": 1, "output": 5 } }
+            }),
+        ),
+    }
+}
+
+/// Enriched sample models for a provider SDK (`@ai-sdk/anthropic`, ...)
+pub fn sample_models(provider_kind: &str) -> Vec<FetchedModel> {
+    let (owner, models) = sample_data(provider_kind);
+    let models_object = models.as_object().cloned().unwrap_or_default();
+    let fetched: Vec<FetchedModel> = models_object
+        .iter()
+        .map(|(id, model)| FetchedModel {
+            id: id.clone(),
+            name: model.get("name").and_then(|v| v.as_str()).map(str::to_string),
+            owned_by: Some(owner.to_string()),
+            created: None,
+            metadata: None,
+        })
+        .collect();
+
+    let mut fetched = filter_models(fetched, &[], &[]);
+    let sample_table = MetadataTable::from_models_dev(&json!({ owner: { "models": models } }));
+    let sources: [&dyn ModelMetadataSource; 2] = [model_metadata::static_source(), &sample_table];
+    model_metadata::enrich_models(&mut fetched, &sources);
+    fetched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samples_are_enriched_for_each_kind() {
+        for kind in SAMPLE_PROVIDER_KINDS {
+            let models = sample_models(kind);
+            assert!(!models.is_empty(), "{}", kind);
+            for model in &models {
+                let metadata = model.metadata.as_ref().unwrap_or_else(|| panic!("{} has no metadata", model.id));
+                assert!(metadata.context_window.is_some_and(|v| v > 0), "{}", model.id);
+                assert!(metadata.max_output_tokens.is_some(), "{}", model.id);
+                assert!(metadata.input_cost.is_some() && metadata.output_cost.is_some(), "{}", model.id);
+                assert!(metadata.supports_reasoning.is_some(), "{}", model.id);
+                assert!(model.name.is_some() && model.owned_by.is_some());
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 
@@ -168,6 +170,250 @@ fn expand_env_vars(value: &str) -> String {
     result
 }
 
+/// Collect every variable exported by the shell configuration files
+///
+/// Files are applied from lowest to highest priority, so the result matches
+/// `get_env_from_shell_config` for each variable
+pub fn get_shell_env_from_config() -> HashMap<String, String> {
+    let mut env = HashMap::new();
+    let Some(config_files) = get_shell_config_files() else {
+        return env;
+    };
+
+    for config_file in config_files.iter().rev() {
+        let Ok(content) = fs::read_to_string(config_file) else {
+            continue;
+        };
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') {
+                continue;
+            }
+            let without_export = trimmed.strip_prefix("export ").unwrap_or(trimmed).trim();
+            let Some((name, _)) = without_export.split_once('=') else {
+                continue;
+            };
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                continue;
+            }
+            if let Some(value) = parse_export_line(trimmed, name) {
+                env.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    env
+}
+
+/// Process environment overlaid with the shell configuration exports
+pub fn get_shell_env() -> HashMap<String, String> {
+    let started = std::time::Instant::now();
+    let mut env: HashMap<String, String> = std::env::vars().collect();
+    env.extend(get_shell_env_from_config());
+    crate::coding::metrics::recorder().record_shell_env(started.elapsed());
+    env
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvDiagnosticSeverity {
+    /// opencode will most likely fail to start
+    Error,
+    /// Some features (usually MCP servers) may break
+    Warning,
+}
+
+/// A problem found in the environment, with a suggested fix
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvDiagnostic {
+    pub variable: String,
+    pub severity: EnvDiagnosticSeverity,
+    pub message: String,
+    pub remediation: String,
+}
+
+impl EnvDiagnostic {
+    fn new(variable: &str, severity: EnvDiagnosticSeverity, message: String, remediation: &str) -> Self {
+        Self {
+            variable: variable.to_string(),
+            severity,
+            message,
+            remediation: remediation.to_string(),
+        }
+    }
+}
+
+/// `NODE_OPTIONS` flags that break Node-based MCP servers
+const PROBLEMATIC_NODE_OPTIONS: &[&str] = &["--inspect", "--inspect-brk", "--require", "-r", "--loader", "--import"];
+
+/// Check an environment for settings known to break opencode
+pub fn diagnose_shell_env(env: &HashMap<String, String>) -> Vec<EnvDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let separator = if cfg!(windows) { ';' } else { ':' };
+
+    match env.get("PATH").map(|path| path.trim()) {
+        None | Some("") => diagnostics.push(EnvDiagnostic::new(
+            "PATH",
+            EnvDiagnosticSeverity::Error,
+            "PATH is empty, opencode and MCP commands cannot be found".to_string(),
+            "Export a PATH that includes the system directories in your shell configuration",
+        )),
+        Some(path) => {
+            if path.split(separator).any(|entry| entry.trim().is_empty()) {
+                diagnostics.push(EnvDiagnostic::new(
+                    "PATH",
+                    EnvDiagnosticSeverity::Warning,
+                    "PATH contains an empty entry, which resolves to the current directory".to_string(),
+                    "Remove the leading, trailing or doubled separator from PATH",
+                ));
+            }
+            if let Some(entry) = path.split(separator).find(|entry| entry.contains('$') || entry.starts_with('~')) {
+                diagnostics.push(EnvDiagnostic::new(
+                    "PATH",
+                    EnvDiagnosticSeverity::Warning,
+                    format!("PATH entry '{}' is not expanded", entry),
+                    "Use $HOME instead of ~ and make sure referenced variables are defined before PATH",
+                ));
+            }
+        }
+    }
+
+    let has_home = ["HOME", "USERPROFILE"]
+        .iter()
+        .any(|name| env.get(*name).map(|v| !v.trim().is_empty()).unwrap_or(false));
+    if !has_home {
+        diagnostics.push(EnvDiagnostic::new(
+            "HOME",
+            EnvDiagnosticSeverity::Error,
+            "Neither HOME nor USERPROFILE is set, the config directory cannot be located".to_string(),
+            "Set HOME (or USERPROFILE on Windows) to your user directory",
+        ));
+    }
+
+    if let Some(options) = env.get("NODE_OPTIONS") {
+        for option in options.split_whitespace() {
+            let flag = option.split('=').next().unwrap_or(option);
+            if PROBLEMATIC_NODE_OPTIONS.contains(&flag) {
+                diagnostics.push(EnvDiagnostic::new(
+                    "NODE_OPTIONS",
+                    EnvDiagnosticSeverity::Warning,
+                    format!("NODE_OPTIONS contains '{}', which is applied to every Node-based MCP server", flag),
+                    "Remove the flag from NODE_OPTIONS or set it only for the process that needs it",
+                ));
+            }
+        }
+    }
+
+    if env.get("ELECTRON_RUN_AS_NODE").is_some() {
+        diagnostics.push(EnvDiagnostic::new(
+            "ELECTRON_RUN_AS_NODE",
+            EnvDiagnosticSeverity::Warning,
+            "ELECTRON_RUN_AS_NODE is set, Electron-based tools started by opencode run as plain Node".to_string(),
+            "Unset ELECTRON_RUN_AS_NODE in your shell configuration",
+        ));
+    }
+
+    if env.get("NODE_TLS_REJECT_UNAUTHORIZED").map(|v| v.trim()) == Some("0") {
+        diagnostics.push(EnvDiagnostic::new(
+            "NODE_TLS_REJECT_UNAUTHORIZED",
+            EnvDiagnosticSeverity::Warning,
+            "TLS certificate verification is disabled for Node-based MCP servers".to_string(),
+            "Unset NODE_TLS_REJECT_UNAUTHORIZED and use NODE_EXTRA_CA_CERTS for custom certificates",
+        ));
+    }
+
+    diagnostics
+}
+
+/// Check the user's shell environment for settings known to break opencode
+#[tauri::command]
+pub fn diagnose_opencode_shell_env() -> Vec<EnvDiagnostic> {
+    diagnose_shell_env(&get_shell_env())
+}
+
+/// The resolved environment at a point in time, secrets masked, for bug reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvSnapshot {
+    /// RFC 3339
+    pub taken_at: String,
+    pub variables: BTreeMap<String, String>,
+}
+
+/// A variable that differs between two snapshots
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum EnvDiff {
+    Added { name: String, value: String },
+    Removed { name: String, value: String },
+    Changed { name: String, before: String, after: String },
+}
+
+/// Snapshot of an environment, values of secret variables are masked
+pub fn snapshot_env(env: &HashMap<String, String>, taken_at: String) -> EnvSnapshot {
+    let variables = env
+        .iter()
+        .map(|(name, value)| {
+            let value = if crate::coding::redact::is_secret_key(name) && !value.is_empty() {
+                crate::coding::redact::REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    EnvSnapshot { taken_at, variables }
+}
+
+/// Variables added, removed or changed from `a` to `b`, by name
+/// Masked secrets compare equal, so a changed secret value is not reported
+pub fn diff_snapshots(a: &EnvSnapshot, b: &EnvSnapshot) -> Vec<EnvDiff> {
+    let mut diffs = Vec::new();
+    for (name, before) in &a.variables {
+        match b.variables.get(name) {
+            None => diffs.push(EnvDiff::Removed {
+                name: name.clone(),
+                value: before.clone(),
+            }),
+            Some(after) if after != before => diffs.push(EnvDiff::Changed {
+                name: name.clone(),
+                before: before.clone(),
+                after: after.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (name, value) in &b.variables {
+        if !a.variables.contains_key(name) {
+            diffs.push(EnvDiff::Added {
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+    diffs.sort_by(|x, y| env_diff_name(x).cmp(env_diff_name(y)));
+    diffs
+}
+
+fn env_diff_name(diff: &EnvDiff) -> &str {
+    match diff {
+        EnvDiff::Added { name, .. } | EnvDiff::Removed { name, .. } | EnvDiff::Changed { name, .. } => name,
+    }
+}
+
+/// Snapshot the user's shell environment (process env + shell config exports)
+#[tauri::command]
+pub fn snapshot_shell_env() -> EnvSnapshot {
+    snapshot_env(&get_shell_env(), chrono::Local::now().to_rfc3339())
+}
+
+/// Compare two snapshots, e.g. ours with one attached to a bug report
+#[tauri::command]
+pub fn diff_env_snapshots(a: EnvSnapshot, b: EnvSnapshot) -> Vec<EnvDiff> {
+    diff_snapshots(&a, &b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,5 +468,57 @@ mod tests {
             None
         );
     }
-}
 
+    #[test]
+    fn test_diagnose_empty_path() {
+        let env = HashMap::from([
+            ("PATH".to_string(), "".to_string()),
+            ("HOME".to_string(), "/home/user".to_string()),
+            ("NODE_OPTIONS".to_string(), "--max-old-space-size=4096 --inspect=9229".to_string()),
+        ]);
+
+        let diagnostics = diagnose_shell_env(&env);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].variable, "PATH");
+        assert_eq!(diagnostics[0].severity, EnvDiagnosticSeverity::Error);
+        assert_eq!(diagnostics[1].variable, "NODE_OPTIONS");
+        assert!(diagnostics[1].message.contains("'--inspect'"));
+    }
+
+    #[test]
+    fn test_diff_env_snapshots() {
+        let before = snapshot_env(
+            &HashMap::from([
+                ("PATH".to_string(), "/usr/bin".to_string()),
+                ("HOME".to_string(), "/home/user".to_string()),
+                ("OPENAI_API_KEY".to_string(), "sk-old".to_string()),
+            ]),
+            "2026-01-01T00:00:00+00:00".to_string(),
+        );
+        let after = snapshot_env(
+            &HashMap::from([
+                ("PATH".to_string(), "/opt/node/bin:/usr/bin".to_string()),
+                ("HOME".to_string(), "/home/user".to_string()),
+                ("OPENAI_API_KEY".to_string(), "sk-new".to_string()),
+                ("NODE_OPTIONS".to_string(), "--inspect".to_string()),
+            ]),
+            "2026-01-02T00:00:00+00:00".to_string(),
+        );
+        assert_eq!(after.variables["OPENAI_API_KEY"], crate::coding::redact::REDACTED);
+
+        assert_eq!(
+            diff_snapshots(&before, &after),
+            vec![
+                EnvDiff::Added {
+                    name: "NODE_OPTIONS".to_string(),
+                    value: "--inspect".to_string(),
+                },
+                EnvDiff::Changed {
+                    name: "PATH".to_string(),
+                    before: "/usr/bin".to_string(),
+                    after: "/opt/node/bin:/usr/bin".to_string(),
+                },
+            ]
+        );
+    }
+}
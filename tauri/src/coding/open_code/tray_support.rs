@@ -51,7 +51,7 @@ pub struct TrayModelData {
 pub async fn get_opencode_tray_model_data<R: Runtime>(
     app: &AppHandle<R>,
 ) -> Result<(TrayModelData, TrayModelData), String> {
-    let result = read_opencode_config(app.state()).await?;
+    let result = read_opencode_config(app.state(), app.clone()).await?;
     let config = extract_config_or_default(result);
 
     let current_main = config.model.as_ref().map(|s: &String| s.as_str()).unwrap_or("");
@@ -158,7 +158,7 @@ pub async fn apply_opencode_model<R: Runtime>(
     let model_id = parts[1];
 
     // Read current config
-    let result = read_opencode_config(app.state()).await?;
+    let result = read_opencode_config(app.state(), app.clone()).await?;
     let mut config = extract_config_or_default(result);
 
     // Build new config value: "provider_id/model_id" format
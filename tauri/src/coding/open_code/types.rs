@@ -107,6 +107,12 @@ pub struct OpenCodeProvider {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub options: Option<OpenCodeProviderOptions>,
     pub models: HashMap<String, OpenCodeModel>,
+    /// Only these model ids are offered, empty means all (opencode's allow list)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub whitelist: Option<Vec<String>>,
+    /// These model ids are never offered, wins over `whitelist` (opencode's deny list)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blacklist: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,6 +141,10 @@ pub fn run() {
 
                 app.manage(db_state);
             });
+
+            app.manage(coding::open_code::provider_health::ProviderMonitorState::default());
+            app.manage(coding::open_code::idle_prefetch::IdlePrefetchState::default());
+            app.manage(coding::oh_my_opencode::dependencies::DependencyGraphCache::default());
             
             // Create system tray
             tray::create_tray(&app_handle).expect("Failed to create system tray");
@@ -365,12 +369,26 @@ pub fn run() {
             coding::open_code::get_opencode_common_config,
             coding::open_code::save_opencode_common_config,
             coding::open_code::fetch_provider_models,
+            coding::open_code::get_sample_provider_models,
             coding::open_code::get_opencode_free_models,
             coding::open_code::get_provider_models,
             coding::open_code::get_opencode_unified_models,
             coding::open_code::get_opencode_auth_providers,
             coding::open_code::get_opencode_auth_config_path,
             coding::open_code::backup_opencode_config,
+            coding::open_code::provider_health::start_provider_monitor,
+            coding::open_code::provider_health::stop_provider_monitor,
+            coding::open_code::idle_prefetch::start_idle_prefetch,
+            coding::open_code::idle_prefetch::stop_idle_prefetch,
+            coding::open_code::idle_prefetch::report_user_activity,
+            coding::open_code::idle_prefetch::get_prefetched_provider_models,
+            coding::open_code::shell_env::diagnose_opencode_shell_env,
+            coding::open_code::shell_env::snapshot_shell_env,
+            coding::open_code::shell_env::diff_env_snapshots,
+            coding::open_code::provider_id::get_canonical_provider_id,
+            coding::open_code::provider_diff::diff_opencode_providers,
+            coding::metrics::get_load_metrics,
+            coding::debug_bundle::export_debug_bundle,
             // Codex
             coding::codex::get_codex_config_dir_path,
             coding::codex::get_codex_config_file_path,
@@ -394,11 +412,71 @@ pub fn run() {
             coding::oh_my_opencode::update_oh_my_opencode_config,
             coding::oh_my_opencode::delete_oh_my_opencode_config,
             coding::oh_my_opencode::apply_oh_my_opencode_config,
+            coding::oh_my_opencode::list_oh_my_opencode_apply_log,
+            coding::oh_my_opencode::revert_oh_my_opencode_last_apply,
+            coding::oh_my_opencode::compact_oh_my_opencode_backups,
+            coding::oh_my_opencode::apply_oh_my_opencode_config_to_targets,
+            coding::oh_my_opencode::apply_oh_my_opencode_global_only,
+            coding::oh_my_opencode::clear_oh_my_opencode_disabled,
             coding::oh_my_opencode::reorder_oh_my_opencode_configs,
+            coding::oh_my_opencode::merge_oh_my_opencode_duplicate_configs,
+            coding::oh_my_opencode::migrate_oh_my_opencode_record,
+            coding::oh_my_opencode::normalize_oh_my_opencode_timestamps,
             coding::oh_my_opencode::get_oh_my_opencode_config_path_info,
             coding::oh_my_opencode::get_oh_my_opencode_global_config,
             coding::oh_my_opencode::save_oh_my_opencode_global_config,
             coding::oh_my_opencode::check_oh_my_opencode_config_exists,
+            coding::oh_my_opencode::get_oh_my_opencode_agent_groups,
+            coding::oh_my_opencode::set_oh_my_opencode_agent_group,
+            coding::oh_my_opencode::set_oh_my_opencode_agents_model,
+            coding::oh_my_opencode::bulk_edit_oh_my_opencode_agents,
+            coding::oh_my_opencode::sanitize_oh_my_opencode_agent_prompts,
+            coding::oh_my_opencode::find_oh_my_opencode_agent_name_collisions,
+            coding::oh_my_opencode::get_oh_my_opencode_agent_content_ids,
+            coding::oh_my_opencode::import_oh_my_opencode_agents_from_dir,
+            coding::oh_my_opencode::export_oh_my_opencode_global_config,
+            coding::oh_my_opencode::import_oh_my_opencode_global_config,
+            coding::oh_my_opencode::lint_oh_my_opencode_raw_json,
+            coding::oh_my_opencode::export_oh_my_opencode_encrypted_bundle,
+            coding::oh_my_opencode::import_oh_my_opencode_encrypted_bundle,
+            coding::oh_my_opencode::import_oh_my_opencode_bundle_resolved,
+            coding::oh_my_opencode::get_oh_my_opencode_bundle_changelog,
+            coding::oh_my_opencode::import_oh_my_opencode_bundle_streaming,
+            coding::oh_my_opencode::import_oh_my_opencode_agent_markdown,
+            coding::oh_my_opencode::export_oh_my_opencode_agents_markdown,
+            coding::oh_my_opencode::get_oh_my_opencode_config_footprint,
+            coding::oh_my_opencode::get_oh_my_opencode_effective_config,
+            coding::oh_my_opencode::get_oh_my_opencode_agents_affected_by_global,
+            coding::oh_my_opencode::compute_oh_my_opencode_overlay,
+            coding::oh_my_opencode::find_oh_my_opencode_merge_conflicts,
+            coding::oh_my_opencode::merge_oh_my_opencode_with_resolutions,
+            coding::oh_my_opencode::detect_oh_my_opencode_resolution_cycles,
+            coding::oh_my_opencode::get_oh_my_opencode_agent_dependency_graph,
+            coding::oh_my_opencode::fix_oh_my_opencode_tool_names,
+            coding::oh_my_opencode::minimize_oh_my_opencode_config,
+            coding::oh_my_opencode::auto_repair_oh_my_opencode_content,
+            coding::oh_my_opencode::merge_oh_my_opencode_disabled_casings,
+            coding::oh_my_opencode::recommend_oh_my_opencode_models,
+            coding::oh_my_opencode::get_oh_my_opencode_agent_provider_matrix,
+            coding::oh_my_opencode::classify_oh_my_opencode_experimental_flags,
+            coding::oh_my_opencode::export_oh_my_opencode_self_contained,
+            coding::oh_my_opencode::export_oh_my_opencode_vcs_form,
+            coding::oh_my_opencode::import_oh_my_opencode_vcs_form,
+            coding::oh_my_opencode::rename_oh_my_opencode_mcp,
+            coding::oh_my_opencode::find_oh_my_opencode_unused_mcps,
+            coding::oh_my_opencode::disable_oh_my_opencode_mcps,
+            coding::oh_my_opencode::select_oh_my_opencode_provider,
+            coding::oh_my_opencode::find_oh_my_opencode_unused_providers,
+            coding::oh_my_opencode::validate_oh_my_opencode_config,
+            coding::oh_my_opencode::export_oh_my_opencode_validation_sarif,
+            coding::oh_my_opencode::list_oh_my_opencode_configs_with_status,
+            coding::oh_my_opencode::get_oh_my_opencode_integrity_manifest,
+            coding::oh_my_opencode::verify_oh_my_opencode_integrity,
+            coding::oh_my_opencode::preflight_oh_my_opencode_config,
+            coding::oh_my_opencode::get_oh_my_opencode_launch_env,
+            coding::oh_my_opencode::test_oh_my_opencode_mcp_tools,
+            coding::oh_my_opencode::find_oh_my_opencode_mcp_tool_collisions,
+            coding::oh_my_opencode::lint_oh_my_opencode_policy,
             // WSL Sync
             coding::wsl::wsl_detect,
             coding::wsl::wsl_check_distro,
@@ -1,19 +1,23 @@
 import React from 'react';
-import { Modal, Table, Radio, Button, Space, Typography, message, Alert, Input, Tooltip } from 'antd';
+import { Modal, Table, Radio, Button, Space, Typography, message, Alert, Input, Tooltip, Checkbox } from 'antd';
 import { CloudDownloadOutlined, ReloadOutlined, SearchOutlined, UndoOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
+import { getPrefetchedProviderModels } from '@/services/opencodeApi';
 import type { FetchModelsModalProps, FetchedModel, ApiType, FetchModelsResponse } from './types';
 
 const { Text } = Typography;
 
 const FetchModelsModal: React.FC<FetchModelsModalProps> = ({
   open,
+  providerId,
   providerName,
   baseUrl,
   apiKey,
   headers,
   sdkType,
+  allowedModels,
+  deniedModels,
   existingModelIds,
   onCancel,
   onSuccess,
@@ -30,6 +34,8 @@ const FetchModelsModal: React.FC<FetchModelsModalProps> = ({
   const [fetched, setFetched] = React.useState(false);
   const [customUrl, setCustomUrl] = React.useState('');
   const [searchText, setSearchText] = React.useState('');
+  // Development only: fill the picker with sample models instead of calling the provider
+  const [useSampleModels, setUseSampleModels] = React.useState(false);
 
   // Only show Native option for Google and Anthropic SDKs
   const supportsNative = sdkType === '@ai-sdk/google' || sdkType === '@ai-sdk/anthropic';
@@ -93,22 +99,43 @@ const FetchModelsModal: React.FC<FetchModelsModalProps> = ({
     }
   }, [open, calculatedUrl]);
 
+  // Show the list prefetched while idle right away, fetching again stays available
+  React.useEffect(() => {
+    if (!open || !providerId) return;
+    let cancelled = false;
+    getPrefetchedProviderModels(providerId)
+      .then((response) => {
+        if (cancelled || !response) return;
+        setModels(response.models);
+        setFetched(true);
+      })
+      .catch((err) => {
+        console.error('Failed to read prefetched models:', err);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [open, providerId]);
+
   // Fetch models from provider API
   const handleFetch = async () => {
     setLoading(true);
     setError(null);
 
     try {
-      const response = await invoke<FetchModelsResponse>('fetch_provider_models', {
-        request: {
-          baseUrl,
-          apiKey,
-          headers,
-          apiType,
-          sdkType,
-          customUrl, // Use custom URL instead of calculated one
-        },
-      });
+      const request = {
+        baseUrl,
+        apiKey,
+        headers,
+        apiType,
+        sdkType,
+        customUrl, // Use custom URL instead of calculated one
+        allowedModels,
+        deniedModels,
+      };
+      const response = import.meta.env.DEV && useSampleModels
+        ? await invoke<FetchModelsResponse>('get_sample_provider_models', { sdkType })
+        : await invoke<FetchModelsResponse>('fetch_provider_models', { request, providerId });
 
       setModels(response.models);
       setFetched(true);
@@ -258,6 +285,11 @@ const FetchModelsModal: React.FC<FetchModelsModalProps> = ({
           >
             {fetched ? t('opencode.fetchModels.refresh') : t('opencode.fetchModels.fetch')}
           </Button>
+          {import.meta.env.DEV && (
+            <Checkbox checked={useSampleModels} onChange={(e) => setUseSampleModels(e.target.checked)}>
+              {t('opencode.fetchModels.useSampleModels')}
+            </Checkbox>
+          )}
           <Input
             prefix={<SearchOutlined />}
             placeholder={t('opencode.fetchModels.searchPlaceholder')}
@@ -5,12 +5,27 @@
 /** API type for fetching models */
 export type ApiType = 'native' | 'openai_compat';
 
+/** Features a model supports beyond plain text chat */
+export type ModelCapability = 'tool' | 'vision';
+
+/** Context window and pricing (USD per million tokens), when known */
+export interface ModelMetadata {
+  contextWindow?: number;
+  maxOutputTokens?: number;
+  inputCost?: number;
+  outputCost?: number;
+  supportsReasoning?: boolean;
+  /** Complete list when known, absent means unknown */
+  capabilities?: ModelCapability[];
+}
+
 /** Fetched model info from API */
 export interface FetchedModel {
   id: string;
   name?: string;
   ownedBy?: string;
   created?: number;
+  metadata?: ModelMetadata;
 }
 
 /** Response from fetch models API */
@@ -22,11 +37,17 @@ export interface FetchModelsResponse {
 /** Props for FetchModelsModal component */
 export interface FetchModelsModalProps {
   open: boolean;
+  /** Provider key, shares fetched lists with the idle prefetch */
+  providerId?: string;
   providerName: string;
   baseUrl: string;
   apiKey?: string;
   headers?: Record<string, string>;
   sdkType?: string;
+  /** Provider allow list, only these model ids are shown (empty means all) */
+  allowedModels?: string[];
+  /** Provider deny list, these model ids are never shown */
+  deniedModels?: string[];
   existingModelIds: string[];
   onCancel: () => void;
   onSuccess: (selectedModels: FetchedModel[]) => void;
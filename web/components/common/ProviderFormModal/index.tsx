@@ -22,6 +22,8 @@ export interface ProviderFormValues {
   timeout?: number;
   disableTimeout?: boolean;
   setCacheKey?: boolean;
+  allowedModels?: string[];
+  deniedModels?: string[];
 }
 
 interface ProviderFormModalProps {
@@ -86,6 +88,8 @@ const ProviderFormModal: React.FC<ProviderFormModalProps> = ({
     const timeout = form.getFieldValue('timeout');
     const disableTimeout = form.getFieldValue('disableTimeout');
     const setCacheKey = form.getFieldValue('setCacheKey');
+    const allowedModels = form.getFieldValue('allowedModels');
+    const deniedModels = form.getFieldValue('deniedModels');
     
     // Check headers
     let hasHeaders = false;
@@ -106,7 +110,9 @@ const ProviderFormModal: React.FC<ProviderFormModalProps> = ({
     const hasOpenCodeAdvanced = showOpenCodeAdvanced && (
       disableTimeout === true || 
       timeout !== undefined || 
-      setCacheKey === true
+      setCacheKey === true ||
+      (allowedModels?.length ?? 0) > 0 ||
+      (deniedModels?.length ?? 0) > 0
     );
     
     return hasHeaders || hasOpenCodeAdvanced;
@@ -142,7 +148,9 @@ const ProviderFormModal: React.FC<ProviderFormModalProps> = ({
         if (!shouldExpand && showOpenCodeAdvanced) {
           shouldExpand = disableTimeout === true || 
             timeout !== undefined || 
-            setCacheKey === true;
+            setCacheKey === true ||
+            (initialValues.allowedModels?.length ?? 0) > 0 ||
+            (initialValues.deniedModels?.length ?? 0) > 0;
         }
         
         setAdvancedExpanded(shouldExpand);
@@ -398,6 +406,22 @@ const ProviderFormModal: React.FC<ProviderFormModalProps> = ({
                 >
                   <Switch />
                 </Form.Item>
+
+                {/* Model allow / deny lists, applied when fetching models */}
+                <Form.Item
+                  label={t('opencode.provider.allowedModels')}
+                  name="allowedModels"
+                  extra={<Text type="secondary" style={{ fontSize: 12 }}>{t('opencode.provider.allowedModelsHint')}</Text>}
+                >
+                  <Select mode="tags" tokenSeparators={[',', ' ']} open={false} />
+                </Form.Item>
+                <Form.Item
+                  label={t('opencode.provider.deniedModels')}
+                  name="deniedModels"
+                  extra={<Text type="secondary" style={{ fontSize: 12 }}>{t('opencode.provider.deniedModelsHint')}</Text>}
+                >
+                  <Select mode="tags" tokenSeparators={[',', ' ']} open={false} />
+                </Form.Item>
               </>
             )}
           </>
@@ -256,7 +256,9 @@ const OhMyOpenCodeConfigModal: React.FC<OhMyOpenCodeConfigModalProps> = ({
             resizable
             mode="text"
             placeholder={`{
-    "temperature": 0.5
+    "temperature": 0.5,
+    "reasoning_effort": "medium",
+    "thinking_budget": 8000
 }`}
           />
         </Form.Item>
@@ -1,7 +1,8 @@
 import React from 'react';
-import { Button, Typography, Collapse, Empty, Spin, Space, message, Modal, Alert, Tag } from 'antd';
+import { Button, Typography, Collapse, Empty, Spin, Space, message, Modal, Alert, Tag, Select } from 'antd';
 import { PlusOutlined, SettingOutlined, LinkOutlined, WarningOutlined } from '@ant-design/icons';
 import { useTranslation } from 'react-i18next';
+import { listen } from '@tauri-apps/api/event';
 import type { OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig } from '@/types/ohMyOpenCode';
 import OhMyOpenCodeConfigCard from './OhMyOpenCodeConfigCard';
 import OhMyOpenCodeConfigModal, { OhMyOpenCodeConfigFormValues } from './OhMyOpenCodeConfigModal';
@@ -43,12 +44,25 @@ const OhMyOpenCodeSettings: React.FC<OhMyOpenCodeSettingsProps> = ({
   const [editingConfig, setEditingConfig] = React.useState<OhMyOpenCodeConfig | null>(null);
   const [globalConfig, setGlobalConfig] = React.useState<OhMyOpenCodeGlobalConfig | null>(null);
   const [isCopyMode, setIsCopyMode] = React.useState(false);
+  // Applied config being deleted, it needs a config to apply instead or a forced delete
+  const [deletingAppliedConfig, setDeletingAppliedConfig] = React.useState<OhMyOpenCodeConfig | null>(null);
+  const [reassignTo, setReassignTo] = React.useState<string | undefined>(undefined);
 
   // Load configs on mount and when refresh key changes
   React.useEffect(() => {
     loadConfigs();
   }, [omoConfigRefreshKey]);
 
+  // Reload when a config is deleted elsewhere (e.g. another window)
+  React.useEffect(() => {
+    const unlisten = listen<string>('config-deleted', () => {
+      loadConfigs();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   const loadConfigs = async () => {
     setLoading(true);
     try {
@@ -82,24 +96,33 @@ const OhMyOpenCodeSettings: React.FC<OhMyOpenCodeSettingsProps> = ({
     setModalOpen(true);
   };
 
+  const deleteConfig = async (config: OhMyOpenCodeConfig, options?: { reassignTo?: string; force?: boolean }) => {
+    try {
+      await deleteOhMyOpenCodeConfig(config.id, options);
+      message.success(t('common.success'));
+      setDeletingAppliedConfig(null);
+      loadConfigs();
+      // Refresh tray menu after deleting config
+      await refreshTrayMenu();
+      if (onConfigUpdated) {
+        onConfigUpdated();
+      }
+    } catch (error) {
+      console.error('Failed to delete config:', error);
+      message.error(String(error));
+    }
+  };
+
   const handleDeleteConfig = (config: OhMyOpenCodeConfig) => {
+    if (config.isApplied) {
+      setReassignTo(configs.find((c) => c.id !== config.id)?.id);
+      setDeletingAppliedConfig(config);
+      return;
+    }
     Modal.confirm({
       title: t('common.confirm'),
       content: t('opencode.ohMyOpenCode.confirmDelete', { name: config.name }),
-      onOk: async () => {
-        try {
-          await deleteOhMyOpenCodeConfig(config.id);
-          message.success(t('common.success'));
-          loadConfigs();
-          // Refresh tray menu after deleting config
-          await refreshTrayMenu();
-          if (onConfigUpdated) {
-            onConfigUpdated();
-          }
-        } catch {
-          message.error(t('common.error'));
-        }
-      },
+      onOk: () => deleteConfig(config),
     });
   };
 
@@ -317,6 +340,45 @@ const OhMyOpenCodeSettings: React.FC<OhMyOpenCodeSettingsProps> = ({
         onSuccess={handleModalSuccess}
       />
 
+      <Modal
+        open={!!deletingAppliedConfig}
+        title={t('common.confirm')}
+        onCancel={() => setDeletingAppliedConfig(null)}
+        footer={[
+          <Button key="cancel" onClick={() => setDeletingAppliedConfig(null)}>
+            {t('common.cancel')}
+          </Button>,
+          <Button
+            key="force"
+            danger
+            onClick={() => deletingAppliedConfig && deleteConfig(deletingAppliedConfig, { force: true })}
+          >
+            {t('opencode.ohMyOpenCode.forceDelete')}
+          </Button>,
+          <Button
+            key="reassign"
+            type="primary"
+            disabled={!reassignTo}
+            onClick={() => deletingAppliedConfig && deleteConfig(deletingAppliedConfig, { reassignTo })}
+          >
+            {t('opencode.ohMyOpenCode.applyAndDelete')}
+          </Button>,
+        ]}
+      >
+        <Space direction="vertical" style={{ width: '100%' }}>
+          <Text>{t('opencode.ohMyOpenCode.confirmDeleteApplied', { name: deletingAppliedConfig?.name })}</Text>
+          <Select
+            style={{ width: '100%' }}
+            value={reassignTo}
+            onChange={setReassignTo}
+            placeholder={t('opencode.ohMyOpenCode.selectConfig')}
+            options={configs
+              .filter((c) => c.id !== deletingAppliedConfig?.id)
+              .map((c) => ({ label: c.name, value: c.id }))}
+          />
+        </Space>
+      </Modal>
+
       <OhMyOpenCodeGlobalConfigModal
         open={globalModalOpen}
         initialValues={globalConfig || undefined}
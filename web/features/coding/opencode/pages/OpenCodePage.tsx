@@ -20,7 +20,7 @@ import {
   sortableKeyboardCoordinates,
   verticalListSortingStrategy,
 } from '@dnd-kit/sortable';
-import { readOpenCodeConfigWithResult, saveOpenCodeConfig, getOpenCodeConfigPathInfo, getOpenCodeUnifiedModels, getOpenCodeAuthProviders, getOpenCodeAuthConfigPath, type ConfigPathInfo, type UnifiedModelOption, type GetAuthProvidersResponse } from '@/services/opencodeApi';
+import { readOpenCodeConfigWithResult, saveOpenCodeConfig, getOpenCodeConfigPathInfo, getOpenCodeUnifiedModels, getOpenCodeAuthProviders, getOpenCodeAuthConfigPath, startIdlePrefetch, stopIdlePrefetch, reportUserActivity, type ConfigPathInfo, type UnifiedModelOption, type GetAuthProvidersResponse, type PrefetchTarget } from '@/services/opencodeApi';
 import { listOhMyOpenCodeConfigs, applyOhMyOpenCodeConfig } from '@/services/ohMyOpenCodeApi';
 import { refreshTrayMenu } from '@/services/appApi';
 import type { OpenCodeConfig, OpenCodeProvider, OpenCodeModel } from '@/types/opencode';
@@ -186,6 +186,52 @@ const OpenCodePage: React.FC = () => {
     loadConfig();
   }, [loadConfig, openCodeConfigRefreshKey]);
 
+  // Prefetch the model lists of the configured providers while the user is idle
+  React.useEffect(() => {
+    if (!config?.provider) return;
+    const targets: PrefetchTarget[] = Object.entries(config.provider)
+      .filter(([, provider]) => provider.options?.baseURL)
+      .map(([providerId, provider]) => ({
+        providerId,
+        request: {
+          baseUrl: provider.options?.baseURL || '',
+          apiKey: provider.options?.apiKey,
+          headers: provider.options?.headers as Record<string, string> | undefined,
+          // Same default as the fetch models modal
+          apiType: provider.npm === '@ai-sdk/google' || provider.npm === '@ai-sdk/anthropic' ? 'native' : 'openai_compat',
+          sdkType: provider.npm,
+          allowedModels: provider.whitelist,
+          deniedModels: provider.blacklist,
+        },
+      }));
+    startIdlePrefetch(targets).catch((error) => {
+      console.error('Failed to start idle prefetch:', error);
+    });
+    return () => {
+      stopIdlePrefetch().catch((error) => {
+        console.error('Failed to stop idle prefetch:', error);
+      });
+    };
+  }, [config]);
+
+  // Any input cancels a running prefetch, reported at most once per second
+  React.useEffect(() => {
+    let lastReportedAt = 0;
+    const handleActivity = () => {
+      const now = Date.now();
+      if (now - lastReportedAt < 1000) return;
+      lastReportedAt = now;
+      reportUserActivity().catch((error) => {
+        console.error('Failed to report user activity:', error);
+      });
+    };
+    const events = ['keydown', 'mousedown', 'wheel', 'touchstart'] as const;
+    events.forEach((event) => window.addEventListener(event, handleActivity, { passive: true }));
+    return () => {
+      events.forEach((event) => window.removeEventListener(event, handleActivity));
+    };
+  }, []);
+
   // Check if oh-my-opencode plugin is enabled
   const omoPluginEnabled = config?.plugin?.some((p) => p.startsWith('oh-my-opencode')) ?? false;
 
@@ -341,6 +387,8 @@ const OpenCodePage: React.FC = () => {
       timeout: provider.options?.timeout === false ? undefined : (provider.options?.timeout as number | undefined),
       disableTimeout: provider.options?.timeout === false,
       setCacheKey: provider.options?.setCacheKey,
+      allowedModels: provider.whitelist,
+      deniedModels: provider.blacklist,
     });
     setProviderModalOpen(true);
   };
@@ -361,6 +409,8 @@ const OpenCodePage: React.FC = () => {
       timeout: provider.options?.timeout === false ? undefined : (provider.options?.timeout as number | undefined),
       disableTimeout: provider.options?.timeout === false,
       setCacheKey: provider.options?.setCacheKey,
+      allowedModels: provider.whitelist,
+      deniedModels: provider.blacklist,
     });
     setProviderModalOpen(true);
   };
@@ -393,6 +443,8 @@ const OpenCodePage: React.FC = () => {
         ...(values.setCacheKey !== undefined && { setCacheKey: values.setCacheKey }),
       },
       models: currentProviderId ? config.provider[currentProviderId]?.models || {} : {},
+      ...(values.allowedModels?.length && { whitelist: values.allowedModels }),
+      ...(values.deniedModels?.length && { blacklist: values.deniedModels }),
     };
 
     await doSaveConfig({
@@ -581,6 +633,8 @@ const OpenCodePage: React.FC = () => {
       headers: provider.options?.headers as Record<string, string> | undefined,
       sdkName: provider.npm,
       existingModelIds: Object.keys(provider.models || {}),
+      allowedModels: provider.whitelist,
+      deniedModels: provider.blacklist,
     };
   }, [config, fetchModelsProviderId]);
 
@@ -1162,11 +1216,14 @@ const OpenCodePage: React.FC = () => {
       {fetchModelsProviderInfo && (
         <FetchModelsModal
           open={fetchModelsModalOpen}
+          providerId={fetchModelsProviderId}
           providerName={fetchModelsProviderInfo.name}
           baseUrl={fetchModelsProviderInfo.baseUrl}
           apiKey={fetchModelsProviderInfo.apiKey}
           headers={fetchModelsProviderInfo.headers}
           sdkType={fetchModelsProviderInfo.sdkName}
+          allowedModels={fetchModelsProviderInfo.allowedModels}
+          deniedModels={fetchModelsProviderInfo.deniedModels}
           existingModelIds={fetchModelsProviderInfo.existingModelIds}
           onCancel={() => setFetchModelsModalOpen(false)}
           onSuccess={handleFetchModelsSuccess}
@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/core';
 import type { OhMyOpenCodeConfig, OhMyOpenCodeGlobalConfig } from '@/types/ohMyOpenCode';
 import { OH_MY_OPENCODE_AGENTS } from '@/types/ohMyOpenCode';
+import type { FetchedModel } from '@/components/common/FetchModelsModal/types';
 
 // ============================================================================
 // Oh My OpenCode API
@@ -33,9 +34,17 @@ export const updateOhMyOpenCodeConfig = async (
 
 /**
  * Delete an oh-my-opencode configuration
+ * An applied config is refused unless `reassignTo` names a config to apply instead, or `force` is set
  */
-export const deleteOhMyOpenCodeConfig = async (id: string): Promise<void> => {
-    await invoke('delete_oh_my_opencode_config', { id });
+export const deleteOhMyOpenCodeConfig = async (
+    id: string,
+    options?: { reassignTo?: string; force?: boolean }
+): Promise<void> => {
+    await invoke('delete_oh_my_opencode_config', {
+        id,
+        reassignTo: options?.reassignTo ?? null,
+        force: options?.force ?? null,
+    });
 };
 
 /**
@@ -45,6 +54,87 @@ export const applyOhMyOpenCodeConfig = async (configId: string): Promise<void> =
     await invoke('apply_oh_my_opencode_config', { configId });
 };
 
+export interface OhMyOpenCodeApplyLogEntry {
+    appliedAt: string;
+    configId: string;
+    previousConfigId: string | null;
+    backupId: string | null;
+    targetPath: string;
+    /** `target` entries are applies to extra files, never reverted as the last apply */
+    kind: 'main' | 'target';
+}
+
+/**
+ * Applies recorded with a backup of the file they replaced, oldest first
+ */
+export const listOhMyOpenCodeApplyLog = async (): Promise<OhMyOpenCodeApplyLogEntry[]> => {
+    return await invoke<OhMyOpenCodeApplyLogEntry[]>('list_oh_my_opencode_apply_log');
+};
+
+/**
+ * Restore the config file exactly as it was before the last apply
+ */
+export const revertOhMyOpenCodeLastApply = async (): Promise<OhMyOpenCodeApplyLogEntry> => {
+    return await invoke<OhMyOpenCodeApplyLogEntry>('revert_oh_my_opencode_last_apply');
+};
+
+export interface OhMyOpenCodeCompactReport {
+    removedByRetention: number;
+    removedBySize: number;
+    freedBytes: number;
+    remainingBytes: number;
+    protectedBackupId: string | null;
+}
+
+/**
+ * Prune old backups: per-config retention first, then oldest first down to the byte budget
+ * The backup of the last apply is always kept
+ */
+export const compactOhMyOpenCodeBackups = async (
+    retentionPerConfig: number,
+    maxTotalBytes: number
+): Promise<OhMyOpenCodeCompactReport> => {
+    return await invoke<OhMyOpenCodeCompactReport>('compact_oh_my_opencode_backups', {
+        retentionPerConfig,
+        maxTotalBytes,
+    });
+};
+
+export interface OhMyOpenCodeTargetOutcome {
+    target: string;
+    error: string | null;
+}
+
+/**
+ * Write a config to several oh-my-opencode files, each target succeeds or fails on its own
+ */
+export const applyOhMyOpenCodeConfigToTargets = async (
+    configId: string,
+    targets: string[]
+): Promise<OhMyOpenCodeTargetOutcome[]> => {
+    return await invoke<OhMyOpenCodeTargetOutcome[]>('apply_oh_my_opencode_config_to_targets', { configId, targets });
+};
+
+export interface OhMyOpenCodeConfigDiff {
+    key: string;
+    before: unknown | null;
+    after: unknown | null;
+}
+
+/**
+ * Write only the global portions of the config file, agent sections are left untouched
+ */
+export const applyOhMyOpenCodeGlobalOnly = async (): Promise<OhMyOpenCodeConfigDiff[]> => {
+    return await invoke<OhMyOpenCodeConfigDiff[]>('apply_oh_my_opencode_global_only');
+};
+
+/**
+ * Empty the global disabled lists, locked agents stay disabled
+ */
+export const clearOhMyOpenCodeDisabled = async (): Promise<{ agents: number; mcps: number; hooks: number }> => {
+    return await invoke<{ agents: number; mcps: number; hooks: number }>('clear_oh_my_opencode_disabled');
+};
+
 /**
  * Reorder configurations
  */
@@ -52,6 +142,41 @@ export const reorderOhMyOpenCodeConfigs = async (ids: string[]): Promise<void> =
     await invoke('reorder_oh_my_opencode_configs', { ids });
 };
 
+export interface OhMyOpenCodeMergeDupReport {
+    merges: { kept: string; removed: string[]; appliedMoved: boolean }[];
+}
+
+/**
+ * Merge content-identical configs, keeping the oldest of each group
+ */
+export const mergeOhMyOpenCodeDuplicateConfigs = async (): Promise<OhMyOpenCodeMergeDupReport> => {
+    return await invoke<OhMyOpenCodeMergeDupReport>('merge_oh_my_opencode_duplicate_configs');
+};
+
+/**
+ * Migrate a stored config record to a specific schema version (debugging only, no downgrades)
+ */
+export const migrateOhMyOpenCodeRecord = async (
+    value: Record<string, unknown>,
+    targetVersion: number
+): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('migrate_oh_my_opencode_record', { value, targetVersion });
+};
+
+export interface OhMyOpenCodeTimestampChange {
+    path: string;
+    kind: 'normalized' | 'unparseable';
+    before: string;
+    after?: string;
+}
+
+/**
+ * Rewrite the timestamps of every config to UTC RFC 3339, returns the changes per config id
+ */
+export const normalizeOhMyOpenCodeTimestamps = async (): Promise<Record<string, OhMyOpenCodeTimestampChange[]>> => {
+    return await invoke<Record<string, OhMyOpenCodeTimestampChange[]>>('normalize_oh_my_opencode_timestamps');
+};
+
 /**
  * Get config file path info
  */
@@ -67,6 +192,709 @@ export const checkOhMyOpenCodeConfigExists = async (): Promise<boolean> => {
     return await invoke<boolean>('check_oh_my_opencode_config_exists');
 };
 
+// ============================================================================
+// Oh My OpenCode Agent API
+// ============================================================================
+
+/**
+ * Get agent names grouped by UI group (ungrouped agents are under "default")
+ */
+export const getOhMyOpenCodeAgentGroups = async (configId: string): Promise<Record<string, string[]>> => {
+    return await invoke<Record<string, string[]>>('get_oh_my_opencode_agent_groups', { configId });
+};
+
+/**
+ * Set or clear the UI group of an agent
+ */
+export const setOhMyOpenCodeAgentGroup = async (
+    configId: string,
+    agentName: string,
+    group: string | null
+): Promise<OhMyOpenCodeConfig> => {
+    return await invoke<OhMyOpenCodeConfig>('set_oh_my_opencode_agent_group', { configId, agentName, group });
+};
+
+/**
+ * Set the same model on several agents, returns the number of agents changed
+ */
+export const setOhMyOpenCodeAgentsModel = async (
+    configId: string,
+    agentNames: string[],
+    model: string
+): Promise<number> => {
+    return await invoke<number>('set_oh_my_opencode_agents_model', { configId, agentNames, model });
+};
+
+/** Agents to bulk edit, `model` and `name` accept `*` and `?` wildcards */
+export interface OhMyOpenCodeAgentFilter {
+    model?: string;
+    group?: string;
+    name?: string;
+}
+
+/** Field changes for a bulk edit, a null value removes the field */
+export interface OhMyOpenCodeAgentEdit {
+    set: Record<string, unknown>;
+}
+
+/**
+ * Apply the same field changes to every unlocked agent matching the filter, returns the number changed
+ */
+export const bulkEditOhMyOpenCodeAgents = async (
+    configId: string,
+    filter: OhMyOpenCodeAgentFilter,
+    edit: OhMyOpenCodeAgentEdit
+): Promise<number> => {
+    return await invoke<number>('bulk_edit_oh_my_opencode_agents', { configId, filter, edit });
+};
+
+/**
+ * Normalize NBSP, zero-width spaces and mojibake in agent prompts (all agents when omitted)
+ * Smart quotes are only replaced with `smartQuotes`
+ * Returns the agents whose prompt changed
+ */
+export const sanitizeOhMyOpenCodeAgentPrompts = async (
+    configId: string,
+    agentNames?: string[],
+    smartQuotes?: boolean
+): Promise<string[]> => {
+    return await invoke<string[]>('sanitize_oh_my_opencode_agent_prompts', { configId, agentNames, smartQuotes });
+};
+
+/**
+ * Groups of agent names that only differ in case, whitespace or unicode form, to prompt a rename
+ */
+export const findOhMyOpenCodeAgentNameCollisions = async (configId: string): Promise<string[][]> => {
+    return await invoke<string[][]>('find_oh_my_opencode_agent_name_collisions', { configId });
+};
+
+/**
+ * Get content-based ids of agents (agent name -> id), equal ids mean identical agents
+ */
+export const getOhMyOpenCodeAgentContentIds = async (configId: string): Promise<Record<string, string>> => {
+    return await invoke<Record<string, string>>('get_oh_my_opencode_agent_content_ids', { configId });
+};
+
+export interface OhMyOpenCodeAgentDirImport {
+    agents: Record<string, Record<string, unknown>>;
+    errors: { file: string; message: string }[];
+}
+
+/**
+ * Import agents from a directory with one file per agent, keyed by file name
+ */
+export const importOhMyOpenCodeAgentsFromDir = async (dir: string): Promise<OhMyOpenCodeAgentDirImport> => {
+    return await invoke<OhMyOpenCodeAgentDirImport>('import_oh_my_opencode_agents_from_dir', { dir });
+};
+
+/**
+ * Export only the global config (disabled lists, lsp, experimental, ...), secrets redacted
+ */
+export const exportOhMyOpenCodeGlobalConfig = async (): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('export_oh_my_opencode_global_config');
+};
+
+/**
+ * Read a global config export for preview, nothing is saved
+ * Returns the record content (snake_case keys), redacted secrets stay redacted
+ */
+export const importOhMyOpenCodeGlobalConfig = async (value: unknown): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('import_oh_my_opencode_global_config', { value });
+};
+
+export interface OhMyOpenCodeRawJsonIssue {
+    kind: 'syntax' | 'duplicateKey' | 'unquotedKey';
+    severity: 'error' | 'warning' | 'info';
+    /** 1-based */
+    line: number;
+    /** 1-based, in characters */
+    column: number;
+    message: string;
+}
+
+/**
+ * Lint raw config text before importing it, issues in text order
+ */
+export const lintOhMyOpenCodeRawJson = async (raw: string): Promise<OhMyOpenCodeRawJsonIssue[]> => {
+    return await invoke<OhMyOpenCodeRawJsonIssue[]>('lint_oh_my_opencode_raw_json', { raw });
+};
+
+/**
+ * Casing of exported record keys, snakeCase by default
+ * Older bundles were camelCase, both import the same; pass camelCase for tooling that reads the old layout
+ */
+export type OhMyOpenCodeKeyCase = 'snakeCase' | 'camelCase';
+
+/**
+ * Export the global config and configs (all when configIds is omitted) as a passphrase-protected bundle
+ */
+export const exportOhMyOpenCodeEncryptedBundle = async (
+    path: string,
+    passphrase: string,
+    configIds?: string[],
+    keyCase?: OhMyOpenCodeKeyCase
+): Promise<void> => {
+    await invoke('export_oh_my_opencode_encrypted_bundle', { path, passphrase, configIds, keyCase });
+};
+
+/**
+ * Decrypt a bundle for preview, returns [globalConfig, configs]
+ */
+export const importOhMyOpenCodeEncryptedBundle = async (
+    path: string,
+    passphrase: string
+): Promise<[OhMyOpenCodeGlobalConfig, OhMyOpenCodeConfig[]]> => {
+    return await invoke<[OhMyOpenCodeGlobalConfig, OhMyOpenCodeConfig[]]>('import_oh_my_opencode_encrypted_bundle', {
+        path,
+        passphrase,
+    });
+};
+
+export type OhMyOpenCodeConflictResolution = 'skip' | 'overwrite' | 'renameImported' | 'keepBoth';
+
+export interface OhMyOpenCodeImportOutcome {
+    sourceId: string;
+    /** Stored id, null when skipped */
+    id: string | null;
+    name: string;
+    action: 'created' | 'skipped' | 'overwritten' | 'renamed' | 'keptBoth';
+}
+
+export interface OhMyOpenCodeImportResult {
+    configs: OhMyOpenCodeConfig[];
+    outcomes: OhMyOpenCodeImportOutcome[];
+}
+
+/**
+ * Import the configs of a bundle, id collisions are handled by `resolution`
+ */
+export const importOhMyOpenCodeBundleResolved = async (
+    bundle: unknown,
+    resolution: OhMyOpenCodeConflictResolution
+): Promise<OhMyOpenCodeImportResult> => {
+    return await invoke<OhMyOpenCodeImportResult>('import_oh_my_opencode_bundle_resolved', { bundle, resolution });
+};
+
+export interface OhMyOpenCodeConfigRef {
+    id: string;
+    name: string;
+}
+
+export interface OhMyOpenCodeBundleChangelog {
+    added: OhMyOpenCodeConfigRef[];
+    removed: OhMyOpenCodeConfigRef[];
+    /** Configs in both bundles, one summary line per change */
+    changed: (OhMyOpenCodeConfigRef & { summary: string[] })[];
+    global: OhMyOpenCodeConfigDiff[];
+}
+
+/**
+ * Changelog between two versions of a bundle, for release notes
+ */
+export const getOhMyOpenCodeBundleChangelog = async (
+    oldBundle: unknown,
+    newBundle: unknown
+): Promise<OhMyOpenCodeBundleChangelog> => {
+    return await invoke<OhMyOpenCodeBundleChangelog>('get_oh_my_opencode_bundle_changelog', { oldBundle, newBundle });
+};
+
+/** Payload of the `oh-my-opencode-import-progress` event */
+export interface OhMyOpenCodeImportProgress {
+    imported: number;
+    bytesRead: number;
+    totalBytes: number;
+}
+
+/** Result of a streaming import, the global config is not saved */
+export interface OhMyOpenCodeStreamImportSummary {
+    version: number;
+    imported: number;
+    global: OhMyOpenCodeGlobalConfig;
+}
+
+/**
+ * Import a bundle file of any size, configs are stored one at a time
+ * Listen to `oh-my-opencode-import-progress` for progress, returns the number of imported configs
+ * and the bundle's global config, save it with `saveOhMyOpenCodeGlobalConfig` to apply it
+ */
+export const importOhMyOpenCodeBundleStreaming = async (path: string): Promise<OhMyOpenCodeStreamImportSummary> => {
+    return await invoke<OhMyOpenCodeStreamImportSummary>('import_oh_my_opencode_bundle_streaming', { path });
+};
+
+/**
+ * Import an opencode markdown agent file, returns [name, agent]
+ * The name falls back to the file name when the frontmatter has no `name`
+ */
+export const importOhMyOpenCodeAgentMarkdown = async (path: string): Promise<[string, Record<string, unknown>]> => {
+    return await invoke<[string, Record<string, unknown>]>('import_oh_my_opencode_agent_markdown', { path });
+};
+
+/**
+ * Write the agents of a config as opencode markdown agent files into a directory
+ * Returns the written file paths
+ */
+export const exportOhMyOpenCodeAgentsMarkdown = async (configId: string, dir: string): Promise<string[]> => {
+    return await invoke<string[]>('export_oh_my_opencode_agents_markdown', { configId, dir });
+};
+
+export interface OhMyOpenCodeFootprintReport {
+    agents: { name: string; promptTokens: number }[];
+    promptTokens: number;
+    mcpServers: number;
+    lspServers: number;
+    overheadTokens: number;
+    totalTokens: number;
+    threshold: number;
+    warnings: string[];
+}
+
+/**
+ * Estimate the token footprint of a config (agent prompts + MCP overhead)
+ */
+export const getOhMyOpenCodeConfigFootprint = async (
+    configId: string,
+    threshold?: number
+): Promise<OhMyOpenCodeFootprintReport> => {
+    return await invoke<OhMyOpenCodeFootprintReport>('get_oh_my_opencode_config_footprint', { configId, threshold });
+};
+
+/**
+ * Preview the final config oh-my-opencode loads (inheritance, prompt refs, project config,
+ * disabled entries and env substitution applied)
+ */
+export const getOhMyOpenCodeEffectiveConfig = async (
+    configId: string,
+    projectDir?: string
+): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('get_oh_my_opencode_effective_config', { configId, projectDir });
+};
+
+/**
+ * Agents that don't set `field` themselves and would follow a change of the global default
+ */
+export const getOhMyOpenCodeAgentsAffectedByGlobal = async (configId: string, field: string): Promise<string[]> => {
+    return await invoke<string[]>('get_oh_my_opencode_agents_affected_by_global', { configId, field });
+};
+
+export interface OhMyOpenCodeOverlayForChange {
+    overlay: unknown;
+    /** Dotted paths removed in desired, an overlay cannot remove keys */
+    unexpressible: string[];
+}
+
+/**
+ * Smallest overlay that turns base into desired when deep-merged
+ */
+export const computeOhMyOpenCodeOverlay = async (
+    base: unknown,
+    desired: unknown
+): Promise<OhMyOpenCodeOverlayForChange> => {
+    return await invoke<OhMyOpenCodeOverlayForChange>('compute_oh_my_opencode_overlay', { base, desired });
+};
+
+export interface OhMyOpenCodeMergeConflict {
+    path: string;
+    base: unknown;
+    overlay: unknown;
+}
+
+export type OhMyOpenCodeMergeChoice =
+    | { choice: 'base' }
+    | { choice: 'overlay' }
+    | { choice: 'custom'; value: unknown };
+
+/**
+ * Paths where base and overlay set different values
+ */
+export const findOhMyOpenCodeMergeConflicts = async (
+    base: unknown,
+    overlay: unknown
+): Promise<OhMyOpenCodeMergeConflict[]> => {
+    return await invoke<OhMyOpenCodeMergeConflict[]>('find_oh_my_opencode_merge_conflicts', { base, overlay });
+};
+
+/**
+ * Merge overlay into base with each conflict resolved by path, unresolved ones take the overlay unless strict
+ */
+export const mergeOhMyOpenCodeWithResolutions = async (
+    base: unknown,
+    overlay: unknown,
+    resolutions: Record<string, OhMyOpenCodeMergeChoice>,
+    strict?: boolean
+): Promise<unknown> => {
+    return await invoke<unknown>('merge_oh_my_opencode_with_resolutions', { base, overlay, resolutions, strict });
+};
+
+/**
+ * Cycles across extends and prompt_ref, path nodes are `agent:NAME` / `prompt:NAME`
+ */
+export const detectOhMyOpenCodeResolutionCycles = async (
+    configId: string
+): Promise<{ path: string[]; message: string }[]> => {
+    return await invoke<{ path: string[]; message: string }[]>('detect_oh_my_opencode_resolution_cycles', { configId });
+};
+
+/**
+ * Build a minimal, redacted config containing only one agent, for bug reports
+ */
+export const minimizeOhMyOpenCodeConfig = async (
+    configId: string,
+    keepAgent: string
+): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('minimize_oh_my_opencode_config', { configId, keepAgent });
+};
+
+export interface OhMyOpenCodeRepairAction {
+    kind: 'trimModelId' | 'dedupDisabled' | 'pruneDisabled' | 'stripPromptChars' | 'normalizeBaseUrl';
+    /** Dotted path into the content */
+    path: string;
+    message: string;
+}
+
+/**
+ * Apply safe fixes (model id whitespace, duplicate or unknown disabled entries, prompt control
+ * characters, base URL slashes) to oh-my-opencode.json content, returns the fixed content and the fixes made
+ */
+export const autoRepairOhMyOpenCodeContent = async (
+    content: Record<string, unknown>
+): Promise<[Record<string, unknown>, OhMyOpenCodeRepairAction[]]> => {
+    return await invoke<[Record<string, unknown>, OhMyOpenCodeRepairAction[]]>('auto_repair_oh_my_opencode_content', { content });
+};
+
+export interface OhMyOpenCodeDisabledMerge {
+    /** snake_case key of the list */
+    list: string;
+    /** Entries as written, from both keys */
+    from: string[];
+    into: string;
+}
+
+/**
+ * Merge `disabled_*` lists written in both snake_case and camelCase into the snake_case key,
+ * deduped case-insensitively and spelled like the agent / MCP keys, returns the merged content
+ */
+export const mergeOhMyOpenCodeDisabledCasings = async (
+    content: Record<string, unknown>
+): Promise<[Record<string, unknown>, OhMyOpenCodeDisabledMerge[]]> => {
+    return await invoke<[Record<string, unknown>, OhMyOpenCodeDisabledMerge[]]>('merge_oh_my_opencode_disabled_casings', {
+        content,
+    });
+};
+
+export interface OhMyOpenCodeModelRecommendation {
+    modelId: string;
+    score: number;
+    reasons: string[];
+}
+
+/**
+ * Ranked shortlist of models for an agent, advisory only
+ * @param roleHint e.g. "reasoning" or "fast", biases the ranking
+ */
+export const recommendOhMyOpenCodeModels = async (
+    agentName: string,
+    agent: Record<string, unknown>,
+    available: FetchedModel[],
+    roleHint?: string
+): Promise<OhMyOpenCodeModelRecommendation[]> => {
+    return await invoke<OhMyOpenCodeModelRecommendation[]>('recommend_oh_my_opencode_models', {
+        agentName,
+        agent,
+        available,
+        roleHint: roleHint ?? null,
+    });
+};
+
+export interface OhMyOpenCodeMatrixProvider {
+    id: string;
+    allowedModels?: string[];
+    deniedModels?: string[];
+}
+
+export type OhMyOpenCodeMatrixCell =
+    | { status: 'supported' }
+    | { status: 'unsupported'; reason: string }
+    | { status: 'unknown' };
+
+export interface OhMyOpenCodeAgentProviderMatrix {
+    agents: string[];
+    providers: string[];
+    /** cells[agent][provider] */
+    cells: OhMyOpenCodeMatrixCell[][];
+}
+
+/**
+ * Which agents of a config would work on which provider
+ * @param models fetched model list per provider id, providers without one are unknown
+ */
+export const getOhMyOpenCodeAgentProviderMatrix = async (
+    configId: string,
+    providers: OhMyOpenCodeMatrixProvider[],
+    models: Record<string, FetchedModel[]>
+): Promise<OhMyOpenCodeAgentProviderMatrix> => {
+    return await invoke<OhMyOpenCodeAgentProviderMatrix>('get_oh_my_opencode_agent_provider_matrix', {
+        configId,
+        providers,
+        models,
+    });
+};
+
+export type OhMyOpenCodeFlagClassification = {
+    flag: string;
+    status: 'active' | 'unknown' | 'deprecated';
+};
+
+/**
+ * Which experimental flags of the global config the oh-my-opencode schema lists
+ */
+export const classifyOhMyOpenCodeExperimentalFlags = async (): Promise<OhMyOpenCodeFlagClassification[]> => {
+    return await invoke<OhMyOpenCodeFlagClassification[]>('classify_oh_my_opencode_experimental_flags');
+};
+
+/**
+ * Export a config with extends, prompt refs, config env and referenced global sections inlined (secrets redacted)
+ */
+export const exportOhMyOpenCodeSelfContained = async (
+    configId: string,
+    keyCase?: OhMyOpenCodeKeyCase
+): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('export_oh_my_opencode_self_contained', { configId, keyCase });
+};
+
+export interface OhMyOpenCodeVcsExport {
+    /** Canonical JSON to commit */
+    content: string;
+    /** Env name -> secret value, kept out of version control */
+    secrets: Record<string, string>;
+}
+
+/**
+ * Export a config in its canonical form for version control
+ */
+export const exportOhMyOpenCodeVcsForm = async (configId: string): Promise<OhMyOpenCodeVcsExport> => {
+    return await invoke<OhMyOpenCodeVcsExport>('export_oh_my_opencode_vcs_form', { configId });
+};
+
+/**
+ * Rebuild a config from its VCS form and secrets for preview
+ */
+export const importOhMyOpenCodeVcsForm = async (
+    content: string,
+    secrets: Record<string, string>
+): Promise<OhMyOpenCodeConfig> => {
+    return await invoke<OhMyOpenCodeConfig>('import_oh_my_opencode_vcs_form', { content, secrets });
+};
+
+/**
+ * Rename an MCP server, updating disabled_mcps and agent tool references
+ * Returns the number of updated entries
+ */
+export const renameOhMyOpenCodeMcp = async (oldName: string, newName: string): Promise<number> => {
+    return await invoke<number>('rename_oh_my_opencode_mcp', { oldName, newName });
+};
+
+/**
+ * MCP servers of a config that no enabled agent uses
+ */
+export const findOhMyOpenCodeUnusedMcps = async (configId: string): Promise<string[]> => {
+    return await invoke<string[]>('find_oh_my_opencode_unused_mcps', { configId });
+};
+
+/**
+ * Add MCP servers to the global disabled_mcps, returns the number newly disabled
+ */
+export const disableOhMyOpenCodeMcps = async (names: string[]): Promise<number> => {
+    return await invoke<number>('disable_oh_my_opencode_mcps', { names });
+};
+
+export interface OhMyOpenCodeDependencyGraph {
+    nodes: { name: string; missing: boolean; inCycle: boolean }[];
+    edges: { from: string; to: string; inCycle: boolean }[];
+    cycles: string[][];
+}
+
+/**
+ * Agent dependency graph (depends_on) of a config, cycles are annotated
+ */
+export const getOhMyOpenCodeAgentDependencyGraph = async (configId: string): Promise<OhMyOpenCodeDependencyGraph> => {
+    return await invoke<OhMyOpenCodeDependencyGraph>('get_oh_my_opencode_agent_dependency_graph', { configId });
+};
+
+/**
+ * Rename misspelled tool keys in every agent of a config (wrong name -> correct name), e.g. from
+ * accepted "did you mean" suggestions of the tool-name validation rule
+ */
+export const fixOhMyOpenCodeToolNames = async (configId: string, fixes: Record<string, string>): Promise<number> => {
+    return await invoke<number>('fix_oh_my_opencode_tool_names', { configId, fixes });
+};
+
+/**
+ * Simulate failover: the provider that would serve a model given provider health (id -> up)
+ * Returns null when every provider in the fallback chain is down
+ */
+export const selectOhMyOpenCodeProvider = async (
+    modelId: string,
+    health: Record<string, boolean>
+): Promise<string | null> => {
+    return await invoke<string | null>('select_oh_my_opencode_provider', { modelId, health });
+};
+
+/**
+ * OpenCode providers no config or default model uses, a cleanup suggestion only
+ */
+export const findOhMyOpenCodeUnusedProviders = async (): Promise<string[]> => {
+    return await invoke<string[]>('find_oh_my_opencode_unused_providers');
+};
+
+// ============================================================================
+// Oh My OpenCode Validation API
+// ============================================================================
+
+export interface OhMyOpenCodeValidationIssue {
+    rule: string;
+    severity: 'error' | 'warning' | 'info';
+    path: string;
+    message: string;
+}
+
+/**
+ * Run all validation rules on a config, errors first
+ */
+export const validateOhMyOpenCodeConfig = async (configId: string): Promise<OhMyOpenCodeValidationIssue[]> => {
+    return await invoke<OhMyOpenCodeValidationIssue[]>('validate_oh_my_opencode_config', { configId });
+};
+
+/**
+ * Validate every config and return the issues as a SARIF 2.1.0 log
+ */
+export const exportOhMyOpenCodeValidationSarif = async (): Promise<Record<string, unknown>> => {
+    return await invoke<Record<string, unknown>>('export_oh_my_opencode_validation_sarif');
+};
+
+export interface OhMyOpenCodeValidationSummary {
+    validatedAt: string;
+    fingerprint: string;
+    errors: number;
+    warnings: number;
+    infos: number;
+}
+
+/**
+ * List configs with their validation status, unchanged configs reuse the stored result
+ */
+export const listOhMyOpenCodeConfigsWithStatus = async (): Promise<
+    (OhMyOpenCodeConfig & { lastValidation: OhMyOpenCodeValidationSummary })[]
+> => {
+    return await invoke<(OhMyOpenCodeConfig & { lastValidation: OhMyOpenCodeValidationSummary })[]>(
+        'list_oh_my_opencode_configs_with_status'
+    );
+};
+
+export interface OhMyOpenCodeIntegrityManifest {
+    createdAt: string;
+    global: string;
+    /** Config id -> checksum */
+    configs: Record<string, string>;
+}
+
+export interface OhMyOpenCodeIntegrityViolation {
+    /** Config id, or 'global' */
+    id: string;
+    kind: 'modified' | 'added' | 'removed';
+}
+
+/**
+ * Checksums of the global config and every config, formatting changes don't affect them
+ */
+export const getOhMyOpenCodeIntegrityManifest = async (): Promise<OhMyOpenCodeIntegrityManifest> => {
+    return await invoke<OhMyOpenCodeIntegrityManifest>('get_oh_my_opencode_integrity_manifest');
+};
+
+/**
+ * Configs modified, added or removed since the manifest was taken
+ */
+export const verifyOhMyOpenCodeIntegrity = async (
+    manifest: OhMyOpenCodeIntegrityManifest
+): Promise<OhMyOpenCodeIntegrityViolation[]> => {
+    return await invoke<OhMyOpenCodeIntegrityViolation[]>('verify_oh_my_opencode_integrity', { manifest });
+};
+
+export interface OhMyOpenCodeRequirement {
+    kind: 'mcp' | 'lsp';
+    name: string;
+    binary: string;
+    resolved: string | null;
+    installHint?: string;
+}
+
+/**
+ * Check that the MCP commands and LSP binaries of a config are installed (resolved against the shell PATH)
+ */
+export const preflightOhMyOpenCodeConfig = async (
+    configId: string
+): Promise<{ requirements: OhMyOpenCodeRequirement[] }> => {
+    return await invoke<{ requirements: OhMyOpenCodeRequirement[] }>('preflight_oh_my_opencode_config', { configId });
+};
+
+/**
+ * Environment opencode runs with for a config, or one of its MCP servers with `mcpServer`
+ * Base shell env < config env < server env, secret values are masked
+ */
+export const getOhMyOpenCodeLaunchEnv = async (
+    configId: string,
+    mcpServer?: string
+): Promise<Record<string, string>> => {
+    return await invoke<Record<string, string>>('get_oh_my_opencode_launch_env', { configId, mcpServer });
+};
+
+/**
+ * Start (or connect to) one MCP server of a config and list its tools
+ */
+export const testOhMyOpenCodeMcpTools = async (configId: string, mcpName: string): Promise<string[]> => {
+    return await invoke<string[]>('test_oh_my_opencode_mcp_tools', { configId, mcpName });
+};
+
+export interface OhMyOpenCodeToolCollision {
+    tool: string;
+    servers: string[];
+}
+
+/**
+ * Tool names exposed by more than one enabled MCP server, servers that cannot be probed are skipped
+ */
+export const findOhMyOpenCodeMcpToolCollisions = async (configId: string): Promise<OhMyOpenCodeToolCollision[]> => {
+    return await invoke<OhMyOpenCodeToolCollision[]>('find_oh_my_opencode_mcp_tool_collisions', { configId });
+};
+
+// ============================================================================
+// Oh My OpenCode Policy API
+// ============================================================================
+
+export type OhMyOpenCodePolicyRule =
+    | { id: string; type: 'requiredAgent'; agent: string }
+    | { id: string; type: 'forbiddenProvider'; provider: string }
+    | { id: string; type: 'requiredSetting'; path: string; value?: unknown }
+    | { id: string; type: 'maxAgents'; max: number };
+
+export interface OhMyOpenCodePolicyViolation {
+    ruleId: string;
+    path: string;
+    message: string;
+}
+
+/**
+ * Lint a config against policy rules, call before applying
+ */
+export const lintOhMyOpenCodePolicy = async (
+    configId: string,
+    rules: OhMyOpenCodePolicyRule[]
+): Promise<OhMyOpenCodePolicyViolation[]> => {
+    return await invoke<OhMyOpenCodePolicyViolation[]>('lint_oh_my_opencode_policy', {
+        configId,
+        rules: { rules },
+    });
+};
+
 // ============================================================================
 // Oh My OpenCode Global Config API
 // ============================================================================
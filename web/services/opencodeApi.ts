@@ -5,7 +5,8 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { OpenCodeConfig } from '@/types/opencode';
+import type { OpenCodeConfig, OpenCodeProvider } from '@/types/opencode';
+import type { FetchModelsResponse } from '@/components/common/FetchModelsModal/types';
 
 /**
  * Configuration path information
@@ -208,3 +209,212 @@ export const getOpenCodeAuthProviders = async (): Promise<GetAuthProvidersRespon
 export const getOpenCodeAuthConfigPath = async (): Promise<string> => {
   return await invoke<string>('get_opencode_auth_config_path');
 };
+
+// ============================================================================
+// Provider Health Monitor API
+// ============================================================================
+
+/**
+ * Provider checked by the health monitor
+ */
+export interface ProviderHealthTarget {
+  providerId: string;
+  request: {
+    baseUrl: string;
+    apiKey?: string;
+    headers?: Record<string, string>;
+    apiType: 'openai_compat' | 'native';
+    sdkType?: string;
+    customUrl?: string;
+    allowedModels?: string[];
+    deniedModels?: string[];
+  };
+}
+
+/**
+ * Health of a single provider, `transition` is set when the status changed
+ */
+export interface ProviderHealth {
+  providerId: string;
+  up: boolean;
+  latencyMs?: number;
+  error?: string;
+  transition?: 'went_down' | 'recovered';
+}
+
+/**
+ * Payload of the `provider-health` event
+ */
+export interface ProviderHealthEvent {
+  checkedAt: string;
+  providers: ProviderHealth[];
+}
+
+/**
+ * Start (or restart) the provider monitor, results are emitted as `provider-health` events
+ */
+export const startProviderMonitor = async (
+  providers: ProviderHealthTarget[],
+  intervalSecs: number
+): Promise<void> => {
+  await invoke('start_provider_monitor', { providers, intervalSecs });
+};
+
+/**
+ * Stop the provider monitor
+ */
+export const stopProviderMonitor = async (): Promise<void> => {
+  await invoke('stop_provider_monitor');
+};
+
+// ============================================================================
+// Idle Prefetch API
+// ============================================================================
+
+/**
+ * Provider whose model list is refreshed while the user is idle
+ */
+export type PrefetchTarget = ProviderHealthTarget;
+
+/**
+ * Payload of the `prefetch-progress` event
+ */
+export type PrefetchProgress = { providerId: string } & (
+  | { status: 'started' | 'rateLimited' | 'cancelled' }
+  | { status: 'done'; models: number }
+  | { status: 'failed'; error: string }
+);
+
+/**
+ * Start (or restart) prefetching stale model lists after `idleSecs` without activity
+ */
+export const startIdlePrefetch = async (providers: PrefetchTarget[], idleSecs?: number): Promise<void> => {
+  await invoke('start_idle_prefetch', { providers, idleSecs });
+};
+
+/**
+ * Stop prefetching, already prefetched lists are kept
+ */
+export const stopIdlePrefetch = async (): Promise<void> => {
+  await invoke('stop_idle_prefetch');
+};
+
+/**
+ * Report user input, cancels a running prefetch and restarts the idle period
+ */
+export const reportUserActivity = async (): Promise<void> => {
+  await invoke('report_user_activity');
+};
+
+/**
+ * Prefetched model list of a provider, null when not prefetched yet
+ */
+export const getPrefetchedProviderModels = async (providerId: string): Promise<FetchModelsResponse | null> => {
+  return await invoke<FetchModelsResponse | null>('get_prefetched_provider_models', { providerId });
+};
+
+// ============================================================================
+// Shell Environment Diagnostics API
+// ============================================================================
+
+/**
+ * Problem found in the shell environment, with a suggested fix
+ */
+export interface EnvDiagnostic {
+  variable: string;
+  severity: 'error' | 'warning';
+  message: string;
+  remediation: string;
+}
+
+/**
+ * Check the shell environment (process env + shell config exports) for settings that break opencode
+ */
+export const diagnoseOpenCodeShellEnv = async (): Promise<EnvDiagnostic[]> => {
+  return await invoke<EnvDiagnostic[]>('diagnose_opencode_shell_env');
+};
+
+/**
+ * Resolved shell environment at a point in time, secret values masked
+ */
+export interface EnvSnapshot {
+  takenAt: string;
+  variables: Record<string, string>;
+}
+
+export type EnvDiff =
+  | { kind: 'added'; name: string; value: string }
+  | { kind: 'removed'; name: string; value: string }
+  | { kind: 'changed'; name: string; before: string; after: string };
+
+/**
+ * Snapshot the shell environment, e.g. to attach to a bug report
+ */
+export const snapshotShellEnv = async (): Promise<EnvSnapshot> => {
+  return await invoke<EnvSnapshot>('snapshot_shell_env');
+};
+
+/**
+ * Variables added, removed or changed from snapshot `a` to `b`
+ */
+export const diffEnvSnapshots = async (a: EnvSnapshot, b: EnvSnapshot): Promise<EnvDiff[]> => {
+  return await invoke<EnvDiff[]>('diff_env_snapshots', { a, b });
+};
+
+/**
+ * Canonical id of a provider, e.g. `OpenAI` and `open-ai` both become `openai`
+ */
+export const getCanonicalProviderId = async (raw: string): Promise<string> => {
+  return await invoke<string>('get_canonical_provider_id', { raw });
+};
+
+// ============================================================================
+// Provider Diff API
+// ============================================================================
+
+/**
+ * Field differing between two providers with the same id, secrets are masked
+ */
+export interface ProviderFieldDiff {
+  field: string;
+  mine: unknown;
+  theirs: unknown;
+}
+
+export type ProviderDiff =
+  | { kind: 'onlyMine'; id: string }
+  | { kind: 'onlyTheirs'; id: string }
+  | { kind: 'changed'; id: string; fields: ProviderFieldDiff[] };
+
+/**
+ * Compare my providers with a teammate's, matched by provider id
+ */
+export const diffOpenCodeProviders = async (
+  mine: Record<string, OpenCodeProvider>,
+  theirs: Record<string, OpenCodeProvider>
+): Promise<ProviderDiff[]> => {
+  return await invoke<ProviderDiff[]>('diff_opencode_providers', { mine, theirs });
+};
+
+/**
+ * Timing of the load pipeline, for diagnosing slow startups
+ */
+export interface LoadMetrics {
+  configParseMs: number;
+  configCount: number;
+  shellEnvResolveMs: number;
+  modelCacheHits: number;
+  modelCacheMisses: number;
+}
+
+export const getLoadMetrics = async (): Promise<LoadMetrics> => {
+  return await invoke<LoadMetrics>('get_load_metrics');
+};
+
+/**
+ * Redacted bundle of configs, provider summaries, env, opencode version, load metrics
+ * and validation results, to attach to a bug report
+ */
+export const exportDebugBundle = async (): Promise<Record<string, unknown>> => {
+  return await invoke<Record<string, unknown>>('export_debug_bundle');
+};
@@ -10,6 +10,19 @@
  */
 export type OhMyOpenCodeAgentConfig = Record<string, unknown>;
 
+/**
+ * Reasoning effort of an agent, only written for models that support reasoning
+ */
+export type OhMyOpenCodeReasoningEffort = 'low' | 'medium' | 'high';
+
+/**
+ * Typed reasoning settings of an agent (`thinking_budget` is at least 1024 tokens)
+ */
+export interface OhMyOpenCodeAgentReasoning {
+  reasoning_effort?: OhMyOpenCodeReasoningEffort;
+  thinking_budget?: number;
+}
+
 /**
  * Sisyphus agent specific configuration - generic JSON structure
  */
@@ -42,6 +42,10 @@ export interface OpenCodeProvider {
   name?: string;
   options?: OpenCodeProviderOptions;
   models: Record<string, OpenCodeModel>;
+  /** Only these model ids are offered when fetching models, empty means all */
+  whitelist?: string[];
+  /** These model ids are never offered when fetching models, wins over whitelist */
+  blacklist?: string[];
 }
 
 /**